@@ -1,14 +1,38 @@
-use clap::{Parser, Subcommand};
+use clap::{Args, CommandFactory, Parser, Subcommand, ValueEnum};
+use clap_complete::Shell;
 use colored::Colorize;
+use embeddenator_workspace::cargo::DependencyType;
+use embeddenator_workspace::patch;
+use embeddenator_workspace::version::JOURNAL_PATH;
 use embeddenator_workspace::{
-    BumpType, HealthCheckType, HealthChecker, PatchManager, VersionManager,
+    BumpType, CodeOwners, DependencyGraph, DiscoveryNote, DiscoveryReason, FormatStyle,
+    GitDependency, GitRef, HealthCheckType, HealthChecker, HealthReport, HealthStatus,
+    PatchManager, PatchTarget, VersionChange, VersionManager, WorkspaceScanner,
+    DEFAULT_CHECK_TIMEOUT, DEFAULT_PACKAGE_PREFIX, DEFAULT_SPEC_THRESHOLD,
 };
+use std::collections::HashMap;
+use std::io::IsTerminal;
+use std::path::{Path, PathBuf};
 use std::process::{Command, ExitCode};
+use std::time::Duration;
 
 #[derive(Parser)]
 #[command(name = "embeddenator-workspace")]
 #[command(about = "Workspace management utilities for embeddenator development")]
 struct Cli {
+    /// Package-name prefix that identifies workspace packages
+    #[arg(long, global = true, default_value = DEFAULT_PACKAGE_PREFIX)]
+    prefix: String,
+    /// Cargo binary to run (e.g. a vendored cargo or `cross`)
+    #[arg(long, global = true, value_name = "PATH", default_value = "cargo")]
+    cargo: PathBuf,
+    /// Workspace root directory (default: nearest ancestor with update_all.sh
+    /// or an embeddenator/ directory, else the current directory)
+    #[arg(long, global = true, value_name = "DIR")]
+    workspace_root: Option<PathBuf>,
+    /// Disable colored output (also set by NO_COLOR or a non-terminal stdout)
+    #[arg(long, global = true)]
+    no_color: bool,
     #[command(subcommand)]
     command: Commands,
 }
@@ -22,139 +46,549 @@ enum Commands {
     /// Run mdBook
     Mdbook,
     /// Bump version across all packages
-    BumpVersion {
-        /// Bump major version (X.0.0)
-        #[arg(long, group = "bump_type")]
-        major: bool,
-        /// Bump minor version (0.X.0)
-        #[arg(long, group = "bump_type")]
-        minor: bool,
-        /// Bump patch version (0.0.X)
-        #[arg(long, group = "bump_type")]
-        patch: bool,
-        /// Bump prerelease version (0.0.0-alpha.X)
-        #[arg(long, group = "bump_type")]
-        prerelease: bool,
-        /// Show what would be changed without making changes
-        #[arg(long)]
-        dry_run: bool,
-    },
+    BumpVersion(BumpArgs),
     /// Check version consistency across packages
-    CheckVersions {
-        /// Show detailed information
+    CheckVersions(CheckVersionsArgs),
+    /// Check whether any package version already has a release tag
+    VerifyTags,
+    /// List the workspace packages with their version and path
+    List {
+        /// Print a JSON array of `{name, version, path}` objects
         #[arg(long)]
-        verbose: bool,
+        json: bool,
+        /// Only look for manifests this many directory levels below the root
+        #[arg(long, value_name = "N")]
+        depth: Option<usize>,
     },
-    /// Apply local path patches for git dependencies
-    PatchLocal {
-        /// Workspace root directory (defaults to current directory)
+    /// List the packages affected by a set of changed files
+    #[command(group(clap::ArgGroup::new("changes").required(true)))]
+    Affected {
+        /// Git ref to diff against (per package repository)
+        #[arg(long, group = "changes")]
+        changed_since: Option<String>,
+        /// File listing changed paths, one per line, relative to the workspace root
+        #[arg(long, group = "changes")]
+        changed_files: Option<PathBuf>,
+        /// List every package if any changed file is outside all packages
         #[arg(long)]
-        workspace_root: Option<String>,
-        /// Verify patches with cargo metadata
+        all_if_unowned: bool,
+        /// Only look for manifests this many directory levels below the root
+        #[arg(long, value_name = "N")]
+        depth: Option<usize>,
+    },
+    /// Print packages in publish order (dependencies first), one per line
+    Order {
+        /// Only look for manifests this many directory levels below the root
+        #[arg(long, value_name = "N")]
+        depth: Option<usize>,
+    },
+    /// Export the dependency graph between packages
+    Graph {
+        /// Output format
+        #[arg(long, value_enum, default_value_t = GraphFormat::Json)]
+        format: GraphFormat,
+        /// Print size and complexity metrics instead of the graph
         #[arg(long)]
-        verify: bool,
+        stats: bool,
+        /// Print the metrics as JSON
+        #[arg(long, requires = "stats")]
+        json: bool,
+        /// Only look for manifests this many directory levels below the root
+        #[arg(long, value_name = "N")]
+        depth: Option<usize>,
     },
+    /// Apply local path patches for git dependencies
+    PatchLocal(PatchLocalArgs),
     /// Remove local path patches and restore git dependencies
     PatchReset {
-        /// Workspace root directory (defaults to current directory)
-        #[arg(long)]
-        workspace_root: Option<String>,
         /// Clean cargo cache after removing patches
-        #[arg(long)]
+        #[arg(long, conflicts_with = "dry_run")]
         clean: bool,
-    },
-    /// Check workspace health (git status, versions, tests, docs, specs)
-    Health {
-        /// Workspace root directory (defaults to current directory)
+        /// Show the resulting config without modifying it
         #[arg(long)]
-        workspace_root: Option<String>,
-        /// Show detailed information
-        #[arg(long)]
-        verbose: bool,
-        /// Output as JSON instead of terminal/markdown
-        #[arg(long)]
-        json: bool,
-        /// Write markdown report to file
+        dry_run: bool,
+        /// Only remove the patches of these crates
+        #[arg(long, value_delimiter = ',')]
+        only: Vec<String>,
+        /// File holding the patches (config: .cargo/config.toml, manifest: root Cargo.toml)
+        #[arg(long, value_name = "FILE", default_value = "config")]
+        location: PatchTarget,
+    },
+    /// Point a git dependency at a different branch, tag or rev in every member
+    #[command(group(clap::ArgGroup::new("git_ref").required(true)))]
+    PatchRetarget {
+        /// Dependency to retarget, e.g. embeddenator-vsa
+        name: String,
+        /// Branch to track
+        #[arg(long, group = "git_ref")]
+        branch: Option<String>,
+        /// Tag to pin
+        #[arg(long, group = "git_ref")]
+        tag: Option<String>,
+        /// Commit to pin
+        #[arg(long, group = "git_ref")]
+        rev: Option<String>,
+    },
+    /// Show active patches and flag broken or non-normalized patch paths
+    PatchStatus {
+        /// Remove patches whose path no longer holds a crate
         #[arg(long)]
-        output: Option<String>,
-        /// Run specific checks only (git, version, tests, docs, specs)
+        prune: bool,
+    },
+    /// Verify that patch-local followed by patch-reset leaves the config unchanged
+    PatchVerifyRoundtrip,
+    /// Check workspace health (git status, versions, tests, docs, specs, yanked deps, members)
+    Health(HealthArgs),
+    /// Run the health checks and suggest a fix for each problem
+    Doctor {
+        /// Run specific checks only (same names as `health --check`)
         #[arg(long, value_delimiter = ',')]
         check: Vec<String>,
+        /// Include the cargo-audit security check
+        #[arg(long)]
+        audit: bool,
     },
+    /// Print a shell completion script to stdout
+    Completions {
+        /// Shell to generate completions for
+        #[arg(value_enum)]
+        shell: Shell,
+    },
+}
+
+#[derive(Args)]
+struct PatchLocalArgs {
+    /// Verify patches with cargo metadata
+    #[arg(long)]
+    verify: bool,
+    /// Dependency sections to scan (normal, dev, build)
+    #[arg(long, value_delimiter = ',')]
+    sections: Vec<String>,
+    /// Only list patchable dependencies, don't write any patches
+    #[arg(long)]
+    discover_only: bool,
+    /// Output the discovered dependencies as JSON (with --discover-only)
+    #[arg(long, requires = "discover_only")]
+    json: bool,
+    /// Show a diff of .cargo/config.toml before and after patching, or
+    /// why each dependency was skipped (with --discover-only)
+    #[arg(long)]
+    verbose: bool,
+    /// Only patch these crates, keeping the rest on git
+    #[arg(long, value_delimiter = ',')]
+    only: Vec<String>,
+    /// File to write the patches to (config: .cargo/config.toml, manifest: root Cargo.toml)
+    #[arg(long, value_name = "FILE", default_value = "config")]
+    location: PatchTarget,
+    /// Print the [patch] tables that would be written, without writing them
+    #[arg(long, conflicts_with_all = ["discover_only", "verify"])]
+    dry_run: bool,
+    /// Patch even when running under CI
+    #[arg(long)]
+    force: bool,
+    /// Environment variable that marks a CI run (patching is refused when set)
+    #[arg(long, value_name = "VAR", default_value = "CI")]
+    ci_env: String,
+}
+
+#[derive(Args)]
+struct HealthArgs {
+    /// Show detailed information
+    #[arg(long)]
+    verbose: bool,
+    /// Output as JSON instead of terminal/markdown
+    #[arg(long)]
+    json: bool,
+    /// Write markdown report to file
+    #[arg(long)]
+    output: Option<String>,
+    /// Write a self-contained HTML report to file
+    #[arg(long, value_name = "FILE")]
+    html: Option<String>,
+    /// Write a JUnit XML report to file, for CI test report aggregation
+    #[arg(long, value_name = "FILE")]
+    junit: Option<String>,
+    /// Show what changed since a previous `health --json` report
+    #[arg(long, value_name = "FILE")]
+    compare: Option<PathBuf>,
+    /// Run specific checks only (git, version, msrv, build, tests, docs, specs, yanked,
+    /// members, cycles, clippy, fmt, audit)
+    #[arg(long, value_delimiter = ',')]
+    check: Vec<String>,
+    /// Only check the workspace's default-members
+    #[arg(long)]
+    default_members: bool,
+    /// Only check this package (repeatable)
+    #[arg(long = "package", value_name = "NAME")]
+    packages: Vec<String>,
+    /// Only check packages changed since this git ref; others are reported as skipped
+    #[arg(long, value_name = "REF")]
+    since: Option<String>,
+    /// Arguments for `cargo test`, space-separated (default: "--all-features")
+    #[arg(long, value_name = "ARGS", allow_hyphen_values = true)]
+    test_args: Option<String>,
+    /// Arguments for `cargo rustdoc`, space-separated
+    #[arg(long, value_name = "ARGS", allow_hyphen_values = true)]
+    doc_args: Option<String>,
+    /// Report documented-item coverage in the docs check (needs a nightly toolchain)
+    #[arg(long)]
+    doc_coverage: bool,
+    /// Look for repositories and packages this many directory levels below the root (default: 2)
+    #[arg(long, value_name = "N")]
+    depth: Option<usize>,
+    /// Build tests and docs for this target triple instead of the host
+    #[arg(long)]
+    target: Option<String>,
+    /// Report spec coverage without warning on missing specs
+    #[arg(long)]
+    specs_info_only: bool,
+    /// File extensions counted as specs
+    #[arg(
+        long,
+        value_name = "EXT",
+        value_delimiter = ',',
+        default_value = "md,txt"
+    )]
+    spec_ext: Vec<String>,
+    /// Only warn when spec coverage is below this percentage
+    #[arg(long, value_name = "PCT", default_value_t = DEFAULT_SPEC_THRESHOLD)]
+    spec_threshold: f64,
+    /// Group failing packages by their CODEOWNERS owners
+    #[arg(long)]
+    by_owner: bool,
+    /// Also run the cargo-audit security check when running all checks
+    #[arg(long)]
+    audit: bool,
+    /// Fetch each repository's upstream before checking ahead/behind
+    #[arg(long)]
+    fetch: bool,
+    /// Maximum number of checks to run at once (defaults to the number of CPUs)
+    #[arg(long, value_name = "N")]
+    jobs: Option<usize>,
+    /// Exit non-zero when any check is at this level or worse
+    #[arg(long, value_name = "LEVEL", value_enum, default_value_t = FailOn::Fail)]
+    fail_on: FailOn,
+    /// Fail any single check that runs longer than this many seconds
+    #[arg(long, value_name = "SECS", default_value_t = DEFAULT_CHECK_TIMEOUT.as_secs())]
+    timeout_secs: u64,
+}
+
+#[derive(Args)]
+struct CheckVersionsArgs {
+    /// Show detailed information
+    #[arg(long)]
+    verbose: bool,
+    /// Fail unless all packages share a single version
+    #[arg(long)]
+    require_common: bool,
+    /// Only check the workspace's default-members
+    #[arg(long)]
+    default_members: bool,
+    /// Allowed prerelease labels (default: alpha, beta, rc)
+    #[arg(long, value_delimiter = ',')]
+    allowed_prerelease: Vec<String>,
+    /// Dependency kinds to check (normal, dev, build; default: all)
+    #[arg(long, value_delimiter = ',')]
+    kinds: Vec<String>,
+    /// Print the version report as JSON
+    #[arg(long)]
+    json: bool,
+}
+
+#[derive(Args)]
+#[command(group(clap::ArgGroup::new("explicit").args(["set", "from_file"])))]
+struct BumpArgs {
+    /// Bump major version (X.0.0)
+    #[arg(long, group = "bump_type")]
+    major: bool,
+    /// Bump minor version (0.X.0)
+    #[arg(long, group = "bump_type")]
+    minor: bool,
+    /// Bump patch version (0.0.X)
+    #[arg(long, group = "bump_type")]
+    patch: bool,
+    /// Bump prerelease version (0.0.0-alpha.X)
+    #[arg(long, group = "bump_type")]
+    prerelease: bool,
+    /// Label for a prerelease started from a release version (default: alpha)
+    #[arg(long, value_name = "LABEL", default_value = "alpha")]
+    prerelease_label: String,
+    /// Whitespace around `=` in rewritten versions (default, spaced, compact, preserve)
+    #[arg(long, value_name = "STYLE", default_value = "default")]
+    format_style: FormatStyle,
+    /// Promote the prerelease stage (alpha → beta → rc → release)
+    #[arg(long, group = "bump_type")]
+    promote: bool,
+    /// Infer the bump from conventional commits since the last tag
+    #[arg(long, group = "bump_type")]
+    auto: bool,
+    /// Set every package to this exact version instead of bumping
+    #[arg(
+        long,
+        group = "bump_type",
+        value_name = "VERSION",
+        conflicts_with = "package"
+    )]
+    set: Option<semver::Version>,
+    /// Move packages to the versions in a TOML file of `name = "x.y.z"` entries
+    #[arg(
+        long,
+        group = "bump_type",
+        value_name = "FILE",
+        conflicts_with = "package"
+    )]
+    from_file: Option<PathBuf>,
+    /// Raise every package and inter-dependency to the highest package version
+    #[arg(long, group = "bump_type", conflicts_with = "package")]
+    align: bool,
+    /// Only rewrite embeddenator-* dependency requirements (to --to), not package versions
+    #[arg(long, group = "bump_type", requires = "to")]
+    deps_only: bool,
+    /// Version to point dependency requirements at with --deps-only
+    #[arg(long, value_name = "VERSION", requires = "deps_only")]
+    to: Option<semver::Version>,
+    /// Undo the last bump recorded in .embeddenator/last-bump.json
+    #[arg(
+        long,
+        group = "bump_type",
+        conflicts_with_all = ["package", "dry_run"]
+    )]
+    rollback: bool,
+    /// Add a section for the new version to CHANGELOG.md at the workspace root
+    #[arg(long, conflicts_with_all = ["dry_run", "rollback"])]
+    changelog: bool,
+    /// Commit the bump and create an annotated v<version> tag in the workspace repository
+    #[arg(long, conflicts_with_all = ["dry_run", "rollback"])]
+    tag: bool,
+    /// Don't record the bump in .embeddenator/last-bump.json
+    #[arg(long, conflicts_with = "rollback")]
+    no_journal: bool,
+    /// Allow --set or --from-file to move packages to a lower version
+    #[arg(long, requires = "explicit")]
+    allow_downgrade: bool,
+    /// Git ref to read commits from with --auto (defaults to the latest tag)
+    #[arg(long, requires = "auto")]
+    since: Option<String>,
+    /// Bump only this package and update the manifests that depend on it
+    #[arg(long)]
+    package: Option<String>,
+    /// Show what would be changed without making changes
+    #[arg(long)]
+    dry_run: bool,
+    /// Clear build metadata (+...) instead of keeping it
+    #[arg(long)]
+    clear_build: bool,
+    /// Replace build metadata with this value (e.g. git.abc123)
+    #[arg(long, value_name = "META", conflicts_with = "clear_build")]
+    build_metadata: Option<String>,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum GraphFormat {
+    /// Nodes and edges as JSON
+    Json,
+}
+
+/// Lowest check status that makes `health` exit non-zero.
+#[derive(Clone, Copy, ValueEnum)]
+enum FailOn {
+    /// Any warning or failure
+    Warn,
+    /// Failures only
+    Fail,
+}
+
+impl FailOn {
+    fn status(self) -> HealthStatus {
+        match self {
+            Self::Warn => HealthStatus::Warn,
+            Self::Fail => HealthStatus::Fail,
+        }
+    }
 }
 
 fn main() -> ExitCode {
     let cli = Cli::parse();
+    configure_color(cli.no_color);
+    let prefix = cli.prefix.as_str();
+    let cargo = cli.cargo.as_path();
+    let workspace_root = resolve_workspace_root(cli.workspace_root);
 
     match cli.command {
-        Commands::Docs => docs(),
-        Commands::Health {
-            workspace_root,
-            verbose,
+        Commands::Docs => docs(&workspace_root),
+        Commands::Health(args) => health(args, &workspace_root, prefix, cargo),
+        Commands::Rustdoc => rustdoc(&workspace_root),
+        Commands::Mdbook => mdbook(&workspace_root),
+        Commands::BumpVersion(args) => bump_version(args, &workspace_root, prefix),
+        Commands::CheckVersions(args) => check_versions(args, &workspace_root, prefix),
+        Commands::VerifyTags => verify_tags(&workspace_root, prefix),
+        Commands::Affected {
+            changed_since,
+            changed_files,
+            all_if_unowned,
+            depth,
+        } => affected(
+            changed_since,
+            changed_files,
+            all_if_unowned,
+            scanner(&workspace_root, prefix, depth),
+        ),
+        Commands::Order { depth } => order(scanner(&workspace_root, prefix, depth)),
+        Commands::List { json, depth } => list(json, scanner(&workspace_root, prefix, depth)),
+        Commands::Graph {
+            format,
+            stats,
             json,
-            output,
-            check,
-        } => health(workspace_root, verbose, json, output, check),
-        Commands::Rustdoc => rustdoc(),
-        Commands::Mdbook => mdbook(),
-        Commands::BumpVersion {
-            major,
-            minor,
-            patch,
-            prerelease,
-            dry_run,
-        } => bump_version(major, minor, patch, prerelease, dry_run),
-        Commands::CheckVersions { verbose } => check_versions(verbose),
-        Commands::PatchLocal {
-            workspace_root,
-            verify,
-        } => patch_local(workspace_root, verify),
+            depth,
+        } => graph(format, stats, json, scanner(&workspace_root, prefix, depth)),
+        Commands::PatchLocal(args) => patch_local(args, &workspace_root, prefix, cargo),
         Commands::PatchReset {
-            workspace_root,
             clean,
-        } => patch_reset(workspace_root, clean),
+            dry_run,
+            only,
+            location,
+        } => patch_reset(&workspace_root, clean, dry_run, &only, location, cargo),
+        Commands::PatchRetarget {
+            name,
+            branch,
+            tag,
+            rev,
+        } => {
+            let new_ref = match (branch, tag, rev) {
+                (Some(branch), _, _) => GitRef::Branch(branch),
+                (_, Some(tag), _) => GitRef::Tag(tag),
+                (_, _, Some(rev)) => GitRef::Rev(rev),
+                _ => unreachable!("clap requires one of --branch, --tag or --rev"),
+            };
+            patch_retarget(&workspace_root, &name, new_ref)
+        }
+        Commands::PatchStatus { prune } => patch_status(&workspace_root, prune),
+        Commands::PatchVerifyRoundtrip => patch_verify_roundtrip(&workspace_root, prefix, cargo),
+        Commands::Doctor { check, audit } => doctor(&check, audit, &workspace_root, prefix, cargo),
+        Commands::Completions { shell } => completions(shell),
     }
 }
 
-fn bump_version(
-    major: bool,
-    minor: bool,
-    patch: bool,
-    _prerelease: bool,
-    dry_run: bool,
-) -> ExitCode {
+fn bump_version(args: BumpArgs, workspace_root: &Path, prefix: &str) -> ExitCode {
+    let dry_run = args.dry_run;
+
+    let manager = VersionManager::new(workspace_root)
+        .with_prefix(prefix)
+        .with_clear_build(args.clear_build)
+        .with_build_metadata(args.build_metadata.as_deref())
+        .with_allow_downgrade(args.allow_downgrade)
+        .with_prerelease_label(&args.prerelease_label)
+        .with_format_style(args.format_style)
+        .with_journal(!args.no_journal);
+
+    if args.rollback {
+        return rollback_bump(&manager);
+    }
+    if let Some(target) = &args.to {
+        return align_dependencies(&manager, workspace_root, prefix, target, dry_run);
+    }
+
     // Determine bump type (default to prerelease if none specified)
-    let bump_type = if major {
+    let bump_type = if args.major {
         BumpType::Major
-    } else if minor {
+    } else if args.minor {
         BumpType::Minor
-    } else if patch {
+    } else if args.patch {
         BumpType::Patch
+    } else if args.promote {
+        BumpType::PromotePrerelease
+    } else if args.auto {
+        match manager.analyze_commits(args.since.as_deref()) {
+            Ok(analysis) => {
+                println!(
+                    "{} {:?} bump inferred from {} commit(s)",
+                    "Inferred:".cyan().bold(),
+                    analysis.bump,
+                    analysis.commit_count
+                );
+                for commit in &analysis.drivers {
+                    println!("  {} {}", "•".cyan(), commit);
+                }
+                analysis.bump
+            }
+            Err(e) => {
+                eprintln!("{} {}", "Error:".red().bold(), e);
+                return ExitCode::from(1);
+            }
+        }
     } else {
         BumpType::Prerelease
     };
 
-    // Find workspace root (go up until we find update_all.sh or are at root)
-    let workspace_root = std::env::current_dir().expect("Failed to get current directory");
-    let workspace_root = find_workspace_root(&workspace_root).unwrap_or(workspace_root);
-
-    let manager = VersionManager::new(&workspace_root);
-
     if dry_run {
         println!(
             "{}",
             "Dry run mode - no changes will be made".yellow().bold()
         );
     }
+    if args.tag {
+        if let Err(e) = manager.ensure_clean_tree("bump and tag") {
+            eprintln!("{} {}", "Error:".red().bold(), e);
+            return ExitCode::from(1);
+        }
+    }
 
-    println!(
-        "{} {:?} version bump...",
-        "Performing".cyan().bold(),
-        bump_type
-    );
+    let result = if args.align {
+        match manager.align_to_highest(dry_run) {
+            Ok((target, changes)) => {
+                println!("{} {}", "Target:".cyan().bold(), target);
+                if changes.is_empty() {
+                    println!(
+                        "\n{} All packages already at {}",
+                        "✓".green().bold(),
+                        target
+                    );
+                    return ExitCode::SUCCESS;
+                }
+                Ok(changes)
+            }
+            Err(e) => Err(e),
+        }
+    } else if let Some(target) = &args.set {
+        println!("{} all packages to {}...", "Setting".cyan().bold(), target);
+        manager.set_versions(target, dry_run)
+    } else if let Some(path) = &args.from_file {
+        println!(
+            "{} versions from {}...",
+            "Applying".cyan().bold(),
+            path.display()
+        );
+        VersionManager::load_version_map(path)
+            .and_then(|map| manager.apply_version_map(&map, dry_run))
+    } else {
+        println!(
+            "{} {:?} version bump...",
+            "Performing".cyan().bold(),
+            bump_type
+        );
+
+        match &args.package {
+            Some(package) => manager
+                .bump_package(package, bump_type, dry_run)
+                .map(|bump| {
+                    for skipped in &bump.skipped {
+                        eprintln!("{} Left {} untouched", "Warning:".yellow().bold(), skipped);
+                    }
+                    bump.changes
+                }),
+            None => manager.bump_versions(bump_type, dry_run),
+        }
+    };
 
-    match manager.bump_versions(bump_type, dry_run) {
+    match result {
         Ok(changes) => {
+            if changes.is_empty() && args.from_file.is_some() {
+                println!(
+                    "\n{} All packages already at the mapped versions",
+                    "✓".green().bold()
+                );
+                return ExitCode::SUCCESS;
+            }
             if changes.is_empty() {
                 println!("{}", "No packages found to update".yellow());
                 return ExitCode::from(1);
@@ -176,11 +610,17 @@ fn bump_version(
                     "✓".green().bold(),
                     changes.len()
                 );
-                println!(
-                    "\n{} git commit -am \"chore: bump version to {}\"",
-                    "Next:".cyan().bold(),
-                    changes[0].new_version
-                );
+                if !args.tag {
+                    println!(
+                        "\n{} git commit -am \"chore: bump version to {}\"",
+                        "Next:".cyan().bold(),
+                        changes[0].new_version
+                    );
+                }
+
+                if args.changelog || args.tag {
+                    return finish_release(&manager, &changes, args.changelog, args.tag);
+                }
             } else {
                 println!(
                     "\n{} {} package(s) would be updated",
@@ -198,14 +638,258 @@ fn bump_version(
     }
 }
 
-fn check_versions(verbose: bool) -> ExitCode {
-    let workspace_root = std::env::current_dir().expect("Failed to get current directory");
-    let workspace_root = find_workspace_root(&workspace_root).unwrap_or(workspace_root);
+/// Update the changelog and/or tag after a bump; both need a single new version.
+fn finish_release(
+    manager: &VersionManager,
+    changes: &[VersionChange],
+    changelog: bool,
+    tag: bool,
+) -> ExitCode {
+    let version = &changes[0].new_version;
+    if changes.iter().any(|c| &c.new_version != version) {
+        eprintln!(
+            "{} Packages ended on different versions; skipping changelog and tag",
+            "Error:".red().bold()
+        );
+        return ExitCode::from(1);
+    }
 
-    let manager = VersionManager::new(&workspace_root);
+    if changelog {
+        match manager.prepend_changelog(version, &today()) {
+            Ok(true) => println!(
+                "{} Added {} to CHANGELOG.md",
+                "✓".green().bold(),
+                version.to_string().bright_white()
+            ),
+            Ok(false) => println!(
+                "{} CHANGELOG.md already has an entry for {}",
+                "Info:".blue().bold(),
+                version
+            ),
+            Err(e) => {
+                eprintln!("{} {}", "Error:".red().bold(), e);
+                return ExitCode::from(1);
+            }
+        }
+    }
+
+    if !tag {
+        return ExitCode::SUCCESS;
+    }
+
+    let message = format!("chore: bump version to {}", version);
+    match manager.commit_release(&message) {
+        Ok(commit) => println!(
+            "{} Committed {} ({})",
+            "✓".green().bold(),
+            message.bright_white(),
+            &commit.to_string()[..7]
+        ),
+        Err(e) => {
+            eprintln!("{} {}", "Error:".red().bold(), e);
+            eprintln!(
+                "{} Versions were updated but not committed or tagged",
+                "Info:".blue().bold()
+            );
+            return ExitCode::from(1);
+        }
+    }
+
+    match manager.tag_release(version, &format!("Release v{}", version)) {
+        Ok(tag) => {
+            println!("{} Created tag {}", "✓".green().bold(), tag.bright_white());
+            ExitCode::SUCCESS
+        }
+        Err(e) => {
+            eprintln!("{} {}", "Error:".red().bold(), e);
+            eprintln!(
+                "{} Versions were committed but not tagged",
+                "Info:".blue().bold()
+            );
+            ExitCode::from(1)
+        }
+    }
+}
+
+/// Today's local date as `YYYY-MM-DD`.
+fn today() -> String {
+    chrono::Local::now()
+        .date_naive()
+        .format("%Y-%m-%d")
+        .to_string()
+}
+
+fn align_dependencies(
+    manager: &VersionManager,
+    workspace_root: &Path,
+    prefix: &str,
+    target: &semver::Version,
+    dry_run: bool,
+) -> ExitCode {
+    if dry_run {
+        println!(
+            "{}",
+            "Dry run mode - no changes will be made".yellow().bold()
+        );
+    }
+    println!(
+        "{} embeddenator dependency requirements to {}...",
+        "Aligning".cyan().bold(),
+        target
+    );
+
+    let manifests = match WorkspaceScanner::new(workspace_root).find_manifests() {
+        Ok(manifests) => manifests,
+        Err(e) => {
+            eprintln!("{} {}", "Error:".red().bold(), e);
+            return ExitCode::from(1);
+        }
+    };
+    let versions: HashMap<String, semver::Version> = manifests
+        .iter()
+        .flat_map(|m| m.dependencies_with_prefix(prefix))
+        .map(|d| (d.name.clone(), target.clone()))
+        .collect();
+
+    match manager.align_dependencies_to(&versions, dry_run) {
+        Ok(changes) if changes.is_empty() => {
+            println!(
+                "\n{} All embeddenator requirements already at {}",
+                "✓".green().bold(),
+                target
+            );
+            ExitCode::SUCCESS
+        }
+        Ok(changes) => {
+            println!("\n{}", "Requirement Changes:".green().bold());
+            for change in &changes {
+                println!(
+                    "  {} {} {} → {}",
+                    change.package.bright_white().bold(),
+                    change.dependency,
+                    change.old_req.red(),
+                    change.new_version.to_string().green()
+                );
+            }
+
+            let verb = if dry_run { "would be" } else { "were" };
+            println!(
+                "\n{} {} requirement(s) {} updated",
+                "Info:".blue().bold(),
+                changes.len(),
+                verb
+            );
+            ExitCode::SUCCESS
+        }
+        Err(e) => {
+            eprintln!("{} {}", "Error:".red().bold(), e);
+            ExitCode::from(1)
+        }
+    }
+}
+
+fn rollback_bump(manager: &VersionManager) -> ExitCode {
+    println!("{}", "Rolling back the last version bump...".cyan().bold());
+
+    let report = match manager.rollback() {
+        Ok(Some(report)) => report,
+        Ok(None) => {
+            eprintln!(
+                "{} No bump journal found at {}; nothing to roll back",
+                "Warning:".yellow().bold(),
+                JOURNAL_PATH
+            );
+            return ExitCode::from(1);
+        }
+        Err(e) => {
+            eprintln!("{} {}", "Error:".red().bold(), e);
+            return ExitCode::from(1);
+        }
+    };
+
+    if !report.restored.is_empty() {
+        println!("\n{}", "Restored Versions:".green().bold());
+        for change in &report.restored {
+            println!(
+                "  {} {} → {}",
+                change.package.bright_white().bold(),
+                change.old_version.to_string().red(),
+                change.new_version.to_string().green()
+            );
+        }
+    }
+
+    for skipped in &report.skipped {
+        eprintln!("{} Skipped {}", "Warning:".yellow().bold(), skipped);
+    }
+
+    println!(
+        "\n{} {} package(s) restored, {} skipped",
+        "✓".green().bold(),
+        report.restored.len(),
+        report.skipped.len()
+    );
+
+    ExitCode::SUCCESS
+}
+
+fn check_versions(args: CheckVersionsArgs, workspace_root: &Path, prefix: &str) -> ExitCode {
+    let CheckVersionsArgs {
+        verbose,
+        require_common,
+        default_members,
+        allowed_prerelease,
+        kinds,
+        json,
+    } = args;
+
+    let mut manager = VersionManager::new(workspace_root)
+        .with_default_members(default_members)
+        .with_prefix(prefix);
+    if !allowed_prerelease.is_empty() {
+        let labels: Vec<&str> = allowed_prerelease.iter().map(String::as_str).collect();
+        manager = manager.with_allowed_prerelease_labels(&labels);
+    }
+    if !kinds.is_empty() {
+        match parse_dependency_types(&kinds) {
+            Ok(dep_types) => manager = manager.with_dependency_kinds(&dep_types),
+            Err(code) => return code,
+        }
+    }
+
+    if json {
+        return check_versions_json(&manager, require_common);
+    }
 
     println!("{}", "Checking version consistency...".cyan().bold());
 
+    if require_common {
+        match manager.common_version() {
+            Ok(Some(version)) => {
+                println!(
+                    "\n{} {}",
+                    "Workspace version:".blue().bold(),
+                    version.to_string().green()
+                );
+            }
+            Ok(None) => {
+                println!(
+                    "\n{} Packages do not share a single common version",
+                    "✗".red().bold()
+                );
+                println!(
+                    "\n{} Run 'embeddenator-workspace bump-version' to align versions",
+                    "Suggestion:".cyan().bold()
+                );
+                return ExitCode::from(1);
+            }
+            Err(e) => {
+                eprintln!("{} {}", "Error:".red().bold(), e);
+                return ExitCode::from(1);
+            }
+        }
+    }
+
     match manager.check_consistency() {
         Ok(report) => {
             println!(
@@ -214,6 +898,40 @@ fn check_versions(verbose: bool) -> ExitCode {
                 report.total_packages
             );
 
+            if verbose {
+                println!("\n{}", "Package Versions:".blue().bold());
+                for (name, version, path) in &report.packages {
+                    let path = path.strip_prefix(workspace_root).unwrap_or(path);
+                    println!(
+                        "  {} {} {} ({})",
+                        "•".blue(),
+                        name.bright_white(),
+                        version,
+                        path.display()
+                    );
+                }
+
+                if !report.verified.is_empty() {
+                    println!("\n{}", "Verified Dependencies:".green().bold());
+                    for (package, dependency, version) in &report.verified {
+                        println!(
+                            "  {} {} → {} {}",
+                            "✓".green(),
+                            package.bright_white(),
+                            dependency,
+                            version
+                        );
+                    }
+                }
+            }
+
+            if !report.convention_warnings.is_empty() {
+                println!("\n{}", "Convention Warnings:".yellow().bold());
+                for warning in &report.convention_warnings {
+                    println!("  {} {}", "•".yellow(), warning);
+                }
+            }
+
             if report.has_issues() {
                 println!("\n{}", "Issues Found:".red().bold());
 
@@ -231,29 +949,291 @@ fn check_versions(verbose: bool) -> ExitCode {
                             "•".yellow(),
                             inc.package.bright_white(),
                             inc.dependency,
-                            inc.found.to_string().red(),
+                            inc.found.red(),
                             inc.expected.to_string().green()
                         );
                     }
                 }
 
                 println!(
-                    "\n{} Run 'embeddenator-workspace bump-version --prerelease' to fix",
+                    "\n{} Run 'embeddenator-workspace bump-version --align' to fix",
                     "Suggestion:".cyan().bold()
                 );
-
+
+                ExitCode::from(1)
+            } else {
+                println!("\n{} All versions are consistent!", "✓".green().bold());
+                ExitCode::SUCCESS
+            }
+        }
+        Err(e) => {
+            eprintln!("{} {}", "Error:".red().bold(), e);
+            ExitCode::from(1)
+        }
+    }
+}
+
+/// Print the consistency report as JSON; exits 1 on the same conditions as
+/// the text output.
+fn check_versions_json(manager: &VersionManager, require_common: bool) -> ExitCode {
+    let common = if require_common {
+        match manager.common_version() {
+            Ok(version) => version.is_some(),
+            Err(e) => {
+                eprintln!("{} {}", "Error:".red().bold(), e);
+                return ExitCode::from(1);
+            }
+        }
+    } else {
+        true
+    };
+
+    let report = match manager.check_consistency() {
+        Ok(report) => report,
+        Err(e) => {
+            eprintln!("{} {}", "Error:".red().bold(), e);
+            return ExitCode::from(1);
+        }
+    };
+
+    match serde_json::to_string_pretty(&report) {
+        Ok(json_output) => println!("{}", json_output),
+        Err(e) => {
+            eprintln!(
+                "{} Failed to serialize to JSON: {}",
+                "Error:".red().bold(),
+                e
+            );
+            return ExitCode::from(1);
+        }
+    }
+
+    if report.has_issues() || !common {
+        ExitCode::from(1)
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+fn verify_tags(workspace_root: &Path, prefix: &str) -> ExitCode {
+    let manager = VersionManager::new(workspace_root).with_prefix(prefix);
+
+    println!("{}", "Checking release tags...".cyan().bold());
+
+    match manager.untagged_packages() {
+        Ok(packages) => {
+            let (tagged, untagged): (Vec<_>, Vec<_>) =
+                packages.iter().partition(|(_, _, tagged)| *tagged);
+
+            if !untagged.is_empty() {
+                println!("\n{}", "Ready (untagged):".green().bold());
+                for (name, version, _) in &untagged {
+                    println!(
+                        "  {} {} {}",
+                        "•".green(),
+                        name.bright_white(),
+                        version.to_string().green()
+                    );
+                }
+            }
+
+            if tagged.is_empty() {
+                println!(
+                    "\n{} No package versions are already tagged",
+                    "✓".green().bold()
+                );
+                ExitCode::SUCCESS
+            } else {
+                println!("\n{}", "Already Tagged (needs bump):".red().bold());
+                for (name, version, _) in &tagged {
+                    println!(
+                        "  {} {} {}",
+                        "•".red(),
+                        name.bright_white(),
+                        version.to_string().red()
+                    );
+                }
+
+                println!(
+                    "\n{} Run 'embeddenator-workspace bump-version' before releasing",
+                    "Suggestion:".cyan().bold()
+                );
+                ExitCode::from(1)
+            }
+        }
+        Err(e) => {
+            eprintln!("{} {}", "Error:".red().bold(), e);
+            ExitCode::from(1)
+        }
+    }
+}
+
+/// Turn off colors for `--no-color`, a non-empty `NO_COLOR`, or when stdout
+/// isn't a terminal (unless `CLICOLOR_FORCE` asks for them).
+fn configure_color(no_color: bool) {
+    let env_set = |name: &str| std::env::var_os(name).is_some_and(|v| !v.is_empty() && v != "0");
+    let piped = !std::io::stdout().is_terminal() && !env_set("CLICOLOR_FORCE");
+
+    if no_color || env_set("NO_COLOR") || piped {
+        colored::control::set_override(false);
+    }
+}
+
+/// A scanner over the workspace rooted at `workspace_root`.
+fn scanner(workspace_root: &Path, prefix: &str, depth: Option<usize>) -> WorkspaceScanner {
+    let scanner = WorkspaceScanner::new(workspace_root).with_prefix(prefix);
+    match depth {
+        Some(depth) => scanner.with_max_depth(depth),
+        None => scanner,
+    }
+}
+
+fn affected(
+    changed_since: Option<String>,
+    changed_files: Option<PathBuf>,
+    all_if_unowned: bool,
+    scanner: WorkspaceScanner,
+) -> ExitCode {
+    let files = match (changed_since, changed_files) {
+        (Some(git_ref), _) => scanner.changed_files_since(&git_ref),
+        (None, Some(list)) => std::fs::read_to_string(&list)
+            .map(|content| {
+                content
+                    .lines()
+                    .map(str::trim)
+                    .filter(|line| !line.is_empty())
+                    .map(PathBuf::from)
+                    .collect()
+            })
+            .map_err(|e| anyhow::anyhow!("Failed to read {}: {}", list.display(), e)),
+        (None, None) => unreachable!("clap requires one of the change sources"),
+    };
+
+    let result = files.and_then(|files| {
+        let (packages, unowned) = scanner.partition_by_package(&files)?;
+        if all_if_unowned && !unowned.is_empty() {
+            Ok(scanner
+                .find_embeddenator_packages()?
+                .into_iter()
+                .map(|m| m.package_name)
+                .collect())
+        } else {
+            Ok(packages)
+        }
+    });
+
+    match result {
+        Ok(packages) => {
+            for package in packages {
+                println!("{}", package);
+            }
+            ExitCode::SUCCESS
+        }
+        Err(e) => {
+            eprintln!("{} {}", "Error:".red().bold(), e);
+            ExitCode::from(1)
+        }
+    }
+}
+
+fn graph(format: GraphFormat, stats: bool, json: bool, scanner: WorkspaceScanner) -> ExitCode {
+    let graph = match DependencyGraph::from_scanner(&scanner) {
+        Ok(graph) => graph,
+        Err(e) => {
+            eprintln!("{} {}", "Error:".red().bold(), e);
+            return ExitCode::from(1);
+        }
+    };
+
+    if stats {
+        return graph_stats(&graph, json);
+    }
+
+    match format {
+        GraphFormat::Json => match serde_json::to_string_pretty(&graph.to_json()) {
+            Ok(json_output) => {
+                println!("{}", json_output);
+                ExitCode::SUCCESS
+            }
+            Err(e) => {
+                eprintln!(
+                    "{} Failed to serialize to JSON: {}",
+                    "Error:".red().bold(),
+                    e
+                );
+                ExitCode::from(1)
+            }
+        },
+    }
+}
+
+fn list(json: bool, scanner: WorkspaceScanner) -> ExitCode {
+    let packages = match scanner.find_embeddenator_packages() {
+        Ok(packages) => packages,
+        Err(e) => {
+            eprintln!("{} {}", "Error:".red().bold(), e);
+            return ExitCode::from(1);
+        }
+    };
+
+    // Package directories relative to the workspace root
+    let rows: Vec<(&str, String, String)> = packages
+        .iter()
+        .map(|m| {
+            let dir = m.path.parent().unwrap_or(&m.path);
+            let dir = dir.strip_prefix(scanner.root()).unwrap_or(dir);
+            let dir = if dir.as_os_str().is_empty() {
+                ".".to_string()
+            } else {
+                dir.display().to_string()
+            };
+            (m.package_name.as_str(), m.version.to_string(), dir)
+        })
+        .collect();
+
+    if json {
+        let entries: Vec<serde_json::Value> = rows
+            .iter()
+            .map(|(name, version, path)| {
+                serde_json::json!({ "name": name, "version": version, "path": path })
+            })
+            .collect();
+        return match serde_json::to_string_pretty(&entries) {
+            Ok(json_output) => {
+                println!("{}", json_output);
+                ExitCode::SUCCESS
+            }
+            Err(e) => {
+                eprintln!(
+                    "{} Failed to serialize to JSON: {}",
+                    "Error:".red().bold(),
+                    e
+                );
                 ExitCode::from(1)
-            } else {
-                println!("\n{} All versions are consistent!", "✓".green().bold());
+            }
+        };
+    }
 
-                if verbose {
-                    println!("\n{}", "Package Versions:".blue().bold());
-                    // This would require additional data from the report
-                    // For now, just show success
-                }
+    let name_width = rows
+        .iter()
+        .map(|(name, _, _)| name.len())
+        .max()
+        .unwrap_or(0);
+    let version_width = rows.iter().map(|(_, v, _)| v.len()).max().unwrap_or(0);
+    for (name, version, path) in &rows {
+        println!("{:name_width$}  {:version_width$}  {}", name, version, path);
+    }
 
-                ExitCode::SUCCESS
+    ExitCode::SUCCESS
+}
+
+fn order(scanner: WorkspaceScanner) -> ExitCode {
+    match scanner.publish_order() {
+        Ok(order) => {
+            for name in order {
+                println!("{}", name);
             }
+            ExitCode::SUCCESS
         }
         Err(e) => {
             eprintln!("{} {}", "Error:".red().bold(), e);
@@ -262,6 +1242,62 @@ fn check_versions(verbose: bool) -> ExitCode {
     }
 }
 
+fn graph_stats(graph: &DependencyGraph, json: bool) -> ExitCode {
+    let stats = graph.stats();
+
+    if json {
+        return match serde_json::to_string_pretty(&stats) {
+            Ok(json_output) => {
+                println!("{}", json_output);
+                ExitCode::SUCCESS
+            }
+            Err(e) => {
+                eprintln!(
+                    "{} Failed to serialize to JSON: {}",
+                    "Error:".red().bold(),
+                    e
+                );
+                ExitCode::from(1)
+            }
+        };
+    }
+
+    let ranking = |counts: &[(String, usize)]| {
+        counts
+            .iter()
+            .map(|(name, count)| format!("{} ({})", name, count))
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+
+    println!("{}", "Dependency Graph Stats:".cyan().bold());
+    println!("  {:<12} {}", "Crates", stats.crates);
+    println!("  {:<12} {}", "Edges", stats.edges);
+    println!("  {:<12} {}", "Max depth", stats.max_depth);
+    println!("  {:<12} {}", "Top fan-in", ranking(&stats.top_fan_in));
+    println!("  {:<12} {}", "Top fan-out", ranking(&stats.top_fan_out));
+
+    ExitCode::SUCCESS
+}
+
+fn parse_dependency_types(values: &[String]) -> Result<Vec<DependencyType>, ExitCode> {
+    let mut dep_types = Vec::new();
+    for value in values {
+        match value.parse::<DependencyType>() {
+            Ok(t) => dep_types.push(t),
+            Err(_) => {
+                eprintln!(
+                    "{} Unknown dependency kind: '{}'. Valid kinds: normal, dev, build",
+                    "Error:".red().bold(),
+                    value
+                );
+                return Err(ExitCode::from(1));
+            }
+        }
+    }
+    Ok(dep_types)
+}
+
 fn find_workspace_root(start: &std::path::Path) -> Option<std::path::PathBuf> {
     let mut current = start.to_path_buf();
     loop {
@@ -274,6 +1310,13 @@ fn find_workspace_root(start: &std::path::Path) -> Option<std::path::PathBuf> {
     }
 }
 
+fn completions(shell: Shell) -> ExitCode {
+    let mut cli = Cli::command();
+    let name = cli.get_name().to_string();
+    clap_complete::generate(shell, &mut cli, name, &mut std::io::stdout());
+    ExitCode::SUCCESS
+}
+
 fn run(cmd: &mut Command) -> ExitCode {
     match cmd.status() {
         Ok(st) if st.success() => ExitCode::SUCCESS,
@@ -285,27 +1328,29 @@ fn run(cmd: &mut Command) -> ExitCode {
     }
 }
 
-fn rustdoc() -> ExitCode {
+fn rustdoc(workspace_root: &Path) -> ExitCode {
     let mut cmd = Command::new("bash");
-    cmd.arg("./generate_docs.sh");
+    cmd.arg("./generate_docs.sh").current_dir(workspace_root);
     run(&mut cmd)
 }
 
-fn mdbook() -> ExitCode {
+fn mdbook(workspace_root: &Path) -> ExitCode {
     let mut cmd = Command::new("bash");
-    cmd.arg("./scripts/docs/build_mdbook.sh");
+    cmd.arg("./scripts/docs/build_mdbook.sh")
+        .current_dir(workspace_root);
     run(&mut cmd)
 }
 
-fn docs() -> ExitCode {
-    let rc = rustdoc();
+fn docs(workspace_root: &Path) -> ExitCode {
+    let rc = rustdoc(workspace_root);
     if rc != ExitCode::SUCCESS {
         return rc;
     }
 
     // mdBook is optional; if not installed, the script exits nonzero. Treat that as non-fatal.
     let mut cmd = Command::new("bash");
-    cmd.arg("./scripts/docs/build_mdbook.sh");
+    cmd.arg("./scripts/docs/build_mdbook.sh")
+        .current_dir(workspace_root);
     match cmd.status() {
         Ok(st) if st.success() => ExitCode::SUCCESS,
         Ok(_) => {
@@ -319,19 +1364,72 @@ fn docs() -> ExitCode {
     }
 }
 
-fn patch_local(workspace_root: Option<String>, verify: bool) -> ExitCode {
-    let workspace_root = resolve_workspace_root(workspace_root);
+fn patch_local(
+    args: PatchLocalArgs,
+    workspace_root: &Path,
+    prefix: &str,
+    cargo: &Path,
+) -> ExitCode {
+    let PatchLocalArgs {
+        verify,
+        sections,
+        discover_only,
+        json,
+        verbose,
+        only,
+        location,
+        dry_run,
+        force,
+        ci_env,
+    } = args;
 
-    println!(
-        "{} Scanning for patchable dependencies in {}...",
-        "Discovering:".cyan().bold(),
-        workspace_root.display().to_string().bright_white()
-    );
+    // Local patches make builds use uncommitted local sources; never do that
+    // in automation by accident
+    if !discover_only && !dry_run && !force && patch::is_ci(&ci_env) {
+        eprintln!(
+            "{} Refusing to patch: {} is set, which indicates a CI environment",
+            "Error:".red().bold(),
+            ci_env
+        );
+        eprintln!(
+            "{} Local patches are for development only; pass --force to patch anyway",
+            "Suggestion:".cyan().bold()
+        );
+        return ExitCode::from(1);
+    }
+
+    let dep_types = match parse_dependency_types(&sections) {
+        Ok(dep_types) => dep_types,
+        Err(code) => return code,
+    };
+
+    if !json {
+        println!(
+            "{} Scanning for patchable dependencies in {}...",
+            "Discovering:".cyan().bold(),
+            workspace_root.display().to_string().bright_white()
+        );
+    }
+
+    let mut manager = PatchManager::new(workspace_root)
+        .with_prefix(prefix)
+        .with_cargo_path(cargo)
+        .with_only(&only)
+        .with_target(location);
+    if !dep_types.is_empty() {
+        manager = manager.with_sections(&dep_types);
+    }
 
-    let manager = PatchManager::new(&workspace_root);
+    match manager.discover_with_notes() {
+        Ok((deps, notes)) => {
+            if discover_only {
+                let code = print_discovered(&deps, json);
+                if verbose && !json {
+                    print_discovery_notes(&notes);
+                }
+                return code;
+            }
 
-    match manager.discover_patchable_dependencies() {
-        Ok(deps) => {
             if deps.is_empty() {
                 println!(
                     "{} No git dependencies with local equivalents found",
@@ -356,12 +1454,26 @@ fn patch_local(workspace_root: Option<String>, verify: bool) -> ExitCode {
             }
 
             println!(
-                "\n{} Applying patches to .cargo/config.toml...",
-                "Patching:".cyan().bold()
+                "\n{} Applying patches to {}...",
+                "Patching:".cyan().bold(),
+                location.relative_path()
             );
 
-            match manager.apply_patches(&deps, verify) {
+            match manager.apply_patches(&deps, verify, dry_run) {
+                Ok(report) if dry_run => {
+                    if verbose {
+                        report.print_diff();
+                    }
+                    report.print();
+                    println!("\n{}", "Patches:".cyan().bold());
+                    print!("{}", report.patch_blocks());
+                    println!("\n{} Dry run - config not modified", "Info:".blue().bold());
+                    ExitCode::SUCCESS
+                }
                 Ok(report) => {
+                    if verbose {
+                        report.print_diff();
+                    }
                     report.print();
 
                     if report.verification_error.is_some() {
@@ -391,21 +1503,108 @@ fn patch_local(workspace_root: Option<String>, verify: bool) -> ExitCode {
     }
 }
 
-fn patch_reset(workspace_root: Option<String>, clean: bool) -> ExitCode {
-    let workspace_root = resolve_workspace_root(workspace_root);
+fn print_discovery_notes(notes: &[DiscoveryNote]) {
+    if notes.is_empty() {
+        return;
+    }
+
+    println!("\n{}", "Examined Dependencies:".cyan().bold());
+    for note in notes {
+        let marker = if note.reason == DiscoveryReason::Patchable {
+            "✓".green().bold()
+        } else {
+            "-".dimmed()
+        };
+        println!(
+            "  {} {} {} ({} [{}])",
+            marker,
+            note.dependency.bright_white(),
+            note.reason.describe(),
+            note.package.dimmed(),
+            note.section.dimmed()
+        );
+    }
+}
+
+fn print_discovered(deps: &[GitDependency], json: bool) -> ExitCode {
+    if json {
+        return match serde_json::to_string_pretty(deps) {
+            Ok(json_output) => {
+                println!("{}", json_output);
+                ExitCode::SUCCESS
+            }
+            Err(e) => {
+                eprintln!(
+                    "{} Failed to serialize to JSON: {}",
+                    "Error:".red().bold(),
+                    e
+                );
+                ExitCode::from(1)
+            }
+        };
+    }
+
+    if deps.is_empty() {
+        println!(
+            "{} No git dependencies with local equivalents found",
+            "Info:".blue().bold()
+        );
+        return ExitCode::SUCCESS;
+    }
+
+    println!(
+        "\n{} Found {} patchable dependencies:",
+        "Discovered:".green().bold(),
+        deps.len()
+    );
+
+    for dep in deps {
+        println!("  {} {}", "•".green(), dep.name.bright_white().bold());
+        println!("      git:  {}", dep.git_url);
+        if let Some(reference) = &dep.branch_or_tag {
+            println!("      ref:  {}", reference);
+        }
+        if let Some(rev) = &dep.rev {
+            println!("      rev:  {}", rev);
+        }
+        println!(
+            "      path: {}",
+            dep.local_path.display().to_string().dimmed()
+        );
+    }
+
+    ExitCode::SUCCESS
+}
 
+fn patch_reset(
+    workspace_root: &Path,
+    clean: bool,
+    dry_run: bool,
+    only: &[String],
+    location: PatchTarget,
+    cargo: &Path,
+) -> ExitCode {
     println!(
         "{} Removing patches from {}...",
         "Resetting:".cyan().bold(),
         workspace_root.display().to_string().bright_white()
     );
 
-    let manager = PatchManager::new(&workspace_root);
+    let manager = PatchManager::new(workspace_root)
+        .with_only(only)
+        .with_target(location)
+        .with_cargo_path(cargo);
 
-    match manager.remove_patches() {
+    match manager.remove_patches(dry_run) {
         Ok(report) => {
             report.print();
 
+            if dry_run {
+                report.print_resulting_config();
+                println!("\n{} Dry run - config not modified", "Info:".blue().bold());
+                return ExitCode::SUCCESS;
+            }
+
             if clean && report.removed_count > 0 {
                 match manager.clean_cache() {
                     Ok(_) => {
@@ -434,9 +1633,107 @@ fn patch_reset(workspace_root: Option<String>, clean: bool) -> ExitCode {
     }
 }
 
-fn resolve_workspace_root(workspace_root: Option<String>) -> std::path::PathBuf {
+fn patch_retarget(workspace_root: &Path, name: &str, new_ref: GitRef) -> ExitCode {
+    println!(
+        "{} {} to {} {}...",
+        "Retargeting:".cyan().bold(),
+        name.bright_white().bold(),
+        new_ref.key(),
+        new_ref.name().bright_white()
+    );
+
+    let manager = PatchManager::new(workspace_root);
+
+    match manager.retarget_git(name, new_ref) {
+        Ok(changed) if changed.is_empty() => {
+            println!(
+                "\n{} Every manifest already points at that reference",
+                "✓".green().bold()
+            );
+            ExitCode::SUCCESS
+        }
+        Ok(changed) => {
+            for path in &changed {
+                let display = path.strip_prefix(workspace_root).unwrap_or(path);
+                println!("  {} {}", "•".green(), display.display());
+            }
+            println!(
+                "\n{} {} manifest(s) updated",
+                "✓".green().bold(),
+                changed.len()
+            );
+            ExitCode::SUCCESS
+        }
+        Err(e) => {
+            eprintln!("{} {}", "Error:".red().bold(), e);
+            ExitCode::from(1)
+        }
+    }
+}
+
+fn patch_status(workspace_root: &Path, prune: bool) -> ExitCode {
+    let manager = PatchManager::new(workspace_root);
+
+    if prune {
+        return match manager.prune_dangling_patches(false) {
+            Ok(report) if report.removed_count == 0 => {
+                println!("{} No dangling patches found", "✓".green().bold());
+                ExitCode::SUCCESS
+            }
+            Ok(report) => {
+                report.print();
+                ExitCode::SUCCESS
+            }
+            Err(e) => {
+                eprintln!("{} {}", "Error:".red().bold(), e);
+                ExitCode::from(1)
+            }
+        };
+    }
+
+    match manager.patch_status() {
+        Ok(status) => {
+            status.print();
+            ExitCode::SUCCESS
+        }
+        Err(e) => {
+            eprintln!("{} {}", "Error:".red().bold(), e);
+            ExitCode::from(1)
+        }
+    }
+}
+
+fn patch_verify_roundtrip(workspace_root: &Path, prefix: &str, cargo: &Path) -> ExitCode {
+    println!(
+        "{} Checking patch round-trip in {}...",
+        "Verifying:".cyan().bold(),
+        workspace_root.display().to_string().bright_white()
+    );
+
+    let manager = PatchManager::new(workspace_root)
+        .with_prefix(prefix)
+        .with_cargo_path(cargo);
+
+    match manager.verify_roundtrip() {
+        Ok(report) => {
+            report.print();
+
+            if report.is_lossless() {
+                ExitCode::SUCCESS
+            } else {
+                ExitCode::from(1)
+            }
+        }
+        Err(e) => {
+            eprintln!("{} {}", "Error:".red().bold(), e);
+            ExitCode::from(1)
+        }
+    }
+}
+
+/// The `--workspace-root` if given, else the detected workspace root.
+fn resolve_workspace_root(workspace_root: Option<PathBuf>) -> PathBuf {
     workspace_root
-        .map(std::path::PathBuf::from)
         .or_else(|| {
             let current = std::env::current_dir().ok()?;
             find_workspace_root(&current)
@@ -444,14 +1741,58 @@ fn resolve_workspace_root(workspace_root: Option<String>) -> std::path::PathBuf
         .unwrap_or_else(|| std::env::current_dir().expect("Failed to get current directory"))
 }
 
-fn health(
-    workspace_root: Option<String>,
-    verbose: bool,
-    json: bool,
-    output: Option<String>,
-    check: Vec<String>,
+/// Parse `--check` values, or pick the default checks when none are given.
+fn selected_checks(check: &[String], audit: bool) -> Result<Vec<HealthCheckType>, ExitCode> {
+    if check.is_empty() {
+        // Run all checks
+        let mut types = vec![
+            HealthCheckType::Git,
+            HealthCheckType::Version,
+            HealthCheckType::Msrv,
+            HealthCheckType::Tests,
+            HealthCheckType::Docs,
+            HealthCheckType::Specs,
+            HealthCheckType::Yanked,
+            HealthCheckType::Members,
+            HealthCheckType::Cycles,
+            HealthCheckType::Clippy,
+            HealthCheckType::Fmt,
+        ];
+        if audit {
+            types.push(HealthCheckType::Audit);
+        }
+        return Ok(types);
+    }
+
+    let mut types = Vec::new();
+    for check_str in check {
+        match check_str.parse::<HealthCheckType>() {
+            Ok(t) => types.push(t),
+            Err(_) => {
+                eprintln!(
+                    "{} Unknown check type: '{}'. Valid types: git, version, msrv, build, tests, docs, specs, yanked, members, cycles, clippy, fmt, audit",
+                    "Error:".red().bold(),
+                    check_str
+                );
+                return Err(ExitCode::from(1));
+            }
+        }
+    }
+    Ok(types)
+}
+
+/// Run the health checks and print a fix for each problem found.
+fn doctor(
+    check: &[String],
+    audit: bool,
+    workspace_root: &Path,
+    prefix: &str,
+    cargo: &Path,
 ) -> ExitCode {
-    let workspace_root = resolve_workspace_root(workspace_root);
+    let check_types = match selected_checks(check, audit) {
+        Ok(types) => types,
+        Err(code) => return code,
+    };
 
     println!(
         "{} Checking workspace health in {}...",
@@ -459,39 +1800,136 @@ fn health(
         workspace_root.display().to_string().bright_white()
     );
 
-    let checker = HealthChecker::new(&workspace_root);
+    let checker = HealthChecker::new(workspace_root)
+        .with_prefix(prefix)
+        .with_cargo_path(cargo)
+        .with_audit(audit);
+    let runtime = tokio::runtime::Runtime::new().expect("Failed to create tokio runtime");
+    let result = runtime.block_on(checker.check_selected(&check_types, false));
+    runtime.shutdown_background();
+    let report = match result {
+        Ok(report) => report,
+        Err(e) => {
+            eprintln!("{} {}", "Error:".red().bold(), e);
+            return ExitCode::from(1);
+        }
+    };
+
+    println!();
+    report.print_checklist();
 
-    // Parse check types
-    let check_types = if check.is_empty() {
-        // Run all checks
-        vec![
-            HealthCheckType::Git,
-            HealthCheckType::Version,
-            HealthCheckType::Tests,
-            HealthCheckType::Docs,
-            HealthCheckType::Specs,
-        ]
+    if report.has_failures() {
+        ExitCode::from(1)
     } else {
-        let mut types = Vec::new();
-        for check_str in &check {
-            match check_str.parse::<HealthCheckType>() {
-                Ok(t) => types.push(t),
-                Err(_) => {
-                    eprintln!(
-                        "{} Unknown check type: '{}'. Valid types: git, version, tests, docs, specs",
-                        "Error:".red().bold(),
-                        check_str
-                    );
-                    return ExitCode::from(1);
-                }
-            }
+        ExitCode::SUCCESS
+    }
+}
+
+/// Read a report saved with `health --json`.
+fn load_health_report(path: &Path) -> anyhow::Result<HealthReport> {
+    let content = std::fs::read_to_string(path)
+        .map_err(|e| anyhow::anyhow!("Failed to read {}: {}", path.display(), e))?;
+    serde_json::from_str(&content)
+        .map_err(|e| anyhow::anyhow!("{} is not a health --json report: {}", path.display(), e))
+}
+
+fn health(args: HealthArgs, workspace_root: &Path, prefix: &str, cargo: &Path) -> ExitCode {
+    let HealthArgs {
+        verbose,
+        json,
+        output,
+        html,
+        junit,
+        compare,
+        check,
+        default_members,
+        packages,
+        since,
+        test_args,
+        doc_args,
+        doc_coverage,
+        depth,
+        target,
+        specs_info_only,
+        spec_ext,
+        spec_threshold,
+        by_owner,
+        audit,
+        fetch,
+        jobs,
+        timeout_secs,
+        fail_on,
+    } = args;
+
+    let previous = match compare.as_deref().map(load_health_report).transpose() {
+        Ok(previous) => previous,
+        Err(e) => {
+            eprintln!("{} {}", "Error:".red().bold(), e);
+            return ExitCode::from(1);
+        }
+    };
+
+    // Keep stdout to the report itself so `--json` output can be saved and
+    // fed back through `--compare`
+    if !json {
+        println!(
+            "{} Checking workspace health in {}...",
+            "Analyzing:".cyan().bold(),
+            workspace_root.display().to_string().bright_white()
+        );
+    }
+
+    let checker = HealthChecker::new(workspace_root)
+        .with_prefix(prefix)
+        .with_cargo_path(cargo)
+        .with_default_members(default_members)
+        .with_packages(&packages)
+        .with_target(target)
+        .with_specs_info_only(specs_info_only)
+        .with_spec_extensions(&spec_ext)
+        .with_spec_threshold(spec_threshold)
+        .with_audit(audit)
+        .with_fetch(fetch)
+        .with_doc_coverage(doc_coverage)
+        .with_timeout(Duration::from_secs(timeout_secs));
+    let checker = match jobs {
+        Some(jobs) => checker.with_max_concurrency(jobs),
+        None => checker,
+    };
+    let split = |args: &str| args.split_whitespace().map(String::from).collect();
+    let checker = match test_args {
+        Some(args) => checker.with_test_args(split(&args)),
+        None => checker,
+    };
+    let checker = match doc_args {
+        Some(args) => checker.with_doc_args(split(&args)),
+        None => checker,
+    };
+    let checker = match depth {
+        Some(depth) => checker.with_max_depth(depth),
+        None => checker,
+    };
+    let checker = match since.as_deref().map(|base| checker.changed_since(base)) {
+        Some(Ok(changed)) => changed,
+        Some(Err(e)) => {
+            eprintln!("{} {}", "Error:".red().bold(), e);
+            return ExitCode::from(1);
         }
-        types
+        None => checker,
+    };
+
+    // Parse check types
+    let check_types = match selected_checks(&check, audit) {
+        Ok(types) => types,
+        Err(code) => return code,
     };
 
     // Run checks asynchronously
     let runtime = tokio::runtime::Runtime::new().expect("Failed to create tokio runtime");
-    let report = match runtime.block_on(checker.check_selected(&check_types, verbose)) {
+    let result = runtime.block_on(checker.check_selected(&check_types, verbose));
+    // Don't wait on cargo processes left behind by timed-out checks
+    runtime.shutdown_background();
+    let report = match result {
         Ok(report) => report,
         Err(e) => {
             eprintln!("{} {}", "Error:".red().bold(), e);
@@ -499,9 +1937,39 @@ fn health(
         }
     };
 
+    let codeowners = if by_owner {
+        match CodeOwners::load(workspace_root) {
+            Ok(Some(codeowners)) => Some(codeowners),
+            Ok(None) => {
+                eprintln!(
+                    "{} No CODEOWNERS file found, all packages are unowned",
+                    "Warning:".yellow().bold()
+                );
+                Some(CodeOwners::default())
+            }
+            Err(e) => {
+                eprintln!("{} {}", "Error:".red().bold(), e);
+                return ExitCode::from(1);
+            }
+        }
+    } else {
+        None
+    };
+
     // Output results
     if json {
-        match serde_json::to_string_pretty(&report) {
+        let json_result = match (&codeowners, &previous) {
+            (Some(codeowners), Some(previous)) => {
+                serde_json::to_string_pretty(&serde_json::json!({
+                    "by_owner": report.by_owner(codeowners),
+                    "diff": report.diff(previous),
+                }))
+            }
+            (Some(codeowners), None) => serde_json::to_string_pretty(&report.by_owner(codeowners)),
+            (None, Some(previous)) => serde_json::to_string_pretty(&report.diff(previous)),
+            (None, None) => serde_json::to_string_pretty(&report),
+        };
+        match json_result {
             Ok(json_output) => {
                 println!("{}", json_output);
             }
@@ -516,18 +1984,37 @@ fn health(
         }
     } else {
         report.print_terminal(verbose);
+        if let Some(codeowners) = &codeowners {
+            report.print_by_owner(codeowners);
+        }
+        if let Some(previous) = &previous {
+            report.diff(previous).print_terminal();
+        }
     }
 
-    // Write markdown report if requested
-    if let Some(output_path) = output {
-        let markdown = report.to_markdown();
-        match std::fs::write(&output_path, markdown) {
+    // Write markdown/HTML/JUnit reports if requested
+    let reports = [
+        (
+            output,
+            HealthReport::to_markdown as fn(&HealthReport) -> String,
+        ),
+        (html, HealthReport::to_html),
+        (junit, HealthReport::to_junit_xml),
+    ];
+    for (path, render) in reports {
+        let Some(output_path) = path else { continue };
+        match std::fs::write(&output_path, render(&report)) {
             Ok(_) => {
-                println!(
+                let saved = format!(
                     "\n{} Report written to {}",
                     "Saved:".green().bold(),
                     output_path.bright_white()
                 );
+                if json {
+                    eprintln!("{}", saved);
+                } else {
+                    println!("{}", saved);
+                }
             }
             Err(e) => {
                 eprintln!("{} Failed to write report: {}", "Error:".red().bold(), e);
@@ -537,7 +2024,7 @@ fn health(
     }
 
     // Exit with appropriate code
-    if report.has_failures() {
+    if report.worst_status() >= fail_on.status() {
         ExitCode::from(1)
     } else {
         ExitCode::SUCCESS
@@ -1,9 +1,9 @@
 //! Cargo.toml file parsing and manipulation utilities.
 
 use anyhow::{Context, Result};
-use semver::Version;
+use semver::{Version, VersionReq};
 use std::path::{Path, PathBuf};
-use toml_edit::{value, DocumentMut, Item};
+use toml_edit::{value, DocumentMut, Item, TableLike};
 
 /// Represents a Cargo.toml manifest file.
 #[derive(Debug, Clone)]
@@ -11,16 +11,95 @@ pub struct CargoManifest {
     pub path: PathBuf,
     pub package_name: String,
     pub version: Version,
+    /// `package.rust-version`, if set to a literal value.
+    pub rust_version: Option<String>,
     pub dependencies: Vec<Dependency>,
     document: DocumentMut,
+    format_style: FormatStyle,
+}
+
+/// Whitespace applied around `=` when the tool rewrites a version value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FormatStyle {
+    /// toml_edit's default: the value gets a leading space, the key keeps
+    /// its existing spacing.
+    #[default]
+    Default,
+    /// Always `key = "value"`.
+    Spaced,
+    /// Always `key="value"`.
+    Compact,
+    /// Keep whatever whitespace the rewritten value already had.
+    Preserve,
+}
+
+impl std::str::FromStr for FormatStyle {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "default" => Ok(Self::Default),
+            "spaced" => Ok(Self::Spaced),
+            "compact" => Ok(Self::Compact),
+            "preserve" => Ok(Self::Preserve),
+            _ => Err(format!("Unknown format style: {}", s)),
+        }
+    }
 }
 
 /// Represents a dependency in Cargo.toml.
 #[derive(Debug, Clone)]
 pub struct Dependency {
+    /// Crate name, taken from `package = "..."` for renamed dependencies.
     pub name: String,
+    /// Key the dependency is declared under in the manifest.
+    pub key: String,
+    /// Exact version, if the requirement is a plain `x.y.z` string.
     pub version: Option<Version>,
+    /// Parsed version requirement (`0.20.0`, `^0.20`, `~0.20.0`, ...).
+    pub requirement: Option<VersionReq>,
+    /// Raw version requirement string as written in the manifest.
+    pub req: Option<String>,
     pub dep_type: DependencyType,
+    /// Inherited from the root `[workspace.dependencies]` via `workspace = true`.
+    pub workspace: bool,
+    /// Declared with `optional = true`.
+    pub optional: bool,
+    /// `false` when declared with `default-features = false`.
+    pub default_features: bool,
+    pub source: DependencySource,
+}
+
+/// Where a dependency is fetched from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DependencySource {
+    /// crates.io or another registry.
+    Registry,
+    /// A git repository, with the `rev`, `tag` or `branch` it's pinned to.
+    Git { url: String, rev: Option<String> },
+    /// A local checkout, as written in the manifest.
+    Path(PathBuf),
+}
+
+impl DependencySource {
+    fn parse(item: &Item) -> Self {
+        let Some(table) = item.as_table_like() else {
+            return Self::Registry;
+        };
+        let get = |key: &str| table.get(key).and_then(|v| v.as_str());
+
+        if let Some(url) = get("git") {
+            let rev = get("rev").or_else(|| get("tag")).or_else(|| get("branch"));
+            Self::Git {
+                url: url.to_string(),
+                rev: rev.map(String::from),
+            }
+        } else if let Some(path) = get("path") {
+            Self::Path(PathBuf::from(path))
+        } else {
+            Self::Registry
+        }
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -30,6 +109,38 @@ pub enum DependencyType {
     Build,
 }
 
+impl DependencyType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Normal => "normal",
+            Self::Dev => "dev",
+            Self::Build => "build",
+        }
+    }
+
+    /// Manifest table name for this dependency type.
+    pub fn section_name(&self) -> &'static str {
+        match self {
+            Self::Normal => "dependencies",
+            Self::Dev => "dev-dependencies",
+            Self::Build => "build-dependencies",
+        }
+    }
+}
+
+impl std::str::FromStr for DependencyType {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "normal" => Ok(Self::Normal),
+            "dev" => Ok(Self::Dev),
+            "build" => Ok(Self::Build),
+            _ => Err(format!("Unknown dependency type: {}", s)),
+        }
+    }
+}
+
 impl CargoManifest {
     /// Load a Cargo.toml file from disk.
     pub fn load(path: impl AsRef<Path>) -> Result<Self> {
@@ -40,85 +151,138 @@ impl CargoManifest {
             .parse()
             .with_context(|| format!("Failed to parse {}", path.display()))?;
 
-        let package_name = document["package"]["name"]
-            .as_str()
+        let package = document.get("package");
+
+        let package_name = package
+            .and_then(|p| p.get("name"))
+            .and_then(|v| v.as_str())
             .ok_or_else(|| anyhow::anyhow!("Missing package.name in {}", path.display()))?
             .to_string();
 
-        let version_str = document["package"]["version"]
-            .as_str()
+        let version_str = package
+            .and_then(|p| p.get("version"))
+            .and_then(|v| v.as_str())
             .ok_or_else(|| anyhow::anyhow!("Missing package.version in {}", path.display()))?;
 
         let version = Version::parse(version_str)
             .with_context(|| format!("Invalid version '{}' in {}", version_str, path.display()))?;
 
-        let mut dependencies = Vec::new();
+        let rust_version = package
+            .and_then(|p| p.get("rust-version"))
+            .and_then(|v| v.as_str())
+            .map(String::from);
 
-        // Parse dependencies
-        if let Some(Item::Table(deps)) = document.get("dependencies") {
-            for (name, item) in deps.iter() {
-                if let Some(dep) = Self::parse_dependency(name, item, DependencyType::Normal) {
-                    dependencies.push(dep);
-                }
-            }
-        }
-
-        // Parse dev-dependencies
-        if let Some(Item::Table(deps)) = document.get("dev-dependencies") {
-            for (name, item) in deps.iter() {
-                if let Some(dep) = Self::parse_dependency(name, item, DependencyType::Dev) {
-                    dependencies.push(dep);
-                }
-            }
-        }
-
-        // Parse build-dependencies
-        if let Some(Item::Table(deps)) = document.get("build-dependencies") {
-            for (name, item) in deps.iter() {
-                if let Some(dep) = Self::parse_dependency(name, item, DependencyType::Build) {
-                    dependencies.push(dep);
-                }
-            }
-        }
+        let dependencies = Self::parse_dependencies(&document);
 
         Ok(Self {
             path: path.to_path_buf(),
             package_name,
             version,
+            rust_version,
             dependencies,
             document,
+            format_style: FormatStyle::default(),
         })
     }
 
-    fn parse_dependency(name: &str, item: &Item, dep_type: DependencyType) -> Option<Dependency> {
-        let version = match item {
-            Item::Value(val) if val.is_str() => {
-                // Simple version string: "0.20.0-alpha.1"
-                val.as_str().and_then(|s| Version::parse(s).ok())
-            }
-            Item::Table(_) => {
-                // Table format: { version = "0.20.0-alpha.1", ... }
-                item.get("version")
-                    .and_then(|v| v.as_str())
-                    .and_then(|s| Version::parse(s).ok())
+    /// Parse the entries of every dependency section.
+    fn parse_dependencies(document: &DocumentMut) -> Vec<Dependency> {
+        let mut dependencies = Vec::new();
+
+        for dep_type in [
+            DependencyType::Normal,
+            DependencyType::Dev,
+            DependencyType::Build,
+        ] {
+            if let Some(Item::Table(deps)) = document.get(dep_type.section_name()) {
+                for (name, item) in deps.iter() {
+                    if let Some(dep) = Self::parse_dependency(name, item, dep_type.clone()) {
+                        dependencies.push(dep);
+                    }
+                }
             }
-            _ => None,
+        }
+
+        dependencies
+    }
+
+    fn parse_dependency(name: &str, item: &Item, dep_type: DependencyType) -> Option<Dependency> {
+        let req = dependency_req(item);
+        let version = req.and_then(|s| Version::parse(s).ok());
+        let requirement = req.and_then(|s| VersionReq::parse(s).ok());
+        let workspace = Self::is_workspace_inherited(item);
+        let flag = |key: &str| {
+            item.as_table_like()
+                .and_then(|t| t.get(key))
+                .and_then(|v| v.as_bool())
         };
 
         Some(Dependency {
-            name: name.to_string(),
+            name: Self::crate_name(name, item).to_string(),
+            key: name.to_string(),
             version,
+            requirement,
+            req: req.map(String::from),
             dep_type,
+            workspace,
+            optional: flag("optional").unwrap_or(false),
+            default_features: flag("default-features")
+                .or_else(|| flag("default_features"))
+                .unwrap_or(true),
+            source: DependencySource::parse(item),
         })
     }
 
+    /// Resolve inherited (`workspace = true`) dependencies against the root
+    /// `[workspace.dependencies]`, filling in their version and, for entries
+    /// renamed in the root, their crate name.
+    ///
+    /// The entries stay marked as inherited, so [`Self::update_dependency`]
+    /// still leaves them to the root manifest.
+    pub fn inherit_workspace_dependencies(&mut self, root: &WorkspaceDependencies) {
+        for dep in self.dependencies.iter_mut().filter(|d| d.workspace) {
+            let Some(item) = root.entry(&dep.key) else {
+                continue;
+            };
+            let req = dependency_req(item);
+            dep.name = Self::crate_name(&dep.key, item).to_string();
+            dep.version = req.and_then(|s| Version::parse(s).ok());
+            dep.requirement = req.and_then(|s| VersionReq::parse(s).ok());
+            dep.req = req.map(String::from);
+            dep.source = DependencySource::parse(item);
+        }
+    }
+
+    /// Re-read `dependencies` from the document, keeping what was resolved
+    /// for inherited entries.
+    fn refresh_dependencies(&mut self) {
+        let mut dependencies = Self::parse_dependencies(&self.document);
+        for dep in dependencies.iter_mut().filter(|d| d.workspace) {
+            if let Some(old) = self
+                .dependencies
+                .iter()
+                .find(|d| d.key == dep.key && d.dep_type == dep.dep_type && d.workspace)
+            {
+                *dep = old.clone();
+            }
+        }
+        self.dependencies = dependencies;
+    }
+
+    /// Set the whitespace style used when rewriting versions.
+    pub fn set_format_style(&mut self, style: FormatStyle) {
+        self.format_style = style;
+    }
+
     /// Update the package version.
     pub fn set_version(&mut self, new_version: &Version) -> Result<()> {
         self.version = new_version.clone();
 
         if let Some(package) = self.document.get_mut("package") {
             if let Some(pkg_table) = package.as_table_mut() {
-                pkg_table["version"] = value(new_version.to_string());
+                if !write_version(pkg_table, "version", new_version, self.format_style) {
+                    pkg_table["version"] = value(new_version.to_string());
+                }
             }
         }
 
@@ -126,6 +290,9 @@ impl CargoManifest {
     }
 
     /// Update a dependency version.
+    ///
+    /// Entries that inherit from the workspace (`workspace = true`) are left
+    /// untouched; update the root with [`WorkspaceDependencies`] instead.
     pub fn update_dependency(&mut self, dep_name: &str, new_version: &Version) -> Result<()> {
         let sections = [
             ("dependencies", DependencyType::Normal),
@@ -134,20 +301,51 @@ impl CargoManifest {
         ];
 
         for (section, dep_type) in &sections {
-            if let Some(deps) = self.document.get_mut(section) {
-                if let Some(deps_table) = deps.as_table_mut() {
-                    if let Some(dep_item) = deps_table.get_mut(dep_name) {
-                        Self::update_dep_item_static(dep_item, new_version)?;
-
-                        // Update our internal tracking
-                        if let Some(dep) = self
-                            .dependencies
-                            .iter_mut()
-                            .find(|d| d.name == dep_name && &d.dep_type == dep_type)
-                        {
-                            dep.version = Some(new_version.clone());
-                        }
-                    }
+            let Some(deps_table) = self
+                .document
+                .get_mut(section)
+                .and_then(|deps| deps.as_table_mut())
+            else {
+                continue;
+            };
+
+            // Renamed dependencies are keyed by their alias, not the crate name
+            let keys: Vec<String> = deps_table
+                .iter()
+                .filter(|(key, item)| Self::crate_name(key, item) == dep_name)
+                .map(|(key, _)| key.to_string())
+                .collect();
+
+            for key in keys {
+                let Some(dep_item) = deps_table.get_mut(&key) else {
+                    continue;
+                };
+                if Self::is_workspace_inherited(dep_item) {
+                    continue;
+                }
+                let written = if dep_item.is_str() {
+                    // Simple string version
+                    write_version(deps_table, &key, new_version, self.format_style)
+                } else {
+                    // Inline or standard table with a version key; sibling
+                    // keys such as `features` are left as they are
+                    dep_item.as_table_like_mut().is_some_and(|t| {
+                        write_version(t, "version", new_version, self.format_style)
+                    })
+                };
+                if !written {
+                    continue;
+                }
+
+                // Update our internal tracking
+                if let Some(dep) = self
+                    .dependencies
+                    .iter_mut()
+                    .find(|d| d.key == key && &d.dep_type == dep_type)
+                {
+                    dep.version = Some(new_version.clone());
+                    dep.requirement = VersionReq::parse(&new_version.to_string()).ok();
+                    dep.req = Some(new_version.to_string());
                 }
             }
         }
@@ -155,39 +353,465 @@ impl CargoManifest {
         Ok(())
     }
 
-    fn update_dep_item_static(item: &mut Item, new_version: &Version) -> Result<()> {
-        match item {
-            Item::Value(val) if val.is_str() => {
-                // Simple string version
-                *item = value(new_version.to_string());
-            }
-            Item::Table(_) => {
-                // Table format with version key
-                if let Some(version_item) = item.get_mut("version") {
-                    *version_item = value(new_version.to_string());
+    /// Add `name` to the section for `dep_type`, creating the table if needed.
+    ///
+    /// If the section already declares the crate, its version is overwritten
+    /// instead.
+    pub fn add_dependency(
+        &mut self,
+        name: &str,
+        version: &Version,
+        dep_type: DependencyType,
+    ) -> Result<()> {
+        let section = dep_type.section_name();
+        if self.document.get(section).is_none() {
+            self.document[section] = toml_edit::table();
+        }
+        let deps_table = self.document[section].as_table_like_mut().ok_or_else(|| {
+            anyhow::anyhow!("[{}] is not a table in {}", section, self.path.display())
+        })?;
+
+        let existing = deps_table
+            .iter()
+            .find(|(key, item)| Self::crate_name(key, item) == name)
+            .map(|(key, _)| key.to_string());
+
+        match existing {
+            Some(key) => {
+                let item = deps_table.get_mut(&key).expect("key was just found");
+                if Self::is_workspace_inherited(item) {
+                    anyhow::bail!(
+                        "{} inherits its version from the workspace in {}",
+                        name,
+                        self.path.display()
+                    );
+                }
+                if item.is_str() {
+                    write_version(deps_table, &key, version, self.format_style);
+                } else if let Some(table) = item.as_table_like_mut() {
+                    if !write_version(table, "version", version, self.format_style) {
+                        table.insert("version", value(version.to_string()));
+                    }
                 }
             }
-            _ => {}
+            None => {
+                deps_table.insert(name, value(version.to_string()));
+            }
         }
+
+        self.refresh_dependencies();
         Ok(())
     }
 
+    /// Remove `name` from the section for `dep_type`.
+    ///
+    /// A section left empty is removed along with its header. Returns whether
+    /// anything was removed.
+    pub fn remove_dependency(&mut self, name: &str, dep_type: DependencyType) -> bool {
+        let section = dep_type.section_name();
+        let Some(deps_table) = self
+            .document
+            .get_mut(section)
+            .and_then(|deps| deps.as_table_like_mut())
+        else {
+            return false;
+        };
+
+        let keys: Vec<String> = deps_table
+            .iter()
+            .filter(|(key, item)| Self::crate_name(key, item) == name)
+            .map(|(key, _)| key.to_string())
+            .collect();
+        for key in &keys {
+            deps_table.remove(key);
+        }
+        if deps_table.is_empty() {
+            self.document.remove(section);
+        }
+
+        self.refresh_dependencies();
+        !keys.is_empty()
+    }
+
+    /// Crate a dependency entry refers to: its `package` key if renamed,
+    /// otherwise the table key.
+    fn crate_name<'a>(key: &'a str, item: &'a Item) -> &'a str {
+        item.as_table_like()
+            .and_then(|t| t.get("package"))
+            .and_then(|v| v.as_str())
+            .unwrap_or(key)
+    }
+
+    /// Whether `rust-version` is inherited with `rust-version.workspace = true`.
+    pub fn inherits_rust_version(&self) -> bool {
+        self.document
+            .get("package")
+            .and_then(|p| p.get("rust-version"))
+            .is_some_and(Self::is_workspace_inherited)
+    }
+
+    fn is_workspace_inherited(item: &Item) -> bool {
+        item.as_table_like()
+            .and_then(|t| t.get("workspace"))
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false)
+    }
+
     /// Save the manifest back to disk.
+    ///
+    /// The file is replaced atomically (see [`write_atomic`]), so an
+    /// interrupted save leaves either the old or the new manifest.
     pub fn save(&self) -> Result<()> {
-        std::fs::write(&self.path, self.document.to_string())
+        write_atomic(&self.path, &self.contents())
             .with_context(|| format!("Failed to write {}", self.path.display()))?;
         Ok(())
     }
 
+    /// The manifest as it would be saved.
+    pub fn contents(&self) -> String {
+        self.document.to_string()
+    }
+
     /// Get all embeddenator-* dependencies.
     pub fn embeddenator_dependencies(&self) -> Vec<&Dependency> {
+        self.dependencies_with_prefix("embeddenator-")
+    }
+
+    /// Get all dependencies whose crate name starts with `prefix`.
+    pub fn dependencies_with_prefix(&self, prefix: &str) -> Vec<&Dependency> {
         self.dependencies
             .iter()
-            .filter(|d| d.name.starts_with("embeddenator-"))
+            .filter(|d| d.name.starts_with(prefix))
             .collect()
     }
 }
 
+/// The `[workspace.dependencies]` table of a workspace root Cargo.toml.
+///
+/// The root is usually a virtual manifest without `[package]`, so it is
+/// loaded separately from [`CargoManifest`].
+#[derive(Debug, Clone)]
+pub struct WorkspaceDependencies {
+    pub path: PathBuf,
+    document: DocumentMut,
+    format_style: FormatStyle,
+}
+
+impl WorkspaceDependencies {
+    /// Load the root manifest at `path`.
+    ///
+    /// Returns `None` if the file doesn't exist or has no
+    /// `[workspace.dependencies]` table.
+    pub fn load(path: impl AsRef<Path>) -> Result<Option<Self>> {
+        let path = path.as_ref();
+        if !path.exists() {
+            return Ok(None);
+        }
+
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        Self::parse(path, &content)
+    }
+
+    /// Like [`WorkspaceDependencies::load`], from `content` already read
+    /// from (or staged for) `path`.
+    pub fn parse(path: impl AsRef<Path>, content: &str) -> Result<Option<Self>> {
+        let path = path.as_ref();
+        let document: DocumentMut = content
+            .parse()
+            .with_context(|| format!("Failed to parse {}", path.display()))?;
+
+        let has_deps = document
+            .get("workspace")
+            .and_then(|ws| ws.get("dependencies"))
+            .is_some_and(|deps| deps.is_table_like());
+
+        Ok(has_deps.then(|| Self {
+            path: path.to_path_buf(),
+            document,
+            format_style: FormatStyle::default(),
+        }))
+    }
+
+    /// Set the whitespace style used when rewriting versions.
+    pub fn set_format_style(&mut self, style: FormatStyle) {
+        self.format_style = style;
+    }
+
+    fn dependencies(&self) -> Option<&dyn toml_edit::TableLike> {
+        self.document
+            .get("workspace")
+            .and_then(|ws| ws.get("dependencies"))
+            .and_then(|deps| deps.as_table_like())
+    }
+
+    /// The entry declared under `key`.
+    pub(crate) fn entry(&self, key: &str) -> Option<&Item> {
+        self.dependencies()?.get(key)
+    }
+
+    /// Key the crate `name` is declared under, following `package` renames.
+    fn key_of(&self, name: &str) -> Option<String> {
+        self.dependencies()?
+            .iter()
+            .find(|(key, item)| CargoManifest::crate_name(key, item) == name)
+            .map(|(key, _)| key.to_string())
+    }
+
+    /// The raw version requirement declared for `name`.
+    pub fn req(&self, name: &str) -> Option<&str> {
+        self.entry(&self.key_of(name)?).and_then(dependency_req)
+    }
+
+    /// The version pinned for `name`, if it has a parseable version.
+    pub fn version(&self, name: &str) -> Option<Version> {
+        self.req(name).and_then(|s| Version::parse(s).ok())
+    }
+
+    /// The version requirement declared for `name`, if it parses.
+    pub fn requirement(&self, name: &str) -> Option<VersionReq> {
+        self.req(name).and_then(|s| VersionReq::parse(s).ok())
+    }
+
+    /// Update the version pinned for `name`.
+    ///
+    /// Returns `false` if the dependency isn't declared or has no version.
+    pub fn set_version(&mut self, name: &str, new_version: &Version) -> bool {
+        let style = self.format_style;
+        let Some(key) = self.key_of(name) else {
+            return false;
+        };
+        let Some(deps) = self
+            .document
+            .get_mut("workspace")
+            .and_then(|ws| ws.get_mut("dependencies"))
+            .and_then(|deps| deps.as_table_like_mut())
+        else {
+            return false;
+        };
+
+        match deps.get_mut(&key) {
+            Some(item) if item.as_str().is_some() => write_version(deps, &key, new_version, style),
+            Some(item) => item
+                .as_table_like_mut()
+                .is_some_and(|t| write_version(t, "version", new_version, style)),
+            None => false,
+        }
+    }
+
+    /// Save the manifest back to disk.
+    ///
+    /// The file is replaced atomically (see [`write_atomic`]), so an
+    /// interrupted save leaves either the old or the new manifest.
+    pub fn save(&self) -> Result<()> {
+        write_atomic(&self.path, &self.contents())
+            .with_context(|| format!("Failed to write {}", self.path.display()))?;
+        Ok(())
+    }
+
+    /// The manifest as it would be saved.
+    pub fn contents(&self) -> String {
+        self.document.to_string()
+    }
+}
+
+/// A set of manifest writes applied all-or-nothing.
+///
+/// Contents are staged in memory; [`ManifestTransaction::commit`] snapshots
+/// the files on disk, writes them in order, and restores the snapshot if any
+/// write fails, so a failed save never leaves the workspace half-updated.
+#[derive(Debug, Default)]
+pub struct ManifestTransaction {
+    staged: Vec<(PathBuf, String)>,
+}
+
+impl ManifestTransaction {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Stage `contents` for `path`, replacing anything staged for it before.
+    pub fn stage(&mut self, path: impl Into<PathBuf>, contents: String) {
+        let path = path.into();
+        match self.staged.iter_mut().find(|(p, _)| *p == path) {
+            Some(entry) => entry.1 = contents,
+            None => self.staged.push((path, contents)),
+        }
+    }
+
+    pub fn stage_manifest(&mut self, manifest: &CargoManifest) {
+        self.stage(&manifest.path, manifest.contents());
+    }
+
+    pub fn stage_workspace_dependencies(&mut self, deps: &WorkspaceDependencies) {
+        self.stage(&deps.path, deps.contents());
+    }
+
+    /// Contents staged for `path`, if any.
+    pub fn staged(&self, path: &Path) -> Option<&str> {
+        self.staged
+            .iter()
+            .find(|(p, _)| p == path)
+            .map(|(_, contents)| contents.as_str())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.staged.is_empty()
+    }
+
+    /// Write every staged file, restoring the originals if any write fails.
+    pub fn commit(self) -> Result<()> {
+        self.commit_with(write_atomic)
+    }
+
+    fn commit_with(self, mut write: impl FnMut(&Path, &str) -> std::io::Result<()>) -> Result<()> {
+        let mut originals = Vec::with_capacity(self.staged.len());
+        for (path, _) in &self.staged {
+            match std::fs::read_to_string(path) {
+                Ok(content) => originals.push(Some(content)),
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => originals.push(None),
+                Err(e) => {
+                    return Err(e).with_context(|| format!("Failed to read {}", path.display()))
+                }
+            }
+        }
+
+        for (index, (path, contents)) in self.staged.iter().enumerate() {
+            if let Err(e) = write(path, contents) {
+                // The failed write may have truncated its file, so restore it too
+                let failed_restores: Vec<String> = self.staged[..=index]
+                    .iter()
+                    .zip(&originals)
+                    .filter_map(|((path, _), original)| {
+                        let restored = match original {
+                            Some(content) => std::fs::write(path, content),
+                            None => std::fs::remove_file(path).or_else(|e| {
+                                if e.kind() == std::io::ErrorKind::NotFound {
+                                    Ok(())
+                                } else {
+                                    Err(e)
+                                }
+                            }),
+                        };
+                        restored.err().map(|_| path.display().to_string())
+                    })
+                    .collect();
+
+                let message = if failed_restores.is_empty() {
+                    format!(
+                        "Failed to write {}; no manifests were changed",
+                        path.display()
+                    )
+                } else {
+                    format!(
+                        "Failed to write {}; could not restore {}",
+                        path.display(),
+                        failed_restores.join(", ")
+                    )
+                };
+                return Err(anyhow::Error::new(e).context(message));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Write `contents` to `path` by way of a temporary file in the same
+/// directory that is renamed into place, so readers (and a crash mid-write)
+/// never see a truncated file. The temporary file is removed on failure.
+///
+/// A symlinked `path` is resolved first so the link survives and its target
+/// is replaced, and an existing file's permissions carry over.
+pub(crate) fn write_atomic(path: &Path, contents: &str) -> std::io::Result<()> {
+    use std::io::Write;
+
+    let resolved = std::fs::canonicalize(path);
+    let path = resolved.as_deref().unwrap_or(path);
+    let permissions = std::fs::metadata(path).ok().map(|m| m.permissions());
+
+    let dir = match path.parent() {
+        Some(dir) if !dir.as_os_str().is_empty() => dir,
+        _ => Path::new("."),
+    };
+    let file_name = path
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let temp_path = dir.join(format!(".{}.{}.tmp", file_name, std::process::id()));
+
+    let result = (|| {
+        let mut file = std::fs::File::create(&temp_path)?;
+        file.write_all(contents.as_bytes())?;
+        if let Some(permissions) = permissions {
+            file.set_permissions(permissions)?;
+        }
+        file.sync_all()?;
+        std::fs::rename(&temp_path, path)
+    })();
+    if result.is_err() {
+        let _ = std::fs::remove_file(&temp_path);
+    }
+    result
+}
+
+/// The version requirement of a dependency entry, in string or table form.
+fn dependency_req(item: &Item) -> Option<&str> {
+    match item.as_str() {
+        // Simple version string: "0.20.0-alpha.1"
+        Some(req) => Some(req),
+        // Inline or standard table: { version = "0.20.0-alpha.1", ... }
+        None => item
+            .as_table_like()
+            .and_then(|t| t.get("version"))
+            .and_then(|v| v.as_str()),
+    }
+}
+
+/// Replace the value of `key` in `table` with `new_version`, applying `style`.
+///
+/// The value is rewritten in place: any trailing comment or whitespace after
+/// it is kept, and `style` only decides the spacing around `=`.
+///
+/// Returns `false` if `table` has no such key.
+fn write_version(
+    table: &mut dyn TableLike,
+    key: &str,
+    new_version: &Version,
+    style: FormatStyle,
+) -> bool {
+    let Some((mut key, item)) = table.get_key_value_mut(key) else {
+        return false;
+    };
+
+    let old_decor = item.as_value().map(|v| v.decor().clone());
+    *item = value(new_version.to_string());
+
+    if let Some(val) = item.as_value_mut() {
+        if let Some(suffix) = old_decor.as_ref().and_then(|d| d.suffix()) {
+            val.decor_mut().set_suffix(suffix.clone());
+        }
+        match style {
+            FormatStyle::Default => {}
+            FormatStyle::Spaced => {
+                key.leaf_decor_mut().set_suffix(" ");
+                val.decor_mut().set_prefix(" ");
+            }
+            FormatStyle::Compact => {
+                key.leaf_decor_mut().set_suffix("");
+                val.decor_mut().set_prefix("");
+            }
+            FormatStyle::Preserve => {
+                if let Some(decor) = old_decor {
+                    *val.decor_mut() = decor;
+                }
+            }
+        }
+    }
+
+    true
+}
+
 #[cfg(test)]
 #[path = "cargo_tests.rs"]
 mod tests;
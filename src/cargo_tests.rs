@@ -1,4 +1,4 @@
-use crate::cargo::CargoManifest;
+use crate::cargo::{CargoManifest, ManifestTransaction};
 use std::fs;
 use std::path::PathBuf;
 use tempfile::TempDir;
@@ -131,3 +131,455 @@ fn test_embeddenator_dependencies() {
         .iter()
         .any(|d| d.name == "embeddenator-io"));
 }
+
+#[test]
+fn test_format_style() {
+    use crate::cargo::FormatStyle;
+
+    let temp_dir = TempDir::new().unwrap();
+    let manifest_path = temp_dir.path().join("Cargo.toml");
+    let original = "[package]\nname=\"test-package\"\nversion=\"0.20.0\"\n\n[dependencies]\nembeddenator-vsa  =  \"0.20.0\"\n\n[dependencies.embeddenator-io]\nversion=\"0.20.0\"\n";
+    let new_version = semver::Version::parse("0.21.0").unwrap();
+
+    let rewrite = |style: FormatStyle| {
+        fs::write(&manifest_path, original).unwrap();
+        let mut manifest = CargoManifest::load(&manifest_path).unwrap();
+        manifest.set_format_style(style);
+        manifest.set_version(&new_version).unwrap();
+        manifest
+            .update_dependency("embeddenator-vsa", &new_version)
+            .unwrap();
+        manifest
+            .update_dependency("embeddenator-io", &new_version)
+            .unwrap();
+        manifest.save().unwrap();
+        fs::read_to_string(&manifest_path).unwrap()
+    };
+
+    let default = rewrite(FormatStyle::Default);
+    assert!(default.contains("version= \"0.21.0\""));
+    assert!(default.contains("embeddenator-vsa  = \"0.21.0\""));
+
+    let spaced = rewrite(FormatStyle::Spaced);
+    assert!(spaced.contains("\nversion = \"0.21.0\""));
+    assert!(spaced.contains("embeddenator-vsa = \"0.21.0\""));
+
+    let compact = rewrite(FormatStyle::Compact);
+    assert_eq!(
+        compact,
+        original.replace("0.20.0", "0.21.0").replace("  =  ", "=")
+    );
+
+    let preserve = rewrite(FormatStyle::Preserve);
+    assert_eq!(preserve, original.replace("0.20.0", "0.21.0"));
+}
+
+#[test]
+fn test_transaction_commit() {
+    let temp_dir = TempDir::new().unwrap();
+    let existing = create_test_manifest(&temp_dir, "pkg-a", "0.20.0");
+    let created = temp_dir.path().join("pkg-b/Cargo.toml");
+    fs::create_dir_all(created.parent().unwrap()).unwrap();
+
+    let mut transaction = ManifestTransaction::new();
+    transaction.stage(&existing, "first".to_string());
+    transaction.stage(&existing, "second".to_string());
+    transaction.stage(&created, "new".to_string());
+    assert_eq!(transaction.staged(&existing), Some("second"));
+
+    transaction.commit().unwrap();
+    assert_eq!(fs::read_to_string(&existing).unwrap(), "second");
+    assert_eq!(fs::read_to_string(&created).unwrap(), "new");
+}
+
+#[test]
+fn test_transaction_rolls_back_on_write_failure() {
+    let temp_dir = TempDir::new().unwrap();
+    let paths: Vec<PathBuf> = ["pkg-a", "pkg-b", "pkg-c", "pkg-d"]
+        .iter()
+        .map(|name| create_test_manifest(&temp_dir, name, "0.20.0"))
+        .collect();
+    let originals: Vec<String> = paths
+        .iter()
+        .map(|p| fs::read_to_string(p).unwrap())
+        .collect();
+    let created = temp_dir.path().join("pkg-e/Cargo.toml");
+    fs::create_dir_all(created.parent().unwrap()).unwrap();
+
+    let mut transaction = ManifestTransaction::new();
+    transaction.stage(&created, "new".to_string());
+    for path in &paths {
+        let mut manifest = CargoManifest::load(path).unwrap();
+        manifest
+            .set_version(&semver::Version::parse("0.21.0").unwrap())
+            .unwrap();
+        transaction.stage_manifest(&manifest);
+    }
+
+    // The fourth write truncates its file and then fails
+    let mut writes = 0;
+    let err = transaction
+        .commit_with(|path, contents| {
+            writes += 1;
+            if writes == 4 {
+                fs::write(path, "")?;
+                return Err(std::io::Error::other("disk full"));
+            }
+            fs::write(path, contents)
+        })
+        .unwrap_err();
+    assert!(err.to_string().contains("no manifests were changed"));
+
+    for (path, original) in paths.iter().zip(&originals) {
+        assert_eq!(&fs::read_to_string(path).unwrap(), original);
+    }
+    assert!(!created.exists());
+}
+
+#[test]
+fn test_update_dependency_preserves_comments_and_keys() {
+    let temp_dir = TempDir::new().unwrap();
+    let manifest_path = temp_dir.path().join("Cargo.toml");
+    let original = r#"[package]
+name = "test-package"
+version = "0.20.0"
+
+[dependencies]
+# VSA core
+embeddenator-vsa = { version = "0.20.0", features = ["simd"], default-features = false } # pinned for simd
+embeddenator-io = "0.20.0" # io layer
+embeddenator-fs = { version = "0.20.0", optional = true }
+
+[dependencies.embeddenator-obj]
+version = "0.20.0" # table form
+features = ["serde"]
+"#;
+    fs::write(&manifest_path, original).unwrap();
+
+    let mut manifest = CargoManifest::load(&manifest_path).unwrap();
+    let vsa = manifest
+        .dependencies
+        .iter()
+        .find(|d| d.name == "embeddenator-vsa")
+        .unwrap();
+    assert_eq!(vsa.req.as_deref(), Some("0.20.0"));
+
+    let new_version = semver::Version::parse("0.21.0").unwrap();
+    for dep in [
+        "embeddenator-vsa",
+        "embeddenator-io",
+        "embeddenator-fs",
+        "embeddenator-obj",
+    ] {
+        manifest.update_dependency(dep, &new_version).unwrap();
+    }
+    manifest.save().unwrap();
+
+    let updated = fs::read_to_string(&manifest_path).unwrap();
+    let expected = original.replace(
+        "embeddenator-vsa = { version = \"0.20.0\"",
+        "embeddenator-vsa = { version = \"0.21.0\"",
+    );
+    let expected = expected
+        .replace(
+            "embeddenator-io = \"0.20.0\"",
+            "embeddenator-io = \"0.21.0\"",
+        )
+        .replace(
+            "embeddenator-fs = { version = \"0.20.0\"",
+            "embeddenator-fs = { version = \"0.21.0\"",
+        )
+        .replace(
+            "version = \"0.20.0\" # table form",
+            "version = \"0.21.0\" # table form",
+        );
+    assert_eq!(updated, expected);
+
+    let reloaded = CargoManifest::load(&manifest_path).unwrap();
+    assert!(reloaded
+        .embeddenator_dependencies()
+        .iter()
+        .all(|d| d.version.as_ref() == Some(&new_version)));
+}
+
+#[test]
+fn test_renamed_dependency() {
+    let temp_dir = TempDir::new().unwrap();
+    let manifest_path = temp_dir.path().join("Cargo.toml");
+    let original = r#"[package]
+name = "test-package"
+version = "0.1.0"
+
+[dependencies]
+vsa = { package = "embeddenator-vsa", version = "0.20.0" }
+embeddenator-vsa = "0.20.0"
+serde = "1.0"
+"#;
+    fs::write(&manifest_path, original).unwrap();
+
+    let mut manifest = CargoManifest::load(&manifest_path).unwrap();
+    let embeddenator_deps = manifest.embeddenator_dependencies();
+    assert_eq!(embeddenator_deps.len(), 2);
+    let renamed = embeddenator_deps.iter().find(|d| d.key == "vsa").unwrap();
+    assert_eq!(renamed.name, "embeddenator-vsa");
+
+    let new_version = semver::Version::parse("0.21.0").unwrap();
+    manifest
+        .update_dependency("embeddenator-vsa", &new_version)
+        .unwrap();
+    // The alias isn't a crate name of its own
+    manifest.update_dependency("vsa", &new_version).unwrap();
+    manifest.save().unwrap();
+
+    assert_eq!(
+        fs::read_to_string(&manifest_path).unwrap(),
+        original.replace("0.20.0", "0.21.0")
+    );
+    assert!(manifest
+        .embeddenator_dependencies()
+        .iter()
+        .all(|d| d.version.as_ref() == Some(&new_version)));
+}
+
+#[test]
+fn test_add_dependency() {
+    use crate::cargo::DependencyType;
+
+    let temp_dir = TempDir::new().unwrap();
+    let path = create_test_manifest(&temp_dir, "test-package", "0.20.0");
+    let version = semver::Version::parse("0.20.0").unwrap();
+
+    let mut manifest = CargoManifest::load(&path).unwrap();
+    manifest
+        .add_dependency("embeddenator-vsa", &version, DependencyType::Normal)
+        .unwrap();
+    // No [dev-dependencies] table yet
+    manifest
+        .add_dependency("embeddenator-io", &version, DependencyType::Dev)
+        .unwrap();
+    manifest.save().unwrap();
+
+    let content = fs::read_to_string(&path).unwrap();
+    assert!(content.contains("[dependencies]\nembeddenator-vsa = \"0.20.0\"\n"));
+    assert!(content.contains("[dev-dependencies]\nembeddenator-io = \"0.20.0\"\n"));
+
+    let reloaded = CargoManifest::load(&path).unwrap();
+    let dev = reloaded
+        .dependencies
+        .iter()
+        .find(|d| d.name == "embeddenator-io")
+        .unwrap();
+    assert_eq!(dev.dep_type, DependencyType::Dev);
+    assert_eq!(manifest.dependencies.len(), reloaded.dependencies.len());
+}
+
+#[test]
+fn test_add_existing_dependency_overwrites_version() {
+    use crate::cargo::DependencyType;
+
+    let temp_dir = TempDir::new().unwrap();
+    let path = create_test_manifest_with_deps(
+        &temp_dir,
+        "test-package",
+        "0.20.0",
+        &[("embeddenator-vsa", "0.19.0")],
+    );
+    let version = semver::Version::parse("0.20.0").unwrap();
+
+    let mut manifest = CargoManifest::load(&path).unwrap();
+    manifest
+        .add_dependency("embeddenator-vsa", &version, DependencyType::Normal)
+        .unwrap();
+    manifest.save().unwrap();
+
+    let content = fs::read_to_string(&path).unwrap();
+    assert_eq!(content.matches("embeddenator-vsa").count(), 1);
+    assert!(content.contains("embeddenator-vsa = \"0.20.0\""));
+    assert_eq!(manifest.dependencies.len(), 1);
+    assert_eq!(manifest.dependencies[0].version, Some(version));
+}
+
+#[test]
+fn test_remove_dependency() {
+    use crate::cargo::DependencyType;
+
+    let temp_dir = TempDir::new().unwrap();
+    let path = create_test_manifest_with_deps(
+        &temp_dir,
+        "test-package",
+        "0.20.0",
+        &[
+            ("embeddenator-vsa", "0.20.0"),
+            ("embeddenator-io", "0.20.0"),
+        ],
+    );
+
+    let mut manifest = CargoManifest::load(&path).unwrap();
+    assert!(!manifest.remove_dependency("embeddenator-vsa", DependencyType::Dev));
+    assert!(manifest.remove_dependency("embeddenator-vsa", DependencyType::Normal));
+    assert!(!manifest.remove_dependency("embeddenator-vsa", DependencyType::Normal));
+    manifest.save().unwrap();
+
+    let content = fs::read_to_string(&path).unwrap();
+    assert!(!content.contains("embeddenator-vsa"));
+    assert!(content.contains("[dependencies]\nembeddenator-io = \"0.20.0\"\n"));
+    assert_eq!(manifest.dependencies.len(), 1);
+
+    // Removing the last entry drops the section header too
+    assert!(manifest.remove_dependency("embeddenator-io", DependencyType::Normal));
+    manifest.save().unwrap();
+
+    let content = fs::read_to_string(&path).unwrap();
+    assert!(!content.contains("[dependencies]"));
+    assert!(manifest.dependencies.is_empty());
+    assert!(CargoManifest::load(&path).unwrap().dependencies.is_empty());
+}
+
+#[test]
+fn test_dependency_feature_flags() {
+    let temp_dir = TempDir::new().unwrap();
+    let manifest_path = temp_dir.path().join("Cargo.toml");
+    fs::write(
+        &manifest_path,
+        r#"[package]
+name = "test-package"
+version = "0.20.0"
+
+[dependencies]
+embeddenator-vsa = { version = "0.20.0", optional = true, default-features = false }
+embeddenator-io = "0.20.0"
+
+[dependencies.embeddenator-fs]
+version = "0.20.0"
+optional = true
+"#,
+    )
+    .unwrap();
+
+    let manifest = CargoManifest::load(&manifest_path).unwrap();
+    let flags = |name: &str| {
+        let dep = manifest
+            .dependencies
+            .iter()
+            .find(|d| d.name == name)
+            .unwrap();
+        (dep.optional, dep.default_features)
+    };
+
+    assert_eq!(flags("embeddenator-vsa"), (true, false));
+    assert_eq!(flags("embeddenator-io"), (false, true));
+    assert_eq!(flags("embeddenator-fs"), (true, true));
+}
+
+#[test]
+fn test_dependency_source() {
+    use crate::cargo::DependencySource;
+
+    let temp_dir = TempDir::new().unwrap();
+    let manifest_path = temp_dir.path().join("Cargo.toml");
+    fs::write(
+        &manifest_path,
+        r#"[package]
+name = "test-package"
+version = "0.20.0"
+
+[dependencies]
+embeddenator-vsa = "0.20.0"
+embeddenator-io = { git = "https://github.com/tzervas/embeddenator-io", tag = "v0.20.0" }
+embeddenator-obj = { git = "https://github.com/tzervas/embeddenator-obj" }
+embeddenator-fs = { path = "../embeddenator-fs" }
+"#,
+    )
+    .unwrap();
+
+    let manifest = CargoManifest::load(&manifest_path).unwrap();
+    let source = |name: &str| {
+        manifest
+            .dependencies
+            .iter()
+            .find(|d| d.name == name)
+            .unwrap()
+            .source
+            .clone()
+    };
+
+    assert_eq!(source("embeddenator-vsa"), DependencySource::Registry);
+    assert_eq!(
+        source("embeddenator-io"),
+        DependencySource::Git {
+            url: "https://github.com/tzervas/embeddenator-io".to_string(),
+            rev: Some("v0.20.0".to_string()),
+        }
+    );
+    assert_eq!(
+        source("embeddenator-obj"),
+        DependencySource::Git {
+            url: "https://github.com/tzervas/embeddenator-obj".to_string(),
+            rev: None,
+        }
+    );
+    assert_eq!(
+        source("embeddenator-fs"),
+        DependencySource::Path(PathBuf::from("../embeddenator-fs"))
+    );
+}
+
+#[test]
+fn test_save_is_atomic_and_cleans_up() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = create_test_manifest(&temp_dir, "test-package", "0.20.0-alpha.1");
+
+    let mut manifest = CargoManifest::load(&path).unwrap();
+    manifest
+        .set_version(&semver::Version::parse("0.21.0").unwrap())
+        .unwrap();
+    manifest.save().unwrap();
+
+    let entries: Vec<String> = fs::read_dir(path.parent().unwrap())
+        .unwrap()
+        .map(|entry| entry.unwrap().file_name().to_string_lossy().into_owned())
+        .collect();
+    assert_eq!(entries, vec!["Cargo.toml"]);
+    assert!(fs::read_to_string(&path)
+        .unwrap()
+        .contains("version = \"0.21.0\""));
+}
+
+#[cfg(unix)]
+#[test]
+fn test_save_keeps_permissions_and_symlinks() {
+    use std::os::unix::fs::{symlink, PermissionsExt};
+
+    let temp_dir = TempDir::new().unwrap();
+    let real = temp_dir.path().join("real");
+    fs::create_dir_all(&real).unwrap();
+    let target = real.join("Cargo.toml");
+    fs::write(
+        &target,
+        "[package]\nname = \"embeddenator-vsa\"\nversion = \"0.20.0\"\n",
+    )
+    .unwrap();
+    fs::set_permissions(&target, fs::Permissions::from_mode(0o640)).unwrap();
+
+    let link_dir = temp_dir.path().join("linked");
+    fs::create_dir_all(&link_dir).unwrap();
+    let link = link_dir.join("Cargo.toml");
+    symlink(&target, &link).unwrap();
+
+    let mut manifest = CargoManifest::load(&link).unwrap();
+    manifest
+        .set_version(&semver::Version::parse("0.21.0").unwrap())
+        .unwrap();
+    manifest.save().unwrap();
+
+    assert!(fs::symlink_metadata(&link)
+        .unwrap()
+        .file_type()
+        .is_symlink());
+    assert!(fs::read_to_string(&target)
+        .unwrap()
+        .contains("version = \"0.21.0\""));
+    let mode = fs::metadata(&target).unwrap().permissions().mode();
+    assert_eq!(mode & 0o777, 0o640);
+    assert_eq!(fs::read_dir(&link_dir).unwrap().count(), 1);
+}
@@ -0,0 +1,224 @@
+//! Dependency graph construction and export.
+//!
+//! Builds the graph of embeddenator packages and the dependency edges between
+//! them, for consumption by external graph tooling.
+
+use anyhow::{Context, Result};
+use semver::Version;
+use serde::Serialize;
+use serde_json::json;
+use std::collections::{BTreeMap, BTreeSet, VecDeque};
+use std::path::{Path, PathBuf};
+
+use crate::cargo::{CargoManifest, DependencyType};
+use crate::workspace::WorkspaceScanner;
+
+/// A package node in the dependency graph.
+#[derive(Debug, Clone)]
+pub struct GraphNode {
+    pub name: String,
+    pub version: Version,
+    pub path: PathBuf,
+}
+
+/// A dependency edge between two workspace packages.
+#[derive(Debug, Clone)]
+pub struct GraphEdge {
+    pub from: String,
+    pub to: String,
+    pub kind: DependencyType,
+    pub req: Option<String>,
+}
+
+/// Number of crates listed in [`GraphStats`] fan-in/fan-out rankings.
+pub const TOP_FAN_CRATES: usize = 5;
+
+/// Size and shape metrics for a [`DependencyGraph`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct GraphStats {
+    /// Number of workspace crates (nodes).
+    pub crates: usize,
+    /// Number of dependency edges, counting each dependency kind separately.
+    pub edges: usize,
+    /// Longest chain of non-dev dependencies, in edges (0 if no crate
+    /// depends on another).
+    pub max_depth: usize,
+    /// Crates depended on by the most other crates, as `(name, count)`.
+    pub top_fan_in: Vec<(String, usize)>,
+    /// Crates depending on the most other crates, as `(name, count)`.
+    pub top_fan_out: Vec<(String, usize)>,
+}
+
+/// Dependency graph of the embeddenator packages in a workspace.
+#[derive(Debug, Clone, Default)]
+pub struct DependencyGraph {
+    pub nodes: Vec<GraphNode>,
+    pub edges: Vec<GraphEdge>,
+}
+
+impl DependencyGraph {
+    /// Build the graph from all embeddenator packages under the workspace root.
+    pub fn from_workspace(workspace_root: impl AsRef<Path>) -> Result<Self> {
+        Self::from_scanner(&WorkspaceScanner::new(workspace_root))
+    }
+
+    /// Build the graph from the packages `scanner` finds.
+    pub fn from_scanner(scanner: &WorkspaceScanner) -> Result<Self> {
+        let manifests = scanner
+            .find_embeddenator_packages()
+            .context("Failed to find packages")?;
+
+        Ok(Self::from_manifests(&manifests))
+    }
+
+    /// Build the graph from already-loaded manifests.
+    ///
+    /// Only edges whose target is one of the given packages are kept.
+    pub fn from_manifests(manifests: &[CargoManifest]) -> Self {
+        let mut nodes: Vec<GraphNode> = manifests
+            .iter()
+            .map(|m| GraphNode {
+                name: m.package_name.clone(),
+                version: m.version.clone(),
+                path: m.path.clone(),
+            })
+            .collect();
+        nodes.sort_by(|a, b| a.name.cmp(&b.name));
+
+        let mut edges: Vec<GraphEdge> = manifests
+            .iter()
+            .flat_map(|m| {
+                m.dependencies.iter().map(move |d| GraphEdge {
+                    from: m.package_name.clone(),
+                    to: d.name.clone(),
+                    kind: d.dep_type.clone(),
+                    req: d.req.clone(),
+                })
+            })
+            .filter(|e| nodes.iter().any(|n| n.name == e.to))
+            .collect();
+        edges.sort_by(|a, b| {
+            (&a.from, &a.to, a.kind.as_str()).cmp(&(&b.from, &b.to, b.kind.as_str()))
+        });
+
+        Self { nodes, edges }
+    }
+
+    /// Export the graph as JSON.
+    ///
+    /// Schema: `{ "nodes": [{name, version, path}], "edges": [{from, to, kind, req}] }`,
+    /// with nodes sorted by name and edges by `(from, to, kind)`.
+    pub fn to_json(&self) -> serde_json::Value {
+        let nodes: Vec<serde_json::Value> = self
+            .nodes
+            .iter()
+            .map(|n| {
+                json!({
+                    "name": n.name,
+                    "version": n.version.to_string(),
+                    "path": n.path.display().to_string(),
+                })
+            })
+            .collect();
+
+        let edges: Vec<serde_json::Value> = self
+            .edges
+            .iter()
+            .map(|e| {
+                json!({
+                    "from": e.from,
+                    "to": e.to,
+                    "kind": e.kind.as_str(),
+                    "req": e.req,
+                })
+            })
+            .collect();
+
+        json!({ "nodes": nodes, "edges": edges })
+    }
+
+    /// Compute size and complexity metrics for the graph.
+    ///
+    /// Fan-in and fan-out count distinct crates across all dependency kinds.
+    /// Depth ignores dev-dependencies, which may legitimately form cycles;
+    /// crates caught in any remaining cycle do not contribute to it.
+    pub fn stats(&self) -> GraphStats {
+        let links: BTreeSet<(&str, &str)> = self
+            .edges
+            .iter()
+            .map(|e| (e.from.as_str(), e.to.as_str()))
+            .collect();
+
+        let mut fan_in: BTreeMap<&str, usize> = BTreeMap::new();
+        let mut fan_out: BTreeMap<&str, usize> = BTreeMap::new();
+        for (from, to) in &links {
+            *fan_out.entry(from).or_default() += 1;
+            *fan_in.entry(to).or_default() += 1;
+        }
+
+        GraphStats {
+            crates: self.nodes.len(),
+            edges: self.edges.len(),
+            max_depth: self.max_depth(),
+            top_fan_in: Self::top_counts(fan_in),
+            top_fan_out: Self::top_counts(fan_out),
+        }
+    }
+
+    /// Longest non-dev dependency chain, via a topological traversal that
+    /// starts from crates without dependencies.
+    fn max_depth(&self) -> usize {
+        let build_links: BTreeSet<(&str, &str)> = self
+            .edges
+            .iter()
+            .filter(|e| e.kind != DependencyType::Dev)
+            .map(|e| (e.from.as_str(), e.to.as_str()))
+            .collect();
+
+        let mut pending: BTreeMap<&str, usize> =
+            self.nodes.iter().map(|n| (n.name.as_str(), 0)).collect();
+        let mut dependents: BTreeMap<&str, Vec<&str>> = BTreeMap::new();
+        for (from, to) in &build_links {
+            *pending.entry(from).or_default() += 1;
+            dependents.entry(to).or_default().push(from);
+        }
+
+        let mut depth: BTreeMap<&str, usize> = BTreeMap::new();
+        let mut queue: VecDeque<&str> = pending
+            .iter()
+            .filter(|(_, count)| **count == 0)
+            .map(|(name, _)| *name)
+            .collect();
+
+        while let Some(name) = queue.pop_front() {
+            let current = depth.get(name).copied().unwrap_or(0);
+            for dependent in dependents.get(name).into_iter().flatten() {
+                let entry = depth.entry(dependent).or_default();
+                *entry = (*entry).max(current + 1);
+
+                let count = pending.get_mut(dependent).expect("dependent is a node");
+                *count -= 1;
+                if *count == 0 {
+                    queue.push_back(dependent);
+                }
+            }
+        }
+
+        depth.values().copied().max().unwrap_or(0)
+    }
+
+    /// The [`TOP_FAN_CRATES`] highest counts, ties broken by name.
+    fn top_counts(counts: BTreeMap<&str, usize>) -> Vec<(String, usize)> {
+        let mut counts: Vec<(String, usize)> = counts
+            .into_iter()
+            .map(|(name, count)| (name.to_string(), count))
+            .collect();
+        counts.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        counts.truncate(TOP_FAN_CRATES);
+        counts
+    }
+}
+
+#[cfg(test)]
+#[path = "graph_tests.rs"]
+mod tests;
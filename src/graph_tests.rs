@@ -0,0 +1,119 @@
+use crate::graph::DependencyGraph;
+use std::fs;
+use tempfile::TempDir;
+
+fn create_test_workspace() -> TempDir {
+    let temp_dir = TempDir::new().unwrap();
+
+    let packages = [
+        (
+            "embeddenator-vsa",
+            "[dependencies]\nserde = \"1.0\"\n".to_string(),
+        ),
+        (
+            "embeddenator-io",
+            "[dependencies]\nembeddenator-vsa = \"0.20.0\"\n".to_string(),
+        ),
+        (
+            "embeddenator-fs",
+            "[dependencies]\nembeddenator-io = \"0.20.0\"\n\n[dev-dependencies]\nembeddenator-vsa = \"0.20.0\"\n"
+                .to_string(),
+        ),
+    ];
+
+    for (name, deps) in packages {
+        let pkg = temp_dir.path().join(name);
+        fs::create_dir_all(&pkg).unwrap();
+        fs::write(
+            pkg.join("Cargo.toml"),
+            format!(
+                "[package]\nname = \"{}\"\nversion = \"0.20.0\"\nedition = \"2021\"\n\n{}",
+                name, deps
+            ),
+        )
+        .unwrap();
+    }
+
+    temp_dir
+}
+
+#[test]
+fn test_graph_from_workspace() {
+    let temp_dir = create_test_workspace();
+    let graph = DependencyGraph::from_workspace(temp_dir.path()).unwrap();
+
+    assert_eq!(graph.nodes.len(), 3);
+    // External dependencies like serde are not part of the graph
+    assert_eq!(graph.edges.len(), 3);
+    assert!(graph.edges.iter().all(|e| e.to.starts_with("embeddenator")));
+}
+
+#[test]
+fn test_graph_to_json() {
+    let temp_dir = create_test_workspace();
+    let graph = DependencyGraph::from_workspace(temp_dir.path()).unwrap();
+
+    let json = graph.to_json();
+
+    let nodes = json["nodes"].as_array().unwrap();
+    let names: Vec<&str> = nodes.iter().map(|n| n["name"].as_str().unwrap()).collect();
+    assert_eq!(
+        names,
+        vec!["embeddenator-fs", "embeddenator-io", "embeddenator-vsa"]
+    );
+    assert_eq!(nodes[0]["version"], "0.20.0");
+
+    let edges = json["edges"].as_array().unwrap();
+    assert_eq!(edges[0]["from"], "embeddenator-fs");
+    assert_eq!(edges[0]["to"], "embeddenator-io");
+    assert_eq!(edges[0]["kind"], "normal");
+    assert_eq!(edges[0]["req"], "0.20.0");
+    assert_eq!(edges[1]["kind"], "dev");
+}
+
+#[test]
+fn test_graph_stats() {
+    let temp_dir = create_test_workspace();
+    let graph = DependencyGraph::from_workspace(temp_dir.path()).unwrap();
+
+    let stats = graph.stats();
+    assert_eq!(stats.crates, 3);
+    assert_eq!(stats.edges, 3);
+    // embeddenator-fs -> embeddenator-io -> embeddenator-vsa
+    assert_eq!(stats.max_depth, 2);
+    assert_eq!(
+        stats.top_fan_in,
+        vec![
+            ("embeddenator-vsa".to_string(), 2),
+            ("embeddenator-io".to_string(), 1),
+        ]
+    );
+    assert_eq!(
+        stats.top_fan_out,
+        vec![
+            ("embeddenator-fs".to_string(), 2),
+            ("embeddenator-io".to_string(), 1),
+        ]
+    );
+}
+
+#[test]
+fn test_graph_stats_ignores_dev_cycles() {
+    let temp_dir = create_test_workspace();
+    // A dev-dependency back onto a dependent is a legal cycle
+    let vsa = temp_dir.path().join("embeddenator-vsa/Cargo.toml");
+    let content = fs::read_to_string(&vsa).unwrap();
+    fs::write(
+        &vsa,
+        format!(
+            "{}\n[dev-dependencies]\nembeddenator-fs = \"0.20.0\"\n",
+            content
+        ),
+    )
+    .unwrap();
+
+    let graph = DependencyGraph::from_workspace(temp_dir.path()).unwrap();
+    let stats = graph.stats();
+    assert_eq!(stats.edges, 4);
+    assert_eq!(stats.max_depth, 2);
+}
@@ -1,27 +1,45 @@
 //! Workspace health checking utilities.
 //!
 //! Provides comprehensive health checks for the embeddenator multi-repo workspace,
-//! including git status, version alignment, test coverage, doc coverage, and spec coverage.
+//! including git status, version alignment, test coverage, doc coverage, spec coverage,
+//! yanked dependency versions, and workspace membership.
 
 use anyhow::{Context, Result};
 use colored::Colorize;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::io::{self, Write};
 use std::path::{Path, PathBuf};
 use std::process::{Command, Stdio};
 use std::str::FromStr;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::runtime::Handle;
+use tokio::sync::Semaphore;
 use tokio::task::JoinHandle;
 
+use crate::cargo::CargoManifest;
+use crate::owners::{self, CodeOwners};
 use crate::version::VersionManager;
+use crate::workspace::{normalize_path, WorkspaceScanner, DEFAULT_PACKAGE_PREFIX};
 
 /// Types of health checks that can be performed.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
 pub enum HealthCheckType {
     Git,
     Version,
+    Msrv,
+    Build,
     Tests,
     Docs,
     Specs,
+    Yanked,
+    Members,
+    Cycles,
+    Clippy,
+    Fmt,
+    Audit,
 }
 
 impl FromStr for HealthCheckType {
@@ -31,9 +49,17 @@ impl FromStr for HealthCheckType {
         match s.to_lowercase().as_str() {
             "git" => Ok(Self::Git),
             "version" => Ok(Self::Version),
+            "msrv" => Ok(Self::Msrv),
+            "build" => Ok(Self::Build),
             "tests" => Ok(Self::Tests),
             "docs" => Ok(Self::Docs),
             "specs" => Ok(Self::Specs),
+            "yanked" => Ok(Self::Yanked),
+            "members" => Ok(Self::Members),
+            "cycles" => Ok(Self::Cycles),
+            "clippy" => Ok(Self::Clippy),
+            "fmt" => Ok(Self::Fmt),
+            "audit" => Ok(Self::Audit),
             _ => Err(format!("Unknown health check type: {}", s)),
         }
     }
@@ -44,15 +70,37 @@ impl HealthCheckType {
         match self {
             Self::Git => "git",
             Self::Version => "version",
+            Self::Msrv => "msrv",
+            Self::Build => "build",
             Self::Tests => "tests",
             Self::Docs => "docs",
             Self::Specs => "specs",
+            Self::Yanked => "yanked",
+            Self::Members => "members",
+            Self::Cycles => "cycles",
+            Self::Clippy => "clippy",
+            Self::Fmt => "fmt",
+            Self::Audit => "audit",
+        }
+    }
+
+    /// Weight of this check in [`HealthReport::score`] unless overridden with
+    /// [`HealthChecker::with_weights`].
+    ///
+    /// Checks that gate a release (builds, tests, versions, cycles, security)
+    /// weigh 3, hygiene that can break users later (git, yanked deps,
+    /// members, clippy) weighs 2, and the rest weighs 1.
+    pub fn default_weight(&self) -> u32 {
+        match self {
+            Self::Version | Self::Build | Self::Tests | Self::Cycles | Self::Audit => 3,
+            Self::Git | Self::Yanked | Self::Members | Self::Clippy => 2,
+            Self::Msrv | Self::Docs | Self::Specs | Self::Fmt => 1,
         }
     }
 }
 
-/// Status of a health check.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+/// Status of a health check, ordered from best (`Pass`) to worst (`Fail`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
 pub enum HealthStatus {
     Pass,
@@ -60,6 +108,19 @@ pub enum HealthStatus {
     Fail,
 }
 
+impl FromStr for HealthStatus {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "pass" => Ok(Self::Pass),
+            "warn" => Ok(Self::Warn),
+            "fail" => Ok(Self::Fail),
+            _ => Err(format!("Unknown health status: {}", s)),
+        }
+    }
+}
+
 impl HealthStatus {
     pub fn is_critical(&self) -> bool {
         matches!(self, Self::Fail)
@@ -73,6 +134,118 @@ pub struct HealthCheckResult {
     pub status: HealthStatus,
     pub message: String,
     pub details: Vec<String>,
+    /// Per-package spec coverage, set by the specs check.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub spec_coverage: Option<BTreeMap<String, SpecCoverage>>,
+    /// Documented items across all packages, set by the docs check when
+    /// a nightly toolchain can measure it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub doc_coverage: Option<DocCoverage>,
+    /// Test totals across all packages, set by the tests check.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub test_counts: Option<TestCounts>,
+    /// Wall-clock time the check took, in milliseconds.
+    #[serde(default)]
+    pub duration_ms: u64,
+    /// Package paths, relative to the workspace root, with problems found by this check.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub packages: Vec<String>,
+}
+
+impl HealthCheckResult {
+    /// A suggested next step for fixing a failed or warning check, or `None`
+    /// if it passed.
+    pub fn remediation(&self) -> Option<&'static str> {
+        use HealthCheckType::*;
+        use HealthStatus::*;
+
+        let suggestion = match (self.check_type, self.status) {
+            (_, Pass) => return None,
+            (Git, Fail) => "Commit or stash the uncommitted changes (`git stash push`)",
+            (Git, Warn) => {
+                "Sync with upstream (`git pull --rebase`, then `git push`), or set one with \
+                 `git branch --set-upstream-to`"
+            }
+            (Version, Fail) => "Run `embeddenator-workspace bump-version --align`",
+            (Version, Warn) => {
+                "Use an allowed prerelease label (alpha, beta, rc), or allow it with \
+                 `check-versions --allowed-prerelease`"
+            }
+            (Msrv, _) => {
+                "Set `rust-version` in the root `[workspace.package]` and \
+                 `rust-version.workspace = true` in each package"
+            }
+            (Build, _) => {
+                "Run `cargo check --all-targets` in the listed packages and fix the errors"
+            }
+            (Tests, _) => "Run `cargo test` in the listed packages and fix the failing tests",
+            (Docs, _) => "Run `cargo doc --no-deps` and fix the warnings; document public items",
+            (Specs, _) => "Add spec files under `specs/` in the listed packages",
+            (Yanked, _) => "Run `cargo update -p <crate>` for each yanked dependency",
+            (Members, Fail) => {
+                "Remove missing entries from `[workspace] members` or restore the directories"
+            }
+            (Members, Warn) => "Add the listed packages to `[workspace] members` (or `exclude`)",
+            (Cycles, _) => "Break the cycle by moving the shared code into a lower-level crate",
+            (Clippy, _) => "Run `cargo clippy --fix --all-targets` and fix the remaining lints",
+            (Fmt, _) => "Run `cargo fmt --all`",
+            (Audit, _) => "Upgrade or replace the affected crates; see `cargo audit` for details",
+        };
+        Some(suggestion)
+    }
+}
+
+/// Spec coverage for a single package.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SpecCoverage {
+    /// Whether the package has a `specs/` directory.
+    pub has_dir: bool,
+    /// Number of files under `specs/` with an accepted spec extension.
+    pub spec_files: usize,
+    /// Number of spec files with non-empty content.
+    pub valid_files: usize,
+}
+
+/// Documentation coverage as measured by `rustdoc --show-coverage`.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct DocCoverage {
+    /// Items with a doc comment.
+    pub documented: usize,
+    /// Items rustdoc counts as documentable.
+    pub total: usize,
+    /// `documented / total` as a percentage (100 when there are no items).
+    pub percent: f64,
+}
+
+impl DocCoverage {
+    pub fn new(documented: usize, total: usize) -> Self {
+        let percent = if total > 0 {
+            documented as f64 / total as f64 * 100.0
+        } else {
+            100.0
+        };
+        Self {
+            documented,
+            total,
+            percent,
+        }
+    }
+}
+
+/// Totals from the `test result:` summaries of `cargo test`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TestCounts {
+    pub passed: usize,
+    pub failed: usize,
+    pub ignored: usize,
+}
+
+impl std::ops::AddAssign for TestCounts {
+    fn add_assign(&mut self, other: Self) {
+        self.passed += other.passed;
+        self.failed += other.failed;
+        self.ignored += other.ignored;
+    }
 }
 
 /// Git repository status.
@@ -85,23 +258,326 @@ pub struct GitStatus {
     pub behind: usize,
     pub has_upstream: bool,
     pub dirty_files: Vec<String>,
+    /// Files with changes staged in the index.
+    #[serde(default)]
+    pub staged: Vec<String>,
+    /// Tracked files with unstaged changes in the working tree.
+    #[serde(default)]
+    pub modified: Vec<String>,
+    /// Files not tracked by git.
+    #[serde(default)]
+    pub untracked: Vec<String>,
+    /// Number of stash entries.
+    #[serde(default)]
+    pub stash_count: usize,
+}
+
+impl GitStatus {
+    /// Describe the dirty files by category, e.g. `3 modified, 1 untracked`.
+    ///
+    /// A file with both staged and unstaged changes counts in both.
+    pub fn change_summary(&self) -> String {
+        describe_changes(self.staged.len(), self.modified.len(), self.untracked.len())
+    }
+}
+
+/// `N staged, N modified, N untracked`, leaving out empty categories.
+fn describe_changes(staged: usize, modified: usize, untracked: usize) -> String {
+    [
+        (staged, "staged"),
+        (modified, "modified"),
+        (untracked, "untracked"),
+    ]
+    .iter()
+    .filter(|(count, _)| *count > 0)
+    .map(|(count, label)| format!("{} {}", count, label))
+    .collect::<Vec<_>>()
+    .join(", ")
 }
 
 /// Overall health report for the workspace.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct HealthReport {
+    /// When the report was generated, as an RFC 3339 local time
+    /// (e.g. `2024-06-01T12:00:00-07:00`).
     pub timestamp: String,
     pub workspace_root: PathBuf,
     pub checks: Vec<HealthCheckResult>,
     pub overall_status: HealthStatus,
+    /// Packages left out because they had no changes (see
+    /// [`HealthChecker::changed_since`]).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub skipped: Vec<String>,
+    /// Check weights that differ from [`HealthCheckType::default_weight`].
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub weights: BTreeMap<HealthCheckType, u32>,
+    /// [`HealthReport::score`] when the report was generated.
+    #[serde(default)]
+    pub score: u8,
+}
+
+/// Problems found by one check in packages that share an owner.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct OwnerIssue {
+    pub check_type: HealthCheckType,
+    pub status: HealthStatus,
+    pub packages: Vec<String>,
+}
+
+/// How a check's status moved since a previous report.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum StatusChange {
+    Improved,
+    Regressed,
+    Unchanged,
+}
+
+/// Changes in one check between two reports.
+#[derive(Debug, Clone, Serialize)]
+pub struct CheckDiff {
+    pub check_type: HealthCheckType,
+    /// Status in the previous report, or `None` if the check wasn't run then.
+    pub previous: Option<HealthStatus>,
+    pub current: HealthStatus,
+    pub change: StatusChange,
+    /// Detail lines not in the previous report.
+    pub added_details: Vec<String>,
+    /// Detail lines from the previous report that are gone.
+    pub removed_details: Vec<String>,
+}
+
+/// Differences between a health report and an earlier one.
+#[derive(Debug, Clone, Serialize)]
+pub struct HealthDiff {
+    pub previous_timestamp: String,
+    pub current_timestamp: String,
+    pub checks: Vec<CheckDiff>,
+}
+
+impl HealthDiff {
+    /// Whether any check got worse.
+    pub fn has_regressions(&self) -> bool {
+        self.checks
+            .iter()
+            .any(|c| c.change == StatusChange::Regressed)
+    }
+
+    /// Print the changes to stdout.
+    pub fn print_terminal(&self) {
+        let _ = self.print_terminal_to(&mut io::stdout().lock());
+    }
+
+    /// Write the changes to `w`, skipping checks that didn't change at all.
+    pub fn print_terminal_to<W: Write>(&self, w: &mut W) -> io::Result<()> {
+        writeln!(
+            w,
+            "\n{} {} → {}",
+            "Changes since:".cyan().bold(),
+            self.previous_timestamp,
+            self.current_timestamp
+        )?;
+
+        let changed: Vec<&CheckDiff> = self
+            .checks
+            .iter()
+            .filter(|c| {
+                c.change != StatusChange::Unchanged
+                    || !c.added_details.is_empty()
+                    || !c.removed_details.is_empty()
+            })
+            .collect();
+        if changed.is_empty() {
+            writeln!(w, "  {}", "No changes".dimmed())?;
+            return Ok(());
+        }
+
+        for check in changed {
+            let (icon, label) = match check.change {
+                StatusChange::Improved => ("↑".green(), "improved".green()),
+                StatusChange::Regressed => ("↓".red(), "regressed".red()),
+                StatusChange::Unchanged => ("=".normal(), "unchanged".normal()),
+            };
+            let previous = check
+                .previous
+                .map(|s| format!("{:?}", s))
+                .unwrap_or_else(|| "not run".to_string());
+            writeln!(
+                w,
+                "{} {} {} ({} → {:?})",
+                icon,
+                check.check_type.as_str().bright_white().bold(),
+                label,
+                previous,
+                check.current
+            )?;
+            for detail in &check.added_details {
+                writeln!(w, "    {} {}", "+".green(), detail)?;
+            }
+            for detail in &check.removed_details {
+                writeln!(w, "    {} {}", "-".red(), detail.dimmed())?;
+            }
+        }
+        Ok(())
+    }
 }
 
 impl HealthReport {
+    /// Group non-passing checks by the CODEOWNERS owners of the affected packages.
+    ///
+    /// A package's owners are those of its `Cargo.toml`. Packages without an
+    /// owner are grouped under [`owners::UNOWNED`].
+    pub fn by_owner(&self, codeowners: &CodeOwners) -> BTreeMap<String, Vec<OwnerIssue>> {
+        let mut grouped: BTreeMap<String, Vec<OwnerIssue>> = BTreeMap::new();
+
+        for check in self
+            .checks
+            .iter()
+            .filter(|c| c.status != HealthStatus::Pass)
+        {
+            let mut by_owner: BTreeMap<String, Vec<String>> = BTreeMap::new();
+
+            for package in &check.packages {
+                let mut owners = codeowners.owners_of(&Path::new(package).join("Cargo.toml"));
+                if owners.is_empty() {
+                    owners.push(owners::UNOWNED.to_string());
+                }
+                for owner in owners {
+                    by_owner.entry(owner).or_default().push(package.clone());
+                }
+            }
+
+            for (owner, packages) in by_owner {
+                grouped.entry(owner).or_default().push(OwnerIssue {
+                    check_type: check.check_type,
+                    status: check.status,
+                    packages,
+                });
+            }
+        }
+
+        grouped
+    }
+
+    /// Print the owner-grouped view from [`HealthReport::by_owner`].
+    pub fn print_by_owner(&self, codeowners: &CodeOwners) {
+        let grouped = self.by_owner(codeowners);
+
+        println!("\n{}", "Issues by Owner".bright_white().bold());
+        if grouped.is_empty() {
+            println!("  {} No package-level issues", "✓".green());
+            return;
+        }
+
+        for (owner, issues) in &grouped {
+            println!("\n{}", owner.cyan().bold());
+            for issue in issues {
+                let icon = match issue.status {
+                    HealthStatus::Pass => "✓".green(),
+                    HealthStatus::Warn => "⚠".yellow(),
+                    HealthStatus::Fail => "✗".red(),
+                };
+                println!(
+                    "  {} {}: {}",
+                    icon,
+                    issue.check_type.as_str().bright_white(),
+                    issue.packages.join(", ")
+                );
+            }
+        }
+    }
+
     /// Check if the report contains any critical failures.
     pub fn has_failures(&self) -> bool {
         self.checks.iter().any(|c| c.status.is_critical())
     }
 
+    /// A 0–100 health score: the weighted share of credit across checks.
+    ///
+    /// A passing check earns its full weight, a warning half and a failure
+    /// nothing. With no checks (or all weights zero) the score is 100.
+    pub fn score(&self) -> u8 {
+        let weight = |check: &HealthCheckResult| {
+            self.weights
+                .get(&check.check_type)
+                .copied()
+                .unwrap_or_else(|| check.check_type.default_weight())
+        };
+        let total: u32 = self.checks.iter().map(weight).sum();
+        if total == 0 {
+            return 100;
+        }
+
+        // Counted in half-weights to stay in integers
+        let earned: u32 = self
+            .checks
+            .iter()
+            .map(|check| match check.status {
+                HealthStatus::Pass => 2 * weight(check),
+                HealthStatus::Warn => weight(check),
+                HealthStatus::Fail => 0,
+            })
+            .sum();
+        ((earned * 100 + total) / (2 * total)) as u8
+    }
+
+    /// The worst status among the checks (`Pass` if there are none).
+    pub fn worst_status(&self) -> HealthStatus {
+        self.checks
+            .iter()
+            .map(|c| c.status)
+            .max()
+            .unwrap_or(HealthStatus::Pass)
+    }
+
+    /// Compare this report against an earlier one, check by check.
+    ///
+    /// Only checks in this report are listed. A check the previous report
+    /// didn't run counts as regressed unless it passes now.
+    pub fn diff(&self, previous: &HealthReport) -> HealthDiff {
+        let checks = self
+            .checks
+            .iter()
+            .map(|check| {
+                let before = previous
+                    .checks
+                    .iter()
+                    .find(|c| c.check_type == check.check_type);
+                let previous_status = before.map(|c| c.status);
+                let change = match previous_status.unwrap_or(HealthStatus::Pass) {
+                    s if s < check.status => StatusChange::Regressed,
+                    s if s > check.status => StatusChange::Improved,
+                    _ => StatusChange::Unchanged,
+                };
+                let old_details: &[String] = before.map(|c| c.details.as_slice()).unwrap_or(&[]);
+
+                CheckDiff {
+                    check_type: check.check_type,
+                    previous: previous_status,
+                    current: check.status,
+                    change,
+                    added_details: check
+                        .details
+                        .iter()
+                        .filter(|d| !old_details.contains(d))
+                        .cloned()
+                        .collect(),
+                    removed_details: old_details
+                        .iter()
+                        .filter(|d| !check.details.contains(d))
+                        .cloned()
+                        .collect(),
+                }
+            })
+            .collect();
+
+        HealthDiff {
+            previous_timestamp: previous.timestamp.clone(),
+            current_timestamp: self.timestamp.clone(),
+            checks,
+        }
+    }
+
     /// Generate a Markdown report.
     pub fn to_markdown(&self) -> String {
         let mut output = String::new();
@@ -119,9 +595,16 @@ impl HealthReport {
             HealthStatus::Fail => "❌",
         };
         output.push_str(&format!(
-            "**Overall Status:** {} {:?}\n\n",
+            "**Overall Status:** {} {:?}\n",
             status_emoji, self.overall_status
         ));
+        output.push_str(&format!("**Score:** {}/100\n\n", self.score));
+        if !self.skipped.is_empty() {
+            output.push_str(&format!(
+                "**Skipped (unchanged):** {}\n\n",
+                self.skipped.join(", ")
+            ));
+        }
 
         output.push_str("## Check Results\n\n");
 
@@ -152,21 +635,209 @@ impl HealthReport {
         output
     }
 
-    /// Print a colorized terminal report.
+    /// Generate a self-contained HTML page (inline CSS, no external assets).
+    ///
+    /// Each check gets a colored status badge; details are collapsible.
+    pub fn to_html(&self) -> String {
+        let mut output = String::new();
+
+        output.push_str("<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n");
+        output.push_str("<meta charset=\"utf-8\">\n");
+        output.push_str("<title>Workspace Health Report</title>\n");
+        output.push_str(HTML_STYLE);
+        output.push_str("</head>\n<body>\n");
+
+        output.push_str("<h1>Workspace Health Report</h1>\n");
+        output.push_str(&format!(
+            "<p><strong>Generated:</strong> {}<br>\n<strong>Workspace:</strong> <code>{}</code></p>\n",
+            escape_markup(&self.timestamp),
+            escape_markup(&self.workspace_root.display().to_string())
+        ));
+        output.push_str(&format!(
+            "<p><strong>Overall Status:</strong> {}<br>\n<strong>Score:</strong> {}/100</p>\n",
+            html_badge(self.overall_status),
+            self.score
+        ));
+
+        output.push_str("<h2>Check Results</h2>\n");
+        for check in &self.checks {
+            output.push_str("<section class=\"check\">\n");
+            output.push_str(&format!(
+                "<h3>{} {} check</h3>\n<p>{}</p>\n",
+                html_badge(check.status),
+                check.check_type.as_str(),
+                escape_markup(&check.message)
+            ));
+
+            if !check.details.is_empty() {
+                // Expand details for checks that need attention
+                let open = if check.status == HealthStatus::Pass {
+                    ""
+                } else {
+                    " open"
+                };
+                output.push_str(&format!(
+                    "<details{}>\n<summary>Details ({})</summary>\n<ul>\n",
+                    open,
+                    check.details.len()
+                ));
+                for detail in &check.details {
+                    output.push_str(&format!("<li>{}</li>\n", escape_markup(detail)));
+                }
+                output.push_str("</ul>\n</details>\n");
+            }
+            output.push_str("</section>\n");
+        }
+
+        output.push_str("</body>\n</html>\n");
+        output
+    }
+
+    /// Generate a JUnit XML report with one `<testcase>` per check.
+    ///
+    /// Failed checks carry a `<failure>`, warnings are reported as
+    /// `<skipped>`; details go in the failure body or `<system-out>`.
+    pub fn to_junit_xml(&self) -> String {
+        let count = |status| self.checks.iter().filter(|c| c.status == status).count();
+        let (tests, failures, skipped) = (
+            self.checks.len(),
+            count(HealthStatus::Fail),
+            count(HealthStatus::Warn),
+        );
+
+        let mut output = String::new();
+        output.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        output.push_str(&format!(
+            "<testsuites name=\"workspace-health\" tests=\"{}\" failures=\"{}\" errors=\"0\" skipped=\"{}\">\n",
+            tests, failures, skipped
+        ));
+        output.push_str(&format!(
+            "  <testsuite name=\"workspace-health\" tests=\"{}\" failures=\"{}\" errors=\"0\" skipped=\"{}\" timestamp=\"{}\">\n",
+            tests,
+            failures,
+            skipped,
+            escape_markup(&self.timestamp)
+        ));
+        output.push_str(&format!(
+            "    <properties>\n      <property name=\"workspace\" value=\"{}\"/>\n    </properties>\n",
+            escape_markup(&self.workspace_root.display().to_string())
+        ));
+
+        for check in &self.checks {
+            let name = check.check_type.as_str();
+            let message = escape_markup(&check.message);
+            let details = escape_markup(&check.details.join("\n"));
+            output.push_str(&format!(
+                "    <testcase name=\"{}\" classname=\"health.{}\">\n",
+                name, name
+            ));
+            match check.status {
+                HealthStatus::Fail => output.push_str(&format!(
+                    "      <failure message=\"{}\" type=\"fail\">{}</failure>\n",
+                    message, details
+                )),
+                HealthStatus::Warn => {
+                    output.push_str(&format!("      <skipped message=\"{}\"/>\n", message));
+                    if !check.details.is_empty() {
+                        output.push_str(&format!("      <system-out>{}</system-out>\n", details));
+                    }
+                }
+                HealthStatus::Pass => {
+                    output.push_str(&format!("      <system-out>{}</system-out>\n", message))
+                }
+            }
+            output.push_str("    </testcase>\n");
+        }
+
+        output.push_str("  </testsuite>\n</testsuites>\n");
+        output
+    }
+
+    /// Print the failed and warning checks with a suggested fix for each to
+    /// stdout.
+    pub fn print_checklist(&self) {
+        let _ = self.print_checklist_to(&mut io::stdout().lock());
+    }
+
+    /// Write the failed and warning checks, worst first, each with a
+    /// suggested fix, to `w`.
+    pub fn print_checklist_to<W: Write>(&self, w: &mut W) -> io::Result<()> {
+        let mut problems: Vec<&HealthCheckResult> = self
+            .checks
+            .iter()
+            .filter(|c| c.status != HealthStatus::Pass)
+            .collect();
+        if problems.is_empty() {
+            writeln!(w, "{} No problems found", "✓".green().bold())?;
+            return Ok(());
+        }
+        problems.sort_by_key(|c| std::cmp::Reverse(c.status));
+
+        writeln!(
+            w,
+            "{} {} problem(s) to fix:\n",
+            "Doctor:".cyan().bold(),
+            problems.len()
+        )?;
+        for check in problems {
+            let icon = match check.status {
+                HealthStatus::Fail => "✗".red(),
+                _ => "⚠".yellow(),
+            };
+            writeln!(
+                w,
+                "{} {}: {}",
+                icon,
+                check.check_type.as_str().bright_white().bold(),
+                check.message
+            )?;
+            if !check.packages.is_empty() {
+                writeln!(w, "    {} {}", "in:".dimmed(), check.packages.join(", "))?;
+            }
+            if let Some(fix) = check.remediation() {
+                writeln!(w, "    {} {}", "→".cyan(), fix)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Print a colorized terminal report to stdout.
     pub fn print_terminal(&self, verbose: bool) {
-        println!("\n{}", "═".repeat(80).bright_black());
-        println!("{}", "Workspace Health Report".bright_white().bold());
-        println!("{}", "═".repeat(80).bright_black());
+        // Nothing useful to do if stdout is closed
+        let _ = self.print_terminal_to(&mut io::stdout().lock(), verbose);
+    }
 
-        println!("{} {}", "Generated:".cyan(), self.timestamp);
-        println!("{} {}", "Workspace:".cyan(), self.workspace_root.display());
+    /// Write a colorized terminal report to `w`.
+    pub fn print_terminal_to<W: Write>(&self, w: &mut W, verbose: bool) -> io::Result<()> {
+        writeln!(w, "\n{}", "═".repeat(80).bright_black())?;
+        writeln!(w, "{}", "Workspace Health Report".bright_white().bold())?;
+        writeln!(w, "{}", "═".repeat(80).bright_black())?;
+
+        writeln!(w, "{} {}", "Generated:".cyan(), self.timestamp)?;
+        writeln!(
+            w,
+            "{} {}",
+            "Workspace:".cyan(),
+            self.workspace_root.display()
+        )?;
 
         let status_text = match self.overall_status {
             HealthStatus::Pass => "PASS".green().bold(),
             HealthStatus::Warn => "WARN".yellow().bold(),
             HealthStatus::Fail => "FAIL".red().bold(),
         };
-        println!("{} {}\n", "Overall Status:".cyan(), status_text);
+        writeln!(w, "{} {}", "Overall Status:".cyan(), status_text)?;
+        writeln!(w, "{} {}/100\n", "Score:".cyan(), self.score)?;
+
+        if !self.skipped.is_empty() {
+            writeln!(
+                w,
+                "{} {} unchanged package(s): {}\n",
+                "Skipped:".cyan(),
+                self.skipped.len(),
+                self.skipped.join(", ").dimmed()
+            )?;
+        }
 
         for check in &self.checks {
             let icon = match check.status {
@@ -175,112 +846,528 @@ impl HealthReport {
                 HealthStatus::Fail => "✗".red(),
             };
 
-            println!(
-                "{} {} {}",
+            writeln!(
+                w,
+                "{} {} {} {}",
                 icon,
                 check.check_type.as_str().bright_white().bold(),
-                format!("[{:?}]", check.status).dimmed()
-            );
-            println!("  {}", check.message);
+                format!("[{:?}]", check.status).dimmed(),
+                format!("({:.1}s)", check.duration_ms as f64 / 1000.0).dimmed()
+            )?;
+            writeln!(w, "  {}", check.message)?;
 
             if verbose && !check.details.is_empty() {
                 for detail in &check.details {
-                    println!("    • {}", detail.dimmed());
+                    writeln!(w, "    • {}", detail.dimmed())?;
                 }
             } else if !verbose && check.details.len() > 3 {
                 for detail in check.details.iter().take(3) {
-                    println!("    • {}", detail.dimmed());
+                    writeln!(w, "    • {}", detail.dimmed())?;
                 }
-                println!(
+                writeln!(
+                    w,
                     "    {} {} more details (use --verbose)",
                     "...".dimmed(),
                     (check.details.len() - 3).to_string().dimmed()
-                );
+                )?;
             } else if !check.details.is_empty() {
                 for detail in &check.details {
-                    println!("    • {}", detail.dimmed());
+                    writeln!(w, "    • {}", detail.dimmed())?;
                 }
             }
-            println!();
+            writeln!(w)?;
         }
 
-        println!("{}", "═".repeat(80).bright_black());
+        writeln!(w, "{}", "═".repeat(80).bright_black())?;
+        Ok(())
+    }
+}
+
+const HTML_STYLE: &str = "<style>
+body { font-family: -apple-system, 'Segoe UI', Helvetica, Arial, sans-serif; margin: 2em auto; max-width: 960px; color: #24292f; }
+code { background: #f6f8fa; padding: 0.1em 0.3em; border-radius: 4px; }
+.check { border: 1px solid #d0d7de; border-radius: 6px; padding: 0 1em; margin-bottom: 1em; }
+.badge { display: inline-block; padding: 0.1em 0.6em; border-radius: 1em; color: #fff; font-size: 0.8em; font-weight: 600; text-transform: uppercase; vertical-align: middle; }
+.badge.pass { background: #1a7f37; }
+.badge.warn { background: #9a6700; }
+.badge.fail { background: #cf222e; }
+details { margin-bottom: 1em; }
+summary { cursor: pointer; }
+li { font-family: ui-monospace, SFMono-Regular, Menlo, monospace; font-size: 0.9em; white-space: pre-wrap; }
+</style>
+";
+
+/// A colored `<span>` badge for `status`.
+fn html_badge(status: HealthStatus) -> String {
+    let class = match status {
+        HealthStatus::Pass => "pass",
+        HealthStatus::Warn => "warn",
+        HealthStatus::Fail => "fail",
+    };
+    format!("<span class=\"badge {}\">{}</span>", class, class)
+}
+
+/// Escape text for use in HTML/XML element content and attribute values.
+fn escape_markup(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
     }
+    escaped
 }
 
 /// Health checker for the workspace.
+#[derive(Clone)]
 pub struct HealthChecker {
     workspace_root: PathBuf,
+    options: CheckOptions,
+    max_concurrency: usize,
+    timeout: Duration,
+}
+
+/// How long a single check may run before it is reported as failed.
+pub const DEFAULT_CHECK_TIMEOUT: Duration = Duration::from_secs(10 * 60);
+
+/// Spec file extensions counted by default.
+pub const DEFAULT_SPEC_EXTENSIONS: &[&str] = &["md", "txt"];
+
+/// Directory levels below the root searched for packages and git
+/// repositories by default.
+pub const DEFAULT_SCAN_DEPTH: usize = 2;
+
+/// Arguments passed to `cargo test` by default.
+pub const DEFAULT_TEST_ARGS: &[&str] = &["--all-features"];
+
+/// Spec coverage percentage below which the Specs check warns.
+pub const DEFAULT_SPEC_THRESHOLD: f64 = 100.0;
+
+/// Compiler error lines kept per package by the build check.
+const MAX_BUILD_ERROR_LINES: usize = 5;
+
+/// Age past which a registry index cache entry is too old for the yanked
+/// check to trust.
+pub const INDEX_CACHE_MAX_AGE: Duration = Duration::from_secs(7 * 24 * 60 * 60);
+
+/// Settings shared by every check in a run.
+#[derive(Debug, Clone)]
+struct CheckOptions {
+    default_members: bool,
+    target: Option<String>,
+    specs_info_only: bool,
+    spec_extensions: Vec<String>,
+    spec_threshold: f64,
+    audit: bool,
+    prefix: String,
+    fetch: bool,
+    /// Package directory names to limit checks to (all if `None`).
+    packages: Option<Vec<String>>,
+    /// Packages left out by [`HealthChecker::changed_since`].
+    skipped: Vec<String>,
+    weights: BTreeMap<HealthCheckType, u32>,
+    /// The cargo binary every check runs.
+    cargo: PathBuf,
+    /// Arguments to `cargo test` before `--`.
+    test_args: Vec<String>,
+    /// Arguments to `cargo rustdoc` before `--`.
+    doc_args: Vec<String>,
+    /// Measure documentation coverage with nightly rustdoc.
+    doc_coverage: bool,
+    /// Directory levels walked below the root, if set explicitly.
+    max_depth: Option<usize>,
+}
+
+impl Default for CheckOptions {
+    fn default() -> Self {
+        Self {
+            default_members: false,
+            target: None,
+            specs_info_only: false,
+            spec_extensions: DEFAULT_SPEC_EXTENSIONS
+                .iter()
+                .map(|s| s.to_string())
+                .collect(),
+            spec_threshold: DEFAULT_SPEC_THRESHOLD,
+            audit: false,
+            prefix: DEFAULT_PACKAGE_PREFIX.to_string(),
+            fetch: false,
+            packages: None,
+            skipped: Vec::new(),
+            weights: BTreeMap::new(),
+            cargo: PathBuf::from("cargo"),
+            test_args: DEFAULT_TEST_ARGS.iter().map(|s| s.to_string()).collect(),
+            doc_args: Vec::new(),
+            doc_coverage: false,
+            max_depth: None,
+        }
+    }
+}
+
+impl CheckOptions {
+    /// Whether `name` is one of the selected packages (always true if none are).
+    fn is_selected(&self, name: &str) -> bool {
+        match &self.packages {
+            Some(packages) => packages.iter().any(|p| p == name),
+            None => true,
+        }
+    }
+
+    /// Whether `path` has one of the accepted spec extensions.
+    fn is_spec_file(&self, path: &Path) -> bool {
+        path.extension()
+            .and_then(|ext| ext.to_str())
+            .map(|ext| {
+                self.spec_extensions
+                    .iter()
+                    .any(|accepted| accepted.eq_ignore_ascii_case(ext))
+            })
+            .unwrap_or(false)
+    }
+
+    /// A scanner over `workspace_root` using these options.
+    fn scanner(&self, workspace_root: &Path) -> WorkspaceScanner {
+        let scanner = WorkspaceScanner::new(workspace_root)
+            .with_default_members(self.default_members)
+            .with_prefix(&self.prefix);
+        match self.max_depth {
+            Some(depth) => scanner.with_max_depth(depth),
+            None => scanner,
+        }
+    }
+
+    /// Directory levels walked below the root when looking for packages and
+    /// git repositories.
+    fn scan_depth(&self) -> usize {
+        self.max_depth.unwrap_or(DEFAULT_SCAN_DEPTH)
+    }
+
+    /// `--target <triple>` arguments for cargo, if a target is set.
+    fn target_args(&self) -> Vec<&str> {
+        match &self.target {
+            Some(target) => vec!["--target", target],
+            None => vec![],
+        }
+    }
 }
 
 impl HealthChecker {
     /// Create a new health checker.
     pub fn new(workspace_root: impl AsRef<Path>) -> Self {
         let workspace_root = workspace_root.as_ref().to_path_buf();
-        Self { workspace_root }
+        Self {
+            workspace_root,
+            options: CheckOptions::default(),
+            max_concurrency: std::thread::available_parallelism()
+                .map(|n| n.get())
+                .unwrap_or(1),
+            timeout: DEFAULT_CHECK_TIMEOUT,
+        }
     }
 
-    /// Run all health checks in parallel.
-    pub async fn check_all(&self, verbose: bool) -> Result<HealthReport> {
-        let checks = vec![
-            HealthCheckType::Git,
-            HealthCheckType::Version,
-            HealthCheckType::Tests,
-            HealthCheckType::Docs,
-            HealthCheckType::Specs,
-        ];
+    /// Run at most `n` checks at once (default: the number of CPUs).
+    ///
+    /// Values below 1 are treated as 1.
+    pub fn with_max_concurrency(mut self, n: usize) -> Self {
+        self.max_concurrency = n.max(1);
+        self
+    }
 
-        self.check_selected(&checks, verbose).await
+    /// Fail any single check that runs longer than `timeout`
+    /// (default: [`DEFAULT_CHECK_TIMEOUT`]).
+    ///
+    /// A timed-out check is reported as failed right away, but its cargo
+    /// process keeps its concurrency slot until it exits, so later checks
+    /// never run alongside more than `max_concurrency` processes.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
     }
 
-    /// Run selected health checks in parallel.
-    pub async fn check_selected(
-        &self,
-        check_types: &[HealthCheckType],
-        verbose: bool,
-    ) -> Result<HealthReport> {
-        let mut handles: Vec<JoinHandle<Result<HealthCheckResult>>> = Vec::new();
+    /// Scope package checks to the workspace's `default-members`, if declared.
+    pub fn with_default_members(mut self, enabled: bool) -> Self {
+        self.options.default_members = enabled;
+        self
+    }
 
-        for &check_type in check_types {
-            let workspace_root = self.workspace_root.clone();
+    /// Build tests and docs for the given target triple instead of the host.
+    pub fn with_target(mut self, target: Option<String>) -> Self {
+        self.options.target = target;
+        self
+    }
 
-            let handle = tokio::spawn(async move {
-                match check_type {
-                    HealthCheckType::Git => {
-                        Self::check_git_status_static(&workspace_root, verbose).await
-                    }
-                    HealthCheckType::Version => {
-                        Self::check_version_alignment_static(&workspace_root, verbose).await
-                    }
-                    HealthCheckType::Tests => {
-                        Self::check_tests_static(&workspace_root, verbose).await
-                    }
-                    HealthCheckType::Docs => {
-                        Self::check_docs_static(&workspace_root, verbose).await
-                    }
-                    HealthCheckType::Specs => {
-                        Self::check_spec_coverage_static(&workspace_root, verbose).await
-                    }
-                }
-            });
+    /// Set the file extensions that count as specs (default: `md`, `txt`).
+    ///
+    /// A leading `.` is ignored and matching is case-insensitive.
+    pub fn with_spec_extensions(mut self, extensions: &[String]) -> Self {
+        self.options.spec_extensions = extensions
+            .iter()
+            .map(|ext| ext.trim_start_matches('.').to_string())
+            .collect();
+        self
+    }
 
-            handles.push(handle);
-        }
+    /// Warn only when spec coverage is below `percent` (default: 100, i.e.
+    /// any package without specs warns). Clamped to `0..=100`.
+    pub fn with_spec_threshold(mut self, percent: f64) -> Self {
+        self.options.spec_threshold = percent.clamp(0.0, 100.0);
+        self
+    }
 
-        let mut results = Vec::new();
-        for handle in handles {
-            match handle.await {
-                Ok(Ok(result)) => results.push(result),
-                Ok(Err(e)) => {
-                    eprintln!("Health check failed: {}", e);
-                    // Continue with other checks
-                }
-                Err(e) => {
-                    eprintln!("Task panicked: {}", e);
-                }
+    /// Report spec coverage as informational: the Specs check always passes.
+    pub fn with_specs_info_only(mut self, enabled: bool) -> Self {
+        self.options.specs_info_only = enabled;
+        self
+    }
+
+    /// Only check packages whose name starts with `prefix` (default:
+    /// [`DEFAULT_PACKAGE_PREFIX`]).
+    pub fn with_prefix(mut self, prefix: &str) -> Self {
+        self.options.prefix = prefix.to_string();
+        self
+    }
+
+    /// Include the `cargo audit` check in [`HealthChecker::check_all`].
+    ///
+    /// Off by default so `check_all` doesn't need `cargo-audit` installed.
+    pub fn with_audit(mut self, enabled: bool) -> Self {
+        self.options.audit = enabled;
+        self
+    }
+
+    /// Only check these packages, by directory name.
+    ///
+    /// Package-level checks skip the others, the git check only looks at the
+    /// repositories holding them, and version alignment still reads the whole
+    /// workspace but only reports problems in them. Checks fail to start if a
+    /// name isn't a workspace package.
+    pub fn with_packages(mut self, packages: &[String]) -> Self {
+        self.options.packages = (!packages.is_empty()).then(|| packages.to_vec());
+        self
+    }
+
+    /// A checker limited to the packages with changes since `base_ref`,
+    /// including uncommitted ones.
+    ///
+    /// Other packages are listed as skipped in the report. `base_ref` must
+    /// resolve in every package's repository.
+    pub fn changed_since(&self, base_ref: &str) -> Result<Self> {
+        let scanner = self.options.scanner(&self.workspace_root);
+        let files = scanner.changed_files_since(base_ref)?;
+        let changed = scanner.packages_containing(&files)?;
+
+        // Map package names to the directory names checks select by
+        let changed_dirs: Vec<String> = scanner
+            .find_embeddenator_packages()?
+            .into_iter()
+            .filter(|m| changed.contains(&m.package_name))
+            .filter_map(|m| Some(m.path.parent()?.file_name()?.to_str()?.to_string()))
+            .collect();
+
+        let (selected, skipped): (Vec<String>, Vec<String>) =
+            Self::find_packages_static(&self.workspace_root, &self.options)?
+                .iter()
+                .filter_map(|p| p.file_name()?.to_str().map(String::from))
+                .partition(|name| changed_dirs.contains(name));
+
+        let mut checker = self.clone();
+        checker.options.packages = Some(selected);
+        checker.options.skipped = skipped;
+        Ok(checker)
+    }
+
+    /// Search `depth` directory levels below the root for packages and git
+    /// repositories (default: [`DEFAULT_SCAN_DEPTH`]). A package directory
+    /// counts at its own level; a repository at the level of its `.git`.
+    ///
+    /// Also bounds the manifest scan used by version and member checks, which
+    /// is otherwise unlimited.
+    pub fn with_max_depth(mut self, depth: usize) -> Self {
+        self.options.max_depth = Some(depth);
+        self
+    }
+
+    /// Run this cargo binary instead of `cargo` from `PATH` (e.g. a vendored
+    /// cargo or a wrapper such as `cross`).
+    pub fn with_cargo_path(mut self, cargo: impl Into<PathBuf>) -> Self {
+        self.options.cargo = cargo.into();
+        self
+    }
+
+    /// Replace the arguments passed to `cargo test` before `--` (default:
+    /// [`DEFAULT_TEST_ARGS`]).
+    pub fn with_test_args(mut self, args: Vec<String>) -> Self {
+        self.options.test_args = args;
+        self
+    }
+
+    /// Arguments passed to `cargo rustdoc` before `--` (default: none).
+    pub fn with_doc_args(mut self, args: Vec<String>) -> Self {
+        self.options.doc_args = args;
+        self
+    }
+
+    /// Report the percentage of documented items in the docs check.
+    ///
+    /// Off by default: it documents every package a second time with
+    /// nightly rustdoc, and is skipped with a note if nightly isn't installed.
+    pub fn with_doc_coverage(mut self, enabled: bool) -> Self {
+        self.options.doc_coverage = enabled;
+        self
+    }
+
+    /// Override check weights used by [`HealthReport::score`]; unlisted checks
+    /// keep their [`HealthCheckType::default_weight`].
+    pub fn with_weights(mut self, weights: &[(HealthCheckType, u32)]) -> Self {
+        for &(check_type, weight) in weights {
+            if weight == check_type.default_weight() {
+                self.options.weights.remove(&check_type);
+            } else {
+                self.options.weights.insert(check_type, weight);
+            }
+        }
+        self
+    }
+
+    /// Fetch each repository's upstream remote before computing ahead/behind.
+    ///
+    /// Off by default to avoid network calls; a failed fetch falls back to
+    /// the last fetched state and is reported as a warning.
+    pub fn with_fetch(mut self, enabled: bool) -> Self {
+        self.options.fetch = enabled;
+        self
+    }
+
+    /// Error if any package given to [`HealthChecker::with_packages`] doesn't exist.
+    fn validate_packages(&self) -> Result<()> {
+        let Some(packages) = &self.options.packages else {
+            return Ok(());
+        };
+
+        let all = CheckOptions {
+            packages: None,
+            ..self.options.clone()
+        };
+        let found: Vec<String> = Self::find_packages_static(&self.workspace_root, &all)?
+            .iter()
+            .filter_map(|p| p.file_name()?.to_str().map(String::from))
+            .collect();
+        let missing: Vec<&str> = packages
+            .iter()
+            .filter(|p| !found.contains(p))
+            .map(String::as_str)
+            .collect();
+        if !missing.is_empty() {
+            anyhow::bail!("Package(s) not found in workspace: {}", missing.join(", "));
+        }
+        Ok(())
+    }
+
+    /// Run all health checks in parallel.
+    pub async fn check_all(&self, verbose: bool) -> Result<HealthReport> {
+        let mut checks = vec![
+            HealthCheckType::Git,
+            HealthCheckType::Version,
+            HealthCheckType::Msrv,
+            HealthCheckType::Tests,
+            HealthCheckType::Docs,
+            HealthCheckType::Specs,
+            HealthCheckType::Yanked,
+            HealthCheckType::Members,
+            HealthCheckType::Cycles,
+            HealthCheckType::Clippy,
+            HealthCheckType::Fmt,
+        ];
+        if self.options.audit {
+            checks.push(HealthCheckType::Audit);
+        }
+
+        self.check_selected(&checks, verbose).await
+    }
+
+    /// Run selected health checks in parallel.
+    pub async fn check_selected(
+        &self,
+        check_types: &[HealthCheckType],
+        verbose: bool,
+    ) -> Result<HealthReport> {
+        self.validate_packages()?;
+
+        let mut handles: Vec<JoinHandle<Result<HealthCheckResult>>> = Vec::new();
+        let permits = Arc::new(Semaphore::new(self.max_concurrency));
+        let timeout = self.timeout;
+
+        for &check_type in check_types {
+            let workspace_root = self.workspace_root.clone();
+            let options = self.options.clone();
+            let permits = Arc::clone(&permits);
+
+            let handle = tokio::spawn(async move {
+                let permit = permits
+                    .acquire_owned()
+                    .await
+                    .context("Health check semaphore closed")?;
+                let started = Instant::now();
+                let check = tokio::task::spawn_blocking(move || {
+                    // Checks shell out to cargo synchronously; running them off the
+                    // async workers lets the timeout fire even if one hangs. The
+                    // permit lives as long as the work, not the timeout.
+                    let _permit = permit;
+                    Handle::current().block_on(Self::run_check(
+                        check_type,
+                        &workspace_root,
+                        &options,
+                        verbose,
+                    ))
+                });
+
+                let mut result = match tokio::time::timeout(timeout, check).await {
+                    Ok(joined) => joined.context("Health check task panicked")??,
+                    Err(_) => HealthCheckResult {
+                        check_type,
+                        status: HealthStatus::Fail,
+                        message: format!(
+                            "{} check timed out after {:?}",
+                            check_type.as_str(),
+                            timeout
+                        ),
+                        details: Vec::new(),
+                        spec_coverage: None,
+                        doc_coverage: None,
+                        test_counts: None,
+                        duration_ms: 0,
+                        packages: Vec::new(),
+                    },
+                };
+                result.duration_ms = started.elapsed().as_millis() as u64;
+                Ok(result)
+            });
+
+            handles.push(handle);
+        }
+
+        let mut results = Vec::new();
+        for handle in handles {
+            match handle.await {
+                Ok(Ok(result)) => results.push(result),
+                Ok(Err(e)) => {
+                    eprintln!("Health check failed: {}", e);
+                    // Continue with other checks
+                }
+                Err(e) => {
+                    eprintln!("Task panicked: {}", e);
+                }
             }
         }
 
+        // Keep report ordering independent of the order checks were requested in
+        results.sort_by_key(|r| r.check_type);
+
         // Determine overall status
         let overall_status = if results.iter().any(|r| r.status == HealthStatus::Fail) {
             HealthStatus::Fail
@@ -290,39 +1377,121 @@ impl HealthChecker {
             HealthStatus::Pass
         };
 
-        Ok(HealthReport {
-            timestamp: chrono::Local::now().to_rfc3339(),
+        let mut report = HealthReport {
+            timestamp: chrono::Local::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, false),
             workspace_root: self.workspace_root.clone(),
             checks: results,
             overall_status,
-        })
+            skipped: self.options.skipped.clone(),
+            weights: self.options.weights.clone(),
+            score: 0,
+        };
+        report.score = report.score();
+        Ok(report)
+    }
+
+    /// Run a single check.
+    async fn run_check(
+        check_type: HealthCheckType,
+        workspace_root: &Path,
+        options: &CheckOptions,
+        verbose: bool,
+    ) -> Result<HealthCheckResult> {
+        match check_type {
+            HealthCheckType::Git => {
+                Self::check_git_status_static(workspace_root, options, verbose).await
+            }
+            HealthCheckType::Version => {
+                Self::check_version_alignment_static(workspace_root, options, verbose).await
+            }
+            HealthCheckType::Msrv => {
+                Self::check_msrv_static(workspace_root, options, verbose).await
+            }
+            HealthCheckType::Tests => {
+                Self::check_tests_static(workspace_root, options, verbose).await
+            }
+            HealthCheckType::Build => {
+                Self::check_build_static(workspace_root, options, verbose).await
+            }
+            HealthCheckType::Docs => {
+                Self::check_docs_static(workspace_root, options, verbose).await
+            }
+            HealthCheckType::Specs => {
+                Self::check_spec_coverage_static(workspace_root, options, verbose).await
+            }
+            HealthCheckType::Yanked => {
+                Self::check_yanked_static(workspace_root, options, verbose).await
+            }
+            HealthCheckType::Members => {
+                Self::check_workspace_members_static(workspace_root, options, verbose).await
+            }
+            HealthCheckType::Cycles => {
+                Self::check_dependency_cycles_static(workspace_root, options, verbose).await
+            }
+            HealthCheckType::Clippy => {
+                Self::check_clippy_static(workspace_root, options, verbose).await
+            }
+            HealthCheckType::Fmt => Self::check_fmt_static(workspace_root, options, verbose).await,
+            HealthCheckType::Audit => {
+                Self::check_audit_static(workspace_root, options, verbose).await
+            }
+        }
     }
 
     /// Check git status across all repositories.
     async fn check_git_status_static(
         workspace_root: &Path,
+        options: &CheckOptions,
         verbose: bool,
     ) -> Result<HealthCheckResult> {
-        let repos = Self::find_git_repos_static(workspace_root)?;
+        let mut repos = Self::find_git_repos_static(workspace_root, options)?;
+        if options.packages.is_some() {
+            let selected = Self::find_packages_static(workspace_root, options)?;
+            repos.retain(|repo| selected.iter().any(|pkg| pkg.starts_with(repo)));
+        }
         let mut all_clean = true;
         let mut details = Vec::new();
         let mut warnings = Vec::new();
+        let mut problem_repos = Vec::new();
+        let mut dirty_repos = 0;
+        let (mut staged, mut modified, mut untracked) = (0, 0, 0);
 
         for repo_path in &repos {
+            let repo_name = repo_path
+                .strip_prefix(workspace_root)
+                .unwrap_or(repo_path)
+                .display()
+                .to_string();
+
+            if options.fetch {
+                if let Err(e) = Self::fetch_upstream_static(repo_path) {
+                    warnings.push(format!(
+                        "{}: fetch failed, using last fetched upstream state: {:#}",
+                        repo_name, e
+                    ));
+                }
+            }
+
             match Self::get_git_status_static(repo_path) {
                 Ok(status) => {
-                    let repo_name = repo_path
-                        .strip_prefix(workspace_root)
-                        .unwrap_or(repo_path)
-                        .display()
-                        .to_string();
+                    if status.is_dirty
+                        || status.ahead > 0
+                        || status.behind > 0
+                        || !status.has_upstream
+                    {
+                        problem_repos.push(repo_name.clone());
+                    }
 
                     if status.is_dirty {
                         all_clean = false;
+                        dirty_repos += 1;
+                        staged += status.staged.len();
+                        modified += status.modified.len();
+                        untracked += status.untracked.len();
                         details.push(format!(
-                            "{}: {} dirty file(s) on branch {}",
+                            "{}: {} on branch {}",
                             repo_name,
-                            status.dirty_files.len(),
+                            status.change_summary(),
                             status.branch
                         ));
 
@@ -346,6 +1515,13 @@ impl HealthChecker {
                             repo_name, status.branch
                         ));
                     }
+
+                    if status.stash_count > 0 {
+                        warnings.push(format!(
+                            "{}: {} stash(es) present",
+                            repo_name, status.stash_count
+                        ));
+                    }
                 }
                 Err(e) => {
                     warnings.push(format!("Failed to check {}: {}", repo_path.display(), e));
@@ -365,8 +1541,9 @@ impl HealthChecker {
             format!("All {} repositories are clean and synced", repos.len())
         } else if !all_clean {
             format!(
-                "Found {} repositories with uncommitted changes",
-                details.len()
+                "Found {} repositories with uncommitted changes ({})",
+                dirty_repos,
+                describe_changes(staged, modified, untracked)
             )
         } else {
             format!("All repositories clean, {} warning(s)", warnings.len())
@@ -379,39 +1556,118 @@ impl HealthChecker {
             status,
             message,
             details,
+            spec_coverage: None,
+            doc_coverage: None,
+            test_counts: None,
+            duration_ms: 0,
+            packages: problem_repos,
         })
     }
 
     /// Check version alignment across packages.
     async fn check_version_alignment_static(
         workspace_root: &Path,
-        _verbose: bool,
+        options: &CheckOptions,
+        verbose: bool,
     ) -> Result<HealthCheckResult> {
-        let version_manager = VersionManager::new(workspace_root);
+        let version_manager = VersionManager::new(workspace_root)
+            .with_default_members(options.default_members)
+            .with_prefix(&options.prefix);
+
+        // Report only on selected packages, matched by name or directory
+        let selected = |manifest: &CargoManifest| {
+            options.is_selected(&manifest.package_name)
+                || manifest
+                    .path
+                    .parent()
+                    .and_then(|dir| dir.file_name())
+                    .and_then(|n| n.to_str())
+                    .is_some_and(|d| options.is_selected(d))
+        };
 
-        match version_manager.check_consistency() {
+        match version_manager.check_consistency_where(selected) {
             Ok(report) => {
-                let status = if report.has_issues() {
-                    HealthStatus::Fail
-                } else {
-                    HealthStatus::Pass
-                };
+                let scanner = options.scanner(workspace_root);
+
+                // Manifests the version check couldn't see at all
+                let (_, mut parse_errors) = scanner.find_manifests_with_errors();
+                parse_errors.retain(|(path, _)| {
+                    path.parent()
+                        .and_then(|dir| dir.file_name())
+                        .and_then(|n| n.to_str())
+                        .is_some_and(|d| options.is_selected(d))
+                });
+
+                // Names the version map can only resolve to one of the manifests
+                let mut duplicates = scanner.duplicate_package_names();
+                duplicates.retain(|(name, _)| options.is_selected(name));
+
+                // Package name -> directory relative to the workspace root
+                let dirs: BTreeMap<String, String> = scanner
+                    .find_embeddenator_packages()
+                    .unwrap_or_default()
+                    .into_iter()
+                    .filter_map(|m| {
+                        let dir = m.path.parent()?;
+                        Some((m.package_name, Self::relative_path(workspace_root, dir)))
+                    })
+                    .collect();
 
-                let message = if report.has_issues() {
+                let status =
+                    if report.has_issues() || !parse_errors.is_empty() || !duplicates.is_empty() {
+                        HealthStatus::Fail
+                    } else if !report.convention_warnings.is_empty() {
+                        HealthStatus::Warn
+                    } else {
+                        HealthStatus::Pass
+                    };
+
+                let mut message = if report.has_issues() {
                     format!(
                         "Version inconsistencies detected: {} issue(s), {} dependency mismatch(es)",
                         report.issues.len(),
                         report.inconsistencies.len()
                     )
+                } else if !report.convention_warnings.is_empty() {
+                    format!(
+                        "All {} packages have consistent versions, {} convention warning(s)",
+                        report.total_packages,
+                        report.convention_warnings.len()
+                    )
                 } else {
                     format!(
                         "All {} packages have consistent versions",
                         report.total_packages
                     )
                 };
+                if !parse_errors.is_empty() {
+                    message.push_str(&format!(
+                        "; {} manifest(s) failed to parse",
+                        parse_errors.len()
+                    ));
+                }
+                if !duplicates.is_empty() {
+                    message.push_str(&format!("; {} duplicate package name(s)", duplicates.len()));
+                }
 
                 let mut details = Vec::new();
 
+                for (path, e) in &parse_errors {
+                    details.push(format!(
+                        "{}: failed to parse: {:#}",
+                        Self::relative_path(workspace_root, path),
+                        e
+                    ));
+                }
+
+                for (name, paths) in &duplicates {
+                    let paths: Vec<String> = paths
+                        .iter()
+                        .map(|p| Self::relative_path(workspace_root, p))
+                        .collect();
+                    details.push(format!("{}: declared by {}", name, paths.join(", ")));
+                }
+
                 for issue in &report.issues {
                     details.push(issue.clone());
                 }
@@ -423,11 +1679,43 @@ impl HealthChecker {
                     ));
                 }
 
+                details.extend(report.convention_warnings.iter().cloned());
+
+                if verbose {
+                    details.push(format!(
+                        "{} inter-crate dependency link(s) verified",
+                        report.verified.len()
+                    ));
+                }
+
+                // Attribute dependency mismatches to the declaring package's directory
+                let mut packages: Vec<String> = report
+                    .inconsistencies
+                    .iter()
+                    .filter_map(|inc| dirs.get(&inc.package).cloned())
+                    .chain(
+                        parse_errors
+                            .iter()
+                            .map(|(path, _)| path)
+                            .chain(duplicates.iter().flat_map(|(_, paths)| paths))
+                            .filter_map(|path| {
+                                Some(Self::relative_path(workspace_root, path.parent()?))
+                            }),
+                    )
+                    .collect();
+                packages.sort();
+                packages.dedup();
+
                 Ok(HealthCheckResult {
                     check_type: HealthCheckType::Version,
                     status,
                     message,
                     details,
+                    spec_coverage: None,
+                    doc_coverage: None,
+                    test_counts: None,
+                    duration_ms: 0,
+                    packages,
                 })
             }
             Err(e) => Ok(HealthCheckResult {
@@ -435,26 +1723,238 @@ impl HealthChecker {
                 status: HealthStatus::Fail,
                 message: format!("Failed to check versions: {}", e),
                 details: vec![],
+                spec_coverage: None,
+                doc_coverage: None,
+                test_counts: None,
+                duration_ms: 0,
+                packages: Vec::new(),
             }),
         }
     }
 
-    /// Check test coverage by running cargo test.
-    async fn check_tests_static(
+    /// Check that every package declares the same `rust-version`.
+    ///
+    /// `rust-version.workspace = true` resolves against the root manifest's
+    /// `[workspace.package]`. Packages without one are listed too.
+    async fn check_msrv_static(
         workspace_root: &Path,
+        options: &CheckOptions,
         _verbose: bool,
     ) -> Result<HealthCheckResult> {
-        let packages = Self::find_packages_static(workspace_root)?;
+        let manifests = options
+            .scanner(workspace_root)
+            .find_embeddenator_packages()?;
+        let workspace_msrv = Self::workspace_rust_version(workspace_root)?;
+
+        let msrvs: Vec<(&CargoManifest, Option<String>)> = manifests
+            .iter()
+            .map(|m| {
+                let msrv = match &m.rust_version {
+                    Some(msrv) => Some(msrv.clone()),
+                    None if m.inherits_rust_version() => workspace_msrv.clone(),
+                    None => None,
+                };
+                (m, msrv)
+            })
+            .collect();
+
+        let mut counts: BTreeMap<&str, usize> = BTreeMap::new();
+        for msrv in msrvs.iter().filter_map(|(_, msrv)| msrv.as_deref()) {
+            *counts.entry(msrv).or_default() += 1;
+        }
+        // The most common MSRV is taken as the intended one
+        let expected = counts
+            .iter()
+            .max_by_key(|(_, count)| **count)
+            .map(|(msrv, _)| *msrv);
+        let missing = msrvs.iter().filter(|(_, msrv)| msrv.is_none()).count();
+
+        let mut details = Vec::new();
+        let mut problem_packages = Vec::new();
+        for (manifest, msrv) in &msrvs {
+            details.push(format!(
+                "{}: {}",
+                manifest.package_name,
+                msrv.as_deref().unwrap_or("no rust-version")
+            ));
+            if msrv.as_deref() != expected || msrv.is_none() {
+                if let Some(dir) = manifest.path.parent() {
+                    problem_packages.push(Self::relative_path(workspace_root, dir));
+                }
+            }
+        }
+
+        let (status, message) = if counts.len() > 1 {
+            (
+                HealthStatus::Warn,
+                format!(
+                    "MSRV drift: {} different rust-version values across {} packages",
+                    counts.len(),
+                    manifests.len()
+                ),
+            )
+        } else if missing > 0 {
+            (
+                HealthStatus::Warn,
+                format!(
+                    "{} of {} packages don't declare rust-version",
+                    missing,
+                    manifests.len()
+                ),
+            )
+        } else if let Some(msrv) = expected {
+            (
+                HealthStatus::Pass,
+                format!("All {} packages require Rust {}", manifests.len(), msrv),
+            )
+        } else {
+            (HealthStatus::Pass, "No packages found".to_string())
+        };
+
+        Ok(HealthCheckResult {
+            check_type: HealthCheckType::Msrv,
+            status,
+            message,
+            details,
+            spec_coverage: None,
+            doc_coverage: None,
+            test_counts: None,
+            duration_ms: 0,
+            packages: problem_packages,
+        })
+    }
+
+    /// `[workspace.package] rust-version` from the root manifest, if any.
+    fn workspace_rust_version(workspace_root: &Path) -> Result<Option<String>> {
+        let path = workspace_root.join("Cargo.toml");
+        if !path.exists() {
+            return Ok(None);
+        }
+        let content = std::fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        let doc: toml_edit::DocumentMut = content
+            .parse()
+            .with_context(|| format!("Failed to parse {}", path.display()))?;
+
+        Ok(doc
+            .get("workspace")
+            .and_then(|w| w.get("package"))
+            .and_then(|p| p.get("rust-version"))
+            .and_then(|v| v.as_str())
+            .map(String::from))
+    }
+
+    /// Check that every package compiles with `cargo check`.
+    ///
+    /// A fast pre-flight for the test check: nothing is linked or run.
+    async fn check_build_static(
+        workspace_root: &Path,
+        options: &CheckOptions,
+        _verbose: bool,
+    ) -> Result<HealthCheckResult> {
+        let packages = Self::find_packages_static(workspace_root, options)?;
         let mut passed = 0;
         let mut failed = 0;
         let mut details = Vec::new();
+        let mut problem_packages = Vec::new();
 
         for pkg_path in &packages {
-            let output = Command::new("cargo")
-                .arg("test")
+            let output = Command::new(&options.cargo)
+                .arg("check")
                 .arg("--manifest-path")
                 .arg(pkg_path.join("Cargo.toml"))
+                .arg("--all-targets")
                 .arg("--all-features")
+                .args(options.target_args())
+                .stdout(Stdio::piped())
+                .stderr(Stdio::piped())
+                .output();
+
+            let pkg_name = pkg_path
+                .file_name()
+                .and_then(|n| n.to_str())
+                .unwrap_or("unknown");
+
+            match output {
+                Ok(output) => {
+                    if output.status.success() {
+                        passed += 1;
+                        continue;
+                    }
+
+                    failed += 1;
+                    problem_packages.push(Self::relative_path(workspace_root, pkg_path));
+                    let stderr = String::from_utf8_lossy(&output.stderr);
+                    details.push(format!("{}: failed to build", pkg_name));
+
+                    // Not every error has a code (e.g. syntax errors), so fall
+                    // back to any `error` line
+                    let mut errors = Self::compile_errors(&stderr);
+                    if errors.is_empty() {
+                        errors = stderr
+                            .lines()
+                            .map(str::trim)
+                            .filter(|l| l.starts_with("error"))
+                            .map(String::from)
+                            .collect();
+                    }
+                    for error in errors.iter().take(MAX_BUILD_ERROR_LINES) {
+                        details.push(format!("  {}", error));
+                    }
+                }
+                Err(e) => {
+                    failed += 1;
+                    problem_packages.push(Self::relative_path(workspace_root, pkg_path));
+                    details.push(format!("{}: failed to run cargo check: {}", pkg_name, e));
+                }
+            }
+        }
+
+        let status = if failed > 0 {
+            HealthStatus::Fail
+        } else {
+            HealthStatus::Pass
+        };
+
+        let message = format!(
+            "Build: {} compiled, {} failed out of {} packages",
+            passed,
+            failed,
+            packages.len()
+        );
+
+        Ok(HealthCheckResult {
+            check_type: HealthCheckType::Build,
+            status,
+            message,
+            details,
+            spec_coverage: None,
+            doc_coverage: None,
+            test_counts: None,
+            duration_ms: 0,
+            packages: problem_packages,
+        })
+    }
+
+    /// Check test coverage by running cargo test.
+    async fn check_tests_static(
+        workspace_root: &Path,
+        options: &CheckOptions,
+        _verbose: bool,
+    ) -> Result<HealthCheckResult> {
+        let packages = Self::find_packages_static(workspace_root, options)?;
+        let mut failed_packages = 0;
+        let mut counts = TestCounts::default();
+        let mut details = Vec::new();
+        let mut problem_packages = Vec::new();
+
+        for pkg_path in &packages {
+            let output = Command::new(&options.cargo)
+                .arg("test")
+                .arg("--manifest-path")
+                .arg(pkg_path.join("Cargo.toml"))
+                .args(&options.test_args)
+                .args(options.target_args())
                 .arg("--")
                 .arg("--test-threads=1")
                 .arg("--quiet")
@@ -469,28 +1969,272 @@ impl HealthChecker {
 
             match output {
                 Ok(output) => {
-                    if output.status.success() {
-                        passed += 1;
-                    } else {
-                        failed += 1;
+                    let stdout = String::from_utf8_lossy(&output.stdout);
+                    counts += Self::parse_test_counts(&stdout);
+
+                    if !output.status.success() {
+                        failed_packages += 1;
+                        problem_packages.push(Self::relative_path(workspace_root, pkg_path));
                         let stderr = String::from_utf8_lossy(&output.stderr);
                         details.push(format!("{}: tests failed", pkg_name));
 
                         // Extract test failure summary
-                        for line in stderr.lines() {
+                        for line in stdout.lines().chain(stderr.lines()) {
                             if line.contains("test result:") || line.contains("FAILED") {
                                 details.push(format!("  {}", line.trim()));
                             }
                         }
+
+                        for error in Self::compile_errors(&stderr) {
+                            details.push(format!("  {}", error));
+                        }
                     }
                 }
                 Err(e) => {
-                    failed += 1;
+                    failed_packages += 1;
+                    problem_packages.push(Self::relative_path(workspace_root, pkg_path));
                     details.push(format!("{}: failed to run tests: {}", pkg_name, e));
                 }
             }
         }
 
+        let status = if failed_packages > 0 {
+            HealthStatus::Fail
+        } else {
+            HealthStatus::Pass
+        };
+
+        let mut message = format!(
+            "Tests: {} passed, {} failed across {} packages",
+            counts.passed,
+            counts.failed,
+            packages.len()
+        );
+        if counts.ignored > 0 {
+            message.push_str(&format!(" ({} ignored)", counts.ignored));
+        }
+
+        Ok(HealthCheckResult {
+            check_type: HealthCheckType::Tests,
+            status,
+            message,
+            details,
+            spec_coverage: None,
+            doc_coverage: None,
+            test_counts: Some(counts),
+            duration_ms: 0,
+            packages: problem_packages,
+        })
+    }
+
+    /// Sum the `test result: ok. 42 passed; 0 failed; 1 ignored; ...` lines
+    /// printed for each test binary.
+    pub(crate) fn parse_test_counts(stdout: &str) -> TestCounts {
+        let mut counts = TestCounts::default();
+        for line in stdout.lines() {
+            let Some(summary) = line.trim().strip_prefix("test result:") else {
+                continue;
+            };
+            // Skip the `ok.`/`FAILED.` verdict
+            let summary = summary.split_once('.').map_or(summary, |(_, rest)| rest);
+            for part in summary.split(';') {
+                let mut words = part.split_whitespace();
+                let (Some(n), Some(kind)) = (words.next(), words.next()) else {
+                    continue;
+                };
+                let Ok(n) = n.parse::<usize>() else {
+                    continue;
+                };
+                match kind {
+                    "passed" => counts.passed += n,
+                    "failed" => counts.failed += n,
+                    "ignored" => counts.ignored += n,
+                    _ => {}
+                }
+            }
+        }
+        counts
+    }
+
+    /// Run clippy with `-D warnings` on every package.
+    ///
+    /// Lints denied by `-D warnings` count as warnings; anything else that
+    /// stops clippy (compile errors, `deny` lints) counts as an error.
+    async fn check_clippy_static(
+        workspace_root: &Path,
+        options: &CheckOptions,
+        _verbose: bool,
+    ) -> Result<HealthCheckResult> {
+        let packages = Self::find_packages_static(workspace_root, options)?;
+        let mut total_warnings = 0;
+        let mut total_errors = 0;
+        let mut details = Vec::new();
+        let mut problem_packages = Vec::new();
+
+        for pkg_path in &packages {
+            let output = Command::new(&options.cargo)
+                .arg("clippy")
+                .arg("--manifest-path")
+                .arg(pkg_path.join("Cargo.toml"))
+                .arg("--all-targets")
+                .arg("--all-features")
+                .args(options.target_args())
+                .arg("--")
+                .arg("-D")
+                .arg("warnings")
+                .stdout(Stdio::piped())
+                .stderr(Stdio::piped())
+                .output();
+
+            let pkg_name = pkg_path
+                .file_name()
+                .and_then(|n| n.to_str())
+                .unwrap_or("unknown");
+
+            match output {
+                Ok(output) => {
+                    let stderr = String::from_utf8_lossy(&output.stderr);
+                    let (warnings, mut errors) = Self::parse_clippy_counts(&stderr);
+                    if !output.status.success() && warnings == 0 && errors == 0 {
+                        // clippy itself failed (not installed, bad manifest, ...)
+                        errors = 1;
+                        let reason = stderr
+                            .lines()
+                            .find(|l| l.starts_with("error"))
+                            .unwrap_or("cargo clippy failed")
+                            .trim();
+                        details.push(format!("{}: {}", pkg_name, reason));
+                    } else if warnings > 0 || errors > 0 {
+                        details.push(format!(
+                            "{}: {} warning(s), {} error(s)",
+                            pkg_name, warnings, errors
+                        ));
+                    }
+
+                    if warnings > 0 || errors > 0 {
+                        problem_packages.push(Self::relative_path(workspace_root, pkg_path));
+                    }
+                    total_warnings += warnings;
+                    total_errors += errors;
+                }
+                Err(e) => {
+                    total_errors += 1;
+                    problem_packages.push(Self::relative_path(workspace_root, pkg_path));
+                    details.push(format!("{}: failed to run clippy: {}", pkg_name, e));
+                }
+            }
+        }
+
+        let status = if total_errors > 0 {
+            HealthStatus::Fail
+        } else if total_warnings > 0 {
+            HealthStatus::Warn
+        } else {
+            HealthStatus::Pass
+        };
+
+        let message = format!(
+            "Clippy: {} warning(s), {} error(s) across {} packages",
+            total_warnings,
+            total_errors,
+            packages.len()
+        );
+
+        Ok(HealthCheckResult {
+            check_type: HealthCheckType::Clippy,
+            status,
+            message,
+            details,
+            spec_coverage: None,
+            doc_coverage: None,
+            test_counts: None,
+            duration_ms: 0,
+            packages: problem_packages,
+        })
+    }
+
+    /// Check formatting with `cargo fmt -- --check` on every package.
+    ///
+    /// Packages without any `.rs` files have nothing to format and pass.
+    async fn check_fmt_static(
+        workspace_root: &Path,
+        options: &CheckOptions,
+        _verbose: bool,
+    ) -> Result<HealthCheckResult> {
+        let packages = Self::find_packages_static(workspace_root, options)?;
+        let mut passed = 0;
+        let mut failed = 0;
+        let mut details = Vec::new();
+        let mut problem_packages = Vec::new();
+
+        for pkg_path in &packages {
+            let pkg_name = pkg_path
+                .file_name()
+                .and_then(|n| n.to_str())
+                .unwrap_or("unknown");
+
+            let has_rust_files = walkdir::WalkDir::new(pkg_path)
+                .into_iter()
+                .filter_entry(|e| e.file_name() != "target")
+                .filter_map(|e| e.ok())
+                .any(|e| e.path().extension().is_some_and(|ext| ext == "rs"));
+            if !has_rust_files {
+                passed += 1;
+                continue;
+            }
+
+            let output = Command::new(&options.cargo)
+                .arg("fmt")
+                .arg("--manifest-path")
+                .arg(pkg_path.join("Cargo.toml"))
+                .arg("--")
+                .arg("--check")
+                .arg("--color=never")
+                .stdout(Stdio::piped())
+                .stderr(Stdio::piped())
+                .output();
+
+            match output {
+                Ok(output) => {
+                    if output.status.success() {
+                        passed += 1;
+                        continue;
+                    }
+
+                    failed += 1;
+                    problem_packages.push(Self::relative_path(workspace_root, pkg_path));
+                    let stdout = String::from_utf8_lossy(&output.stdout);
+                    let files = Self::parse_fmt_diff_files(&stdout);
+                    if files.is_empty() {
+                        let stderr = String::from_utf8_lossy(&output.stderr);
+                        let reason = stderr
+                            .lines()
+                            .find(|l| l.starts_with("error"))
+                            .unwrap_or("cargo fmt failed")
+                            .trim();
+                        details.push(format!("{}: {}", pkg_name, reason));
+                    } else {
+                        details.push(format!(
+                            "{}: {} file(s) need formatting",
+                            pkg_name,
+                            files.len()
+                        ));
+                        for file in files {
+                            details.push(format!(
+                                "  {}",
+                                Self::relative_path(workspace_root, Path::new(&file))
+                            ));
+                        }
+                    }
+                }
+                Err(e) => {
+                    failed += 1;
+                    problem_packages.push(Self::relative_path(workspace_root, pkg_path));
+                    details.push(format!("{}: failed to run cargo fmt: {}", pkg_name, e));
+                }
+            }
+        }
+
         let status = if failed > 0 {
             HealthStatus::Fail
         } else {
@@ -498,32 +2242,142 @@ impl HealthChecker {
         };
 
         let message = format!(
-            "Tests: {} passed, {} failed out of {} packages",
+            "Formatting: {} formatted, {} need formatting out of {} packages",
             passed,
             failed,
             packages.len()
         );
 
         Ok(HealthCheckResult {
-            check_type: HealthCheckType::Tests,
+            check_type: HealthCheckType::Fmt,
+            status,
+            message,
+            details,
+            spec_coverage: None,
+            doc_coverage: None,
+            test_counts: None,
+            duration_ms: 0,
+            packages: problem_packages,
+        })
+    }
+
+    /// Scan the workspace lockfile for security advisories with `cargo audit`.
+    ///
+    /// Warns instead of failing when `cargo-audit` isn't installed or can't
+    /// produce a report (e.g. no `Cargo.lock`).
+    async fn check_audit_static(
+        workspace_root: &Path,
+        options: &CheckOptions,
+        _verbose: bool,
+    ) -> Result<HealthCheckResult> {
+        let output = Command::new(&options.cargo)
+            .arg("audit")
+            .arg("--json")
+            .current_dir(workspace_root)
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .output();
+
+        let unavailable = |message: String, details: Vec<String>| HealthCheckResult {
+            check_type: HealthCheckType::Audit,
+            status: HealthStatus::Warn,
+            message,
+            details,
+            spec_coverage: None,
+            doc_coverage: None,
+            test_counts: None,
+            duration_ms: 0,
+            packages: Vec::new(),
+        };
+
+        let output = match output {
+            Ok(output) => output,
+            Err(e) => {
+                return Ok(unavailable(
+                    format!("Could not run cargo audit: {}", e),
+                    Vec::new(),
+                ))
+            }
+        };
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let advisories = match Self::parse_audit_advisories(&stdout) {
+            Ok(advisories) => advisories,
+            Err(_) => {
+                let stderr = String::from_utf8_lossy(&output.stderr);
+                if stderr.contains("no such command") {
+                    return Ok(unavailable(
+                        "cargo-audit is not installed; skipping security audit".to_string(),
+                        vec!["Install it with `cargo install cargo-audit`".to_string()],
+                    ));
+                }
+                let reason = stderr
+                    .lines()
+                    .find(|l| l.trim_start().starts_with("error"))
+                    .unwrap_or("cargo audit produced no report")
+                    .trim();
+                return Ok(unavailable(
+                    "Security audit could not be completed".to_string(),
+                    vec![reason.to_string()],
+                ));
+            }
+        };
+
+        let (status, message) = if advisories.is_empty() {
+            (HealthStatus::Pass, "No known vulnerabilities".to_string())
+        } else {
+            (
+                HealthStatus::Fail,
+                format!("Found {} vulnerability advisory(ies)", advisories.len()),
+            )
+        };
+
+        Ok(HealthCheckResult {
+            check_type: HealthCheckType::Audit,
             status,
             message,
-            details,
+            details: advisories,
+            spec_coverage: None,
+            doc_coverage: None,
+            test_counts: None,
+            duration_ms: 0,
+            packages: Vec::new(),
         })
     }
 
     /// Check documentation coverage.
-    async fn check_docs_static(workspace_root: &Path, _verbose: bool) -> Result<HealthCheckResult> {
-        let packages = Self::find_packages_static(workspace_root)?;
+    async fn check_docs_static(
+        workspace_root: &Path,
+        options: &CheckOptions,
+        _verbose: bool,
+    ) -> Result<HealthCheckResult> {
+        let packages = Self::find_packages_static(workspace_root, options)?;
         let mut passed = 0;
         let mut warnings = 0;
+        let mut build_failures = 0;
         let mut details = Vec::new();
+        let mut problem_packages = Vec::new();
+        let measure_coverage = options.doc_coverage && Self::nightly_installed();
+        if options.doc_coverage && !measure_coverage {
+            details.push(
+                "Doc coverage skipped: no nightly toolchain in `rustup toolchain list`".to_string(),
+            );
+        }
+        // Only reported if every package could be measured
+        let mut measured = measure_coverage.then_some((0, 0));
 
         for pkg_path in &packages {
-            let output = Command::new("cargo")
+            measured = measured.and_then(|(documented, total)| {
+                let coverage = Self::measure_doc_coverage(pkg_path, options)?;
+                Some((documented + coverage.documented, total + coverage.total))
+            });
+
+            let output = Command::new(&options.cargo)
                 .arg("rustdoc")
                 .arg("--manifest-path")
                 .arg(pkg_path.join("Cargo.toml"))
+                .args(&options.doc_args)
+                .args(options.target_args())
                 .arg("--")
                 .arg("-D")
                 .arg("warnings")
@@ -542,8 +2396,22 @@ impl HealthChecker {
                     if output.status.success() {
                         passed += 1;
                     } else {
-                        warnings += 1;
+                        problem_packages.push(Self::relative_path(workspace_root, pkg_path));
                         let stderr = String::from_utf8_lossy(&output.stderr);
+
+                        // A crate that doesn't compile (e.g. for --target) is a failure,
+                        // not a documentation warning
+                        let errors = Self::compile_errors(&stderr);
+                        if !errors.is_empty() {
+                            build_failures += 1;
+                            details.push(format!("{}: failed to build", pkg_name));
+                            for error in errors {
+                                details.push(format!("  {}", error));
+                            }
+                            continue;
+                        }
+
+                        warnings += 1;
                         let warning_count = stderr
                             .lines()
                             .filter(|l| {
@@ -561,41 +2429,132 @@ impl HealthChecker {
                 }
                 Err(e) => {
                     warnings += 1;
+                    problem_packages.push(Self::relative_path(workspace_root, pkg_path));
                     details.push(format!("{}: failed to check docs: {}", pkg_name, e));
                 }
             }
         }
 
-        let status = if warnings > 0 {
+        let status = if build_failures > 0 {
+            HealthStatus::Fail
+        } else if warnings > 0 {
             HealthStatus::Warn
         } else {
             HealthStatus::Pass
         };
 
-        let message = format!(
-            "Documentation: {} clean, {} with warnings out of {} packages",
-            passed,
-            warnings,
-            packages.len()
-        );
+        let doc_coverage = measured
+            .filter(|_| !packages.is_empty())
+            .map(|(documented, total)| DocCoverage::new(documented, total));
+
+        let mut message = if build_failures > 0 {
+            format!(
+                "Documentation: {} clean, {} with warnings, {} failed to build out of {} packages",
+                passed,
+                warnings,
+                build_failures,
+                packages.len()
+            )
+        } else {
+            format!(
+                "Documentation: {} clean, {} with warnings out of {} packages",
+                passed,
+                warnings,
+                packages.len()
+            )
+        };
+        if let Some(coverage) = &doc_coverage {
+            message.push_str(&format!(
+                ", {:.1}% documented ({}/{} items)",
+                coverage.percent, coverage.documented, coverage.total
+            ));
+        }
 
         Ok(HealthCheckResult {
             check_type: HealthCheckType::Docs,
             status,
             message,
             details,
+            spec_coverage: None,
+            doc_coverage,
+            test_counts: None,
+            duration_ms: 0,
+            packages: problem_packages,
         })
     }
 
+    /// Whether rustup has a nightly toolchain installed.
+    fn nightly_installed() -> bool {
+        Command::new("rustup")
+            .args(["toolchain", "list"])
+            .stderr(Stdio::null())
+            .output()
+            .is_ok_and(|output| {
+                output.status.success()
+                    && Self::lists_nightly(&String::from_utf8_lossy(&output.stdout))
+            })
+    }
+
+    /// Whether `rustup toolchain list` output includes a nightly toolchain.
+    pub(crate) fn lists_nightly(toolchains: &str) -> bool {
+        toolchains
+            .lines()
+            .any(|line| line.trim_start().starts_with("nightly"))
+    }
+
+    /// Measure documentation coverage with nightly rustdoc's `--show-coverage`.
+    ///
+    /// Returns `None` if the crate doesn't build.
+    fn measure_doc_coverage(pkg_path: &Path, options: &CheckOptions) -> Option<DocCoverage> {
+        let output = Command::new(&options.cargo)
+            .arg("+nightly")
+            .arg("rustdoc")
+            .arg("--quiet")
+            .arg("--manifest-path")
+            .arg(pkg_path.join("Cargo.toml"))
+            .args(&options.doc_args)
+            .args(options.target_args())
+            .arg("--")
+            .arg("-Z")
+            .arg("unstable-options")
+            .arg("--show-coverage")
+            .arg("--output-format")
+            .arg("json")
+            .arg("--document-private-items")
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .output()
+            .ok()?;
+        if !output.status.success() {
+            return None;
+        }
+        Self::parse_doc_coverage(&String::from_utf8_lossy(&output.stdout)).ok()
+    }
+
+    /// Sum the per-file counts in rustdoc's `--show-coverage` JSON output.
+    pub(crate) fn parse_doc_coverage(json: &str) -> Result<DocCoverage> {
+        let report: BTreeMap<String, serde_json::Value> =
+            serde_json::from_str(json.trim()).context("Failed to parse rustdoc coverage")?;
+        let count = |file: &serde_json::Value, key: &str| {
+            file.get(key).and_then(|v| v.as_u64()).unwrap_or(0) as usize
+        };
+        let (documented, total) = report.values().fold((0, 0), |(documented, total), file| {
+            (
+                documented + count(file, "with_docs"),
+                total + count(file, "total"),
+            )
+        });
+        Ok(DocCoverage::new(documented, total))
+    }
+
     /// Check spec coverage (presence of specs/ directories and documentation).
     async fn check_spec_coverage_static(
         workspace_root: &Path,
+        options: &CheckOptions,
         _verbose: bool,
     ) -> Result<HealthCheckResult> {
-        let packages = Self::find_packages_static(workspace_root)?;
-        let mut with_specs = 0;
-        let mut without_specs = 0;
-        let mut details = Vec::new();
+        let packages = Self::find_packages_static(workspace_root, options)?;
+        let mut coverage = BTreeMap::new();
 
         for pkg_path in &packages {
             let specs_dir = pkg_path.join("specs");
@@ -604,31 +2563,60 @@ impl HealthChecker {
                 .and_then(|n| n.to_str())
                 .unwrap_or("unknown");
 
-            if specs_dir.exists() && specs_dir.is_dir() {
+            let mut entry = SpecCoverage {
+                has_dir: specs_dir.is_dir(),
+                spec_files: 0,
+                valid_files: 0,
+            };
+
+            if entry.has_dir {
                 // Count spec files
-                let spec_count = walkdir::WalkDir::new(&specs_dir)
+                let spec_files: Vec<PathBuf> = walkdir::WalkDir::new(&specs_dir)
                     .into_iter()
                     .filter_map(|e| e.ok())
                     .filter(|e| e.file_type().is_file())
-                    .filter(|e| {
-                        e.path()
-                            .extension()
-                            .and_then(|ext| ext.to_str())
-                            .map(|ext| ext == "md" || ext == "txt")
+                    .filter(|e| options.is_spec_file(e.path()))
+                    .map(|e| e.into_path())
+                    .collect();
+
+                entry.spec_files = spec_files.len();
+                entry.valid_files = spec_files
+                    .iter()
+                    .filter(|path| {
+                        std::fs::read_to_string(path)
+                            .map(|content| !content.trim().is_empty())
                             .unwrap_or(false)
                     })
                     .count();
+            }
 
-                with_specs += 1;
-                if spec_count > 0 {
-                    details.push(format!("{}: {} spec file(s)", pkg_name, spec_count));
-                }
-            } else {
-                without_specs += 1;
+            coverage.insert(pkg_name.to_string(), entry);
+        }
+
+        // Text details are derived from the structured map
+        let mut details = Vec::new();
+        for (pkg_name, entry) in &coverage {
+            if !entry.has_dir {
                 details.push(format!("{}: missing specs/ directory", pkg_name));
+            } else if entry.spec_files > 0 {
+                details.push(format!("{}: {} spec file(s)", pkg_name, entry.spec_files));
             }
         }
 
+        let missing: Vec<String> = packages
+            .iter()
+            .filter(|path| {
+                path.file_name()
+                    .and_then(|n| n.to_str())
+                    .and_then(|name| coverage.get(name))
+                    .is_some_and(|c| !c.has_dir)
+            })
+            .map(|path| Self::relative_path(workspace_root, path))
+            .collect();
+
+        let with_specs = coverage.values().filter(|c| c.has_dir).count();
+        let without_specs = coverage.len() - with_specs;
+
         let total = packages.len();
         let coverage_pct = if total > 0 {
             (with_specs as f64 / total as f64) * 100.0
@@ -636,32 +2624,532 @@ impl HealthChecker {
             0.0
         };
 
-        let status = if without_specs > 0 {
+        let status = if without_specs > 0
+            && coverage_pct < options.spec_threshold
+            && !options.specs_info_only
+        {
             HealthStatus::Warn
         } else {
             HealthStatus::Pass
         };
 
-        let message = format!(
+        let mut message = format!(
             "Spec coverage: {:.1}% ({}/{} packages with specs/)",
             coverage_pct, with_specs, total
         );
+        if options.spec_threshold < DEFAULT_SPEC_THRESHOLD {
+            message.push_str(&format!(", {}% required", options.spec_threshold));
+        }
+        if options.specs_info_only {
+            message.push_str(" (informational)");
+        }
 
         Ok(HealthCheckResult {
             check_type: HealthCheckType::Specs,
             status,
             message,
             details,
+            spec_coverage: Some(coverage),
+            doc_coverage: None,
+            test_counts: None,
+            duration_ms: 0,
+            packages: missing,
+        })
+    }
+
+    /// Check that no package resolves a dependency to a yanked registry version.
+    ///
+    /// Cargo reports locked yanked versions as warnings while resolving, so this
+    /// runs `cargo metadata` per package and collects those warnings. Cargo
+    /// reads yank flags from its local index cache, so registry dependencies
+    /// whose cache entry is missing or older than [`INDEX_CACHE_MAX_AGE`] are
+    /// reported as unknown. Either that or an unreachable registry degrades
+    /// the check to a warning instead of passing.
+    async fn check_yanked_static(
+        workspace_root: &Path,
+        options: &CheckOptions,
+        _verbose: bool,
+    ) -> Result<HealthCheckResult> {
+        let packages = Self::find_packages_static(workspace_root, options)?;
+        let mut yanked = 0;
+        let mut unverified = 0;
+        let mut details = Vec::new();
+        let mut problem_packages = Vec::new();
+        let cargo_home = Self::cargo_home();
+
+        for pkg_path in &packages {
+            let output = Command::new(&options.cargo)
+                .arg("metadata")
+                .arg("--format-version=1")
+                .arg("--manifest-path")
+                .arg(pkg_path.join("Cargo.toml"))
+                .stdout(Stdio::piped())
+                .stderr(Stdio::piped())
+                .output();
+
+            let pkg_name = pkg_path
+                .file_name()
+                .and_then(|n| n.to_str())
+                .unwrap_or("unknown");
+
+            match output {
+                Ok(output) => {
+                    let stderr = String::from_utf8_lossy(&output.stderr);
+                    let found = Self::parse_yanked_warnings(&stderr);
+
+                    if !found.is_empty() || !output.status.success() {
+                        problem_packages.push(Self::relative_path(workspace_root, pkg_path));
+                    }
+
+                    if !found.is_empty() {
+                        yanked += found.len();
+                        for dep in found {
+                            details.push(format!("{}: uses yanked {}", pkg_name, dep));
+                        }
+                    } else if !output.status.success() {
+                        unverified += 1;
+                        let reason = stderr
+                            .lines()
+                            .find(|l| l.starts_with("error"))
+                            .unwrap_or("cargo metadata failed")
+                            .trim();
+                        details.push(format!("{}: could not verify: {}", pkg_name, reason));
+                    } else {
+                        let registry =
+                            Self::registry_packages(&String::from_utf8_lossy(&output.stdout));
+                        let unknown = match &cargo_home {
+                            Some(cargo_home) => Self::unknown_yank_status(
+                                cargo_home,
+                                &registry,
+                                INDEX_CACHE_MAX_AGE,
+                            ),
+                            None => registry,
+                        };
+                        if !unknown.is_empty() {
+                            unverified += 1;
+                            problem_packages.push(Self::relative_path(workspace_root, pkg_path));
+                            details.push(format!(
+                                "{}: yanked status unknown (registry index cache missing or stale): {}",
+                                pkg_name,
+                                unknown.join(", ")
+                            ));
+                        }
+                    }
+                }
+                Err(e) => {
+                    unverified += 1;
+                    problem_packages.push(Self::relative_path(workspace_root, pkg_path));
+                    details.push(format!("{}: could not verify: {}", pkg_name, e));
+                }
+            }
+        }
+
+        let status = if yanked > 0 {
+            HealthStatus::Fail
+        } else if unverified > 0 {
+            HealthStatus::Warn
+        } else {
+            HealthStatus::Pass
+        };
+
+        let message = if yanked > 0 {
+            format!("Found {} yanked dependency version(s)", yanked)
+        } else if unverified > 0 {
+            format!(
+                "No yanked dependencies found, {} of {} packages could not be verified",
+                unverified,
+                packages.len()
+            )
+        } else {
+            format!("No yanked dependencies in {} packages", packages.len())
+        };
+
+        Ok(HealthCheckResult {
+            check_type: HealthCheckType::Yanked,
+            status,
+            message,
+            details,
+            spec_coverage: None,
+            doc_coverage: None,
+            test_counts: None,
+            duration_ms: 0,
+            packages: problem_packages,
+        })
+    }
+
+    /// Check that on-disk packages and `[workspace] members` agree.
+    ///
+    /// Packages missing from the members list are silently skipped by
+    /// `cargo build --workspace`; members missing on disk break the build.
+    async fn check_workspace_members_static(
+        workspace_root: &Path,
+        options: &CheckOptions,
+        _verbose: bool,
+    ) -> Result<HealthCheckResult> {
+        let scanner = options
+            .scanner(workspace_root)
+            .with_max_depth(options.scan_depth());
+
+        let Some(members) = scanner.workspace_members()? else {
+            return Ok(HealthCheckResult {
+                check_type: HealthCheckType::Members,
+                status: HealthStatus::Pass,
+                message: "No [workspace] manifest at root, skipping member check".to_string(),
+                details: vec![],
+                spec_coverage: None,
+                doc_coverage: None,
+                test_counts: None,
+                duration_ms: 0,
+                packages: Vec::new(),
+            });
+        };
+
+        // Compare normalized paths so a relative root matches resolved members
+        let root = normalize_path(workspace_root);
+        let member_dirs: Vec<PathBuf> = members.iter().map(|m| normalize_path(m)).collect();
+        let package_dirs: Vec<PathBuf> = scanner
+            .find_packages_on_disk()?
+            .iter()
+            .filter_map(|m| m.path.parent().map(normalize_path))
+            .filter(|dir| *dir != root)
+            .collect();
+
+        let relative = |path: &Path| {
+            let path = normalize_path(path);
+            path.strip_prefix(&root)
+                .unwrap_or(&path)
+                .display()
+                .to_string()
+        };
+
+        let mut details = Vec::new();
+
+        let not_members: Vec<&PathBuf> = package_dirs
+            .iter()
+            .filter(|dir| !member_dirs.contains(dir))
+            .collect();
+        for dir in &not_members {
+            details.push(format!(
+                "{}: not listed in workspace members",
+                relative(dir)
+            ));
+        }
+
+        let missing: Vec<&PathBuf> = members
+            .iter()
+            .filter(|m| !m.join("Cargo.toml").exists())
+            .collect();
+        for member in &missing {
+            details.push(format!(
+                "{}: workspace member missing on disk",
+                relative(member)
+            ));
+        }
+
+        let status = if !missing.is_empty() {
+            HealthStatus::Fail
+        } else if !not_members.is_empty() {
+            HealthStatus::Warn
+        } else {
+            HealthStatus::Pass
+        };
+
+        let message = if details.is_empty() {
+            format!("All {} workspace members are present", members.len())
+        } else {
+            format!(
+                "{} package(s) not in members, {} member(s) missing on disk",
+                not_members.len(),
+                missing.len()
+            )
+        };
+
+        Ok(HealthCheckResult {
+            check_type: HealthCheckType::Members,
+            status,
+            message,
+            details,
+            spec_coverage: None,
+            doc_coverage: None,
+            test_counts: None,
+            duration_ms: 0,
+            packages: not_members
+                .iter()
+                .chain(missing.iter())
+                .map(|dir| relative(dir))
+                .collect(),
+        })
+    }
+
+    /// Check that the embeddenator packages don't depend on each other in a cycle.
+    async fn check_dependency_cycles_static(
+        workspace_root: &Path,
+        options: &CheckOptions,
+        _verbose: bool,
+    ) -> Result<HealthCheckResult> {
+        let scanner = options.scanner(workspace_root);
+        let cycles = scanner.dependency_cycles()?;
+
+        let dirs: BTreeMap<String, String> = scanner
+            .find_embeddenator_packages()?
+            .into_iter()
+            .filter_map(|m| {
+                let dir = m.path.parent()?;
+                Some((m.package_name, Self::relative_path(workspace_root, dir)))
+            })
+            .collect();
+
+        let details: Vec<String> = cycles
+            .iter()
+            .map(|cycle| format!("Cycle: {}", cycle.join(" ↔ ")))
+            .collect();
+        let mut packages: Vec<String> = cycles
+            .iter()
+            .flatten()
+            .filter_map(|name| dirs.get(name).cloned())
+            .collect();
+        packages.sort();
+
+        let (status, message) = if cycles.is_empty() {
+            (
+                HealthStatus::Pass,
+                "No dependency cycles between packages".to_string(),
+            )
+        } else {
+            (
+                HealthStatus::Fail,
+                format!("{} dependency cycle(s) between packages", cycles.len()),
+            )
+        };
+
+        Ok(HealthCheckResult {
+            check_type: HealthCheckType::Cycles,
+            status,
+            message,
+            details,
+            spec_coverage: None,
+            doc_coverage: None,
+            test_counts: None,
+            duration_ms: 0,
+            packages,
         })
     }
 
+    /// Display `path` relative to the workspace root.
+    fn relative_path(workspace_root: &Path, path: &Path) -> String {
+        path.strip_prefix(workspace_root)
+            .unwrap_or(path)
+            .display()
+            .to_string()
+    }
+
+    /// Extract compiler error lines (`error[E...]`, `could not compile`) from cargo output.
+    pub(crate) fn compile_errors(stderr: &str) -> Vec<String> {
+        stderr
+            .lines()
+            .map(str::trim)
+            .filter(|l| l.starts_with("error[E") || l.contains("could not compile"))
+            .map(String::from)
+            .collect()
+    }
+
+    /// Count `(warnings, errors)` in clippy output run with `-D warnings`.
+    ///
+    /// Only diagnostics pointing at source (`--> file:line`) are counted, which
+    /// skips cargo's own messages and summaries. An error is counted as a
+    /// warning when it carries clippy's lint help link or the
+    /// ``implied by `-D warnings` `` note.
+    pub(crate) fn parse_clippy_counts(stderr: &str) -> (usize, usize) {
+        let mut warnings = 0;
+        let mut errors = 0;
+        // (is_error, is_denied_lint, has_location) for the diagnostic being read
+        let mut current: Option<(bool, bool, bool)> = None;
+
+        let mut finish = |diagnostic: Option<(bool, bool, bool)>| match diagnostic {
+            Some((true, false, true)) => errors += 1,
+            Some((_, _, true)) => warnings += 1,
+            _ => {}
+        };
+
+        for line in stderr.lines() {
+            let is_error = line.starts_with("error");
+            if is_error || line.starts_with("warning") {
+                finish(current.take());
+                current = Some((is_error, false, false));
+            } else if let Some((_, denied, located)) = current.as_mut() {
+                if line.trim_start().starts_with("--> ") {
+                    *located = true;
+                } else if line.contains("implied by `-D warnings`")
+                    || line.contains("rust-lang.github.io/rust-clippy")
+                {
+                    *denied = true;
+                }
+            }
+        }
+        finish(current);
+
+        (warnings, errors)
+    }
+
+    /// List `<advisory id>: <package> <version> (<title>)` for every
+    /// vulnerability in a `cargo audit --json` report.
+    pub(crate) fn parse_audit_advisories(json: &str) -> Result<Vec<String>> {
+        let report: serde_json::Value =
+            serde_json::from_str(json).context("Failed to parse cargo audit output")?;
+        let list = report
+            .get("vulnerabilities")
+            .and_then(|v| v.get("list"))
+            .and_then(|l| l.as_array())
+            .context("cargo audit output has no vulnerabilities list")?;
+
+        Ok(list
+            .iter()
+            .map(|vuln| {
+                let field = |section: &str, key: &str| {
+                    vuln.get(section)
+                        .and_then(|s| s.get(key))
+                        .and_then(|v| v.as_str())
+                        .unwrap_or("?")
+                };
+                format!(
+                    "{}: {} {} ({})",
+                    field("advisory", "id"),
+                    field("package", "name"),
+                    field("package", "version"),
+                    field("advisory", "title")
+                )
+            })
+            .collect())
+    }
+
+    /// Extract the files named in rustfmt's `Diff in <file>:<line>:` headers
+    /// (older rustfmt writes `Diff in <file> at line <line>:`).
+    pub(crate) fn parse_fmt_diff_files(stdout: &str) -> Vec<String> {
+        let mut files: Vec<String> = stdout
+            .lines()
+            .filter_map(|l| l.strip_prefix("Diff in "))
+            .map(|rest| {
+                let rest = rest.trim_end().trim_end_matches(':');
+                match rest.split_once(" at line ") {
+                    Some((file, _)) => file,
+                    None => rest
+                        .rsplit_once(':')
+                        .filter(|(_, line)| line.chars().all(|c| c.is_ascii_digit()))
+                        .map_or(rest, |(file, _)| file),
+                }
+                .to_string()
+            })
+            .collect();
+
+        files.sort();
+        files.dedup();
+        files
+    }
+
+    /// Cargo's home directory (`$CARGO_HOME`, else `~/.cargo`).
+    fn cargo_home() -> Option<PathBuf> {
+        std::env::var_os("CARGO_HOME")
+            .map(PathBuf::from)
+            .or_else(|| std::env::var_os("HOME").map(|home| Path::new(&home).join(".cargo")))
+    }
+
+    /// Registry dependencies in `cargo metadata` JSON output, as sorted
+    /// `name vX.Y.Z` entries. Path and git packages are left out.
+    pub(crate) fn registry_packages(metadata: &str) -> Vec<String> {
+        let Ok(metadata) = serde_json::from_str::<serde_json::Value>(metadata) else {
+            return Vec::new();
+        };
+        let mut found: Vec<String> = metadata
+            .get("packages")
+            .and_then(|p| p.as_array())
+            .into_iter()
+            .flatten()
+            .filter(|p| {
+                p.get("source")
+                    .and_then(|s| s.as_str())
+                    .is_some_and(|s| s.starts_with("registry+") || s.starts_with("sparse+"))
+            })
+            .filter_map(|p| {
+                let name = p.get("name")?.as_str()?;
+                let version = p.get("version")?.as_str()?;
+                Some(format!("{} v{}", name, version))
+            })
+            .collect();
+
+        found.sort();
+        found.dedup();
+        found
+    }
+
+    /// The entries of `packages` (`name vX.Y.Z`) whose index cache file under
+    /// `cargo_home` is missing or older than `max_age` in every registry.
+    pub(crate) fn unknown_yank_status(
+        cargo_home: &Path,
+        packages: &[String],
+        max_age: Duration,
+    ) -> Vec<String> {
+        let indexes: Vec<PathBuf> = std::fs::read_dir(cargo_home.join("registry").join("index"))
+            .into_iter()
+            .flatten()
+            .filter_map(|entry| entry.ok())
+            .map(|entry| entry.path().join(".cache"))
+            .collect();
+
+        packages
+            .iter()
+            .filter(|package| {
+                let name = package.split(' ').next().unwrap_or_default();
+                let entry = Self::index_cache_path(name);
+                !indexes.iter().any(|index| {
+                    std::fs::metadata(index.join(&entry))
+                        .and_then(|m| m.modified())
+                        .ok()
+                        .and_then(|modified| modified.elapsed().ok())
+                        .is_some_and(|age| age <= max_age)
+                })
+            })
+            .cloned()
+            .collect()
+    }
+
+    /// Where a crate's entry lives in a registry index, e.g. `se/rd/serde`.
+    pub(crate) fn index_cache_path(name: &str) -> PathBuf {
+        let name = name.to_lowercase();
+        match name.len() {
+            1 => Path::new("1").join(&name),
+            2 => Path::new("2").join(&name),
+            3 => Path::new("3").join(&name[..1]).join(&name),
+            _ => Path::new(&name[..2]).join(&name[2..4]).join(&name),
+        }
+    }
+
+    /// Extract `name vX.Y.Z` entries from cargo's "is yanked in registry" warnings.
+    pub(crate) fn parse_yanked_warnings(stderr: &str) -> Vec<String> {
+        let mut found: Vec<String> = stderr
+            .lines()
+            .filter(|l| l.contains("is yanked"))
+            .filter_map(|l| {
+                let start = l.find('`')? + 1;
+                let end = start + l[start..].find('`')?;
+                Some(l[start..end].to_string())
+            })
+            .collect();
+
+        found.sort();
+        found.dedup();
+        found
+    }
+
     // Helper methods
 
-    fn find_git_repos_static(workspace_root: &Path) -> Result<Vec<PathBuf>> {
+    fn find_git_repos_static(
+        workspace_root: &Path,
+        options: &CheckOptions,
+    ) -> Result<Vec<PathBuf>> {
         let mut repos = Vec::new();
 
         for entry in walkdir::WalkDir::new(workspace_root)
-            .max_depth(2)
+            .max_depth(options.scan_depth())
             .into_iter()
             .filter_entry(|e| {
                 let name = e.file_name().to_string_lossy();
@@ -680,20 +3168,98 @@ impl HealthChecker {
         Ok(repos)
     }
 
-    fn get_git_status_static(repo_path: &Path) -> Result<GitStatus> {
+    /// Fetch the upstream remote of the checked-out branch.
+    ///
+    /// Does nothing when the branch has no upstream configured.
+    pub(crate) fn fetch_upstream_static(repo_path: &Path) -> Result<()> {
         let repo = git2::Repository::open(repo_path).context("Failed to open git repository")?;
+        let head = repo.head().context("Failed to get HEAD")?;
+        let Some(refname) = head.name() else {
+            return Ok(());
+        };
+        let Ok(remote_name) = repo.branch_upstream_remote(refname) else {
+            return Ok(());
+        };
+        let remote_name = remote_name
+            .as_str()
+            .context("Upstream remote name is not valid UTF-8")?;
+        let mut remote = repo.find_remote(remote_name)?;
+
+        // libgit2 keeps asking for credentials until one works, so only try once
+        let config = repo.config()?;
+        let mut attempted = false;
+        let mut callbacks = git2::RemoteCallbacks::new();
+        callbacks.credentials(move |url, username, allowed| {
+            if attempted {
+                return Err(git2::Error::from_str("authentication failed"));
+            }
+            attempted = true;
+            if allowed.contains(git2::CredentialType::SSH_KEY) {
+                git2::Cred::ssh_key_from_agent(username.unwrap_or("git"))
+            } else if allowed.contains(git2::CredentialType::USER_PASS_PLAINTEXT) {
+                git2::Cred::credential_helper(&config, url, username)
+            } else {
+                git2::Cred::default()
+            }
+        });
+
+        let mut fetch_options = git2::FetchOptions::new();
+        fetch_options.remote_callbacks(callbacks);
+        remote
+            .fetch(&[] as &[&str], Some(&mut fetch_options), None)
+            .with_context(|| format!("Failed to fetch remote '{}'", remote_name))?;
+        Ok(())
+    }
+
+    pub(crate) fn get_git_status_static(repo_path: &Path) -> Result<GitStatus> {
+        let mut repo =
+            git2::Repository::open(repo_path).context("Failed to open git repository")?;
+
+        let mut stash_count = 0;
+        repo.stash_foreach(|_, _, _| {
+            stash_count += 1;
+            true
+        })?;
 
         let head = repo.head().context("Failed to get HEAD")?;
         let branch = head.shorthand().unwrap_or("(detached)").to_string();
 
-        // Check for dirty files
-        let statuses = repo.statuses(None)?;
-        let is_dirty = !statuses.is_empty();
+        // Check for dirty files; ignored files (build output etc.) don't count
+        let mut status_options = git2::StatusOptions::new();
+        status_options
+            .include_ignored(false)
+            .include_untracked(true)
+            .recurse_untracked_dirs(true);
+        let statuses = repo.statuses(Some(&mut status_options))?;
         let dirty_files: Vec<String> = statuses
             .iter()
             .filter_map(|s| s.path().map(String::from))
             .collect();
 
+        let files_with = |flags: git2::Status| -> Vec<String> {
+            statuses
+                .iter()
+                .filter(|s| s.status().intersects(flags))
+                .filter_map(|s| s.path().map(String::from))
+                .collect()
+        };
+        let staged = files_with(
+            git2::Status::INDEX_NEW
+                | git2::Status::INDEX_MODIFIED
+                | git2::Status::INDEX_DELETED
+                | git2::Status::INDEX_RENAMED
+                | git2::Status::INDEX_TYPECHANGE,
+        );
+        let modified = files_with(
+            git2::Status::WT_MODIFIED
+                | git2::Status::WT_DELETED
+                | git2::Status::WT_RENAMED
+                | git2::Status::WT_TYPECHANGE
+                | git2::Status::CONFLICTED,
+        );
+        let untracked = files_with(git2::Status::WT_NEW);
+        let is_dirty = !(staged.is_empty() && modified.is_empty() && untracked.is_empty());
+
         // Check upstream
         let (ahead, behind, has_upstream) =
             if let Ok(local_branch) = repo.find_branch(&branch, git2::BranchType::Local) {
@@ -718,14 +3284,18 @@ impl HealthChecker {
             behind,
             has_upstream,
             dirty_files,
+            staged,
+            modified,
+            untracked,
+            stash_count,
         })
     }
 
-    fn find_packages_static(workspace_root: &Path) -> Result<Vec<PathBuf>> {
+    fn find_packages_static(workspace_root: &Path, options: &CheckOptions) -> Result<Vec<PathBuf>> {
         let mut packages = Vec::new();
 
         for entry in walkdir::WalkDir::new(workspace_root)
-            .max_depth(2)
+            .max_depth(options.scan_depth())
             .into_iter()
             .filter_entry(|e| {
                 let name = e.file_name().to_string_lossy();
@@ -736,9 +3306,9 @@ impl HealthChecker {
             if entry.file_type().is_dir() {
                 let cargo_toml = entry.path().join("Cargo.toml");
                 if cargo_toml.exists() {
-                    // Only include embeddenator-* packages
+                    // Only include packages matching the prefix
                     if let Some(name) = entry.path().file_name() {
-                        if name.to_string_lossy().starts_with("embeddenator") {
+                        if name.to_string_lossy().starts_with(&options.prefix) {
                             packages.push(entry.path().to_path_buf());
                         }
                     }
@@ -746,28 +3316,20 @@ impl HealthChecker {
             }
         }
 
+        if options.default_members {
+            if let Some(defaults) = WorkspaceScanner::new(workspace_root).default_members()? {
+                let defaults: Vec<PathBuf> = defaults.iter().map(|d| normalize_path(d)).collect();
+                packages.retain(|p| defaults.contains(&normalize_path(p)));
+            }
+        }
+
+        packages.retain(|p| {
+            p.file_name()
+                .and_then(|n| n.to_str())
+                .is_some_and(|name| options.is_selected(name))
+        });
+
         packages.sort();
         Ok(packages)
     }
 }
-
-// Note: chrono is not in dependencies yet, using a simple timestamp instead
-mod chrono {
-    pub struct Local;
-    impl Local {
-        pub fn now() -> DateTime {
-            DateTime
-        }
-    }
-    pub struct DateTime;
-    impl DateTime {
-        pub fn to_rfc3339(&self) -> String {
-            use std::time::{SystemTime, UNIX_EPOCH};
-            let now = SystemTime::now()
-                .duration_since(UNIX_EPOCH)
-                .unwrap()
-                .as_secs();
-            format!("{}", now)
-        }
-    }
-}
@@ -2,8 +2,12 @@
 
 #[cfg(test)]
 mod tests {
-    use crate::{HealthCheckType, HealthChecker, HealthStatus};
+    use crate::{
+        HealthCheckType, HealthChecker, HealthStatus, SpecCoverage, TestCounts, INDEX_CACHE_MAX_AGE,
+    };
     use std::fs;
+    use std::path::Path;
+    use std::time::Duration;
     use tempfile::TempDir;
 
     fn create_test_workspace() -> TempDir {
@@ -83,6 +87,267 @@ edition = "2021"
         assert!(spec_check.message.contains("50.0%") || spec_check.message.contains("coverage"));
     }
 
+    #[tokio::test]
+    async fn test_spec_coverage_extensions() {
+        let temp_dir = create_test_workspace();
+        let specs_dir = temp_dir.path().join("embeddenator-test1/specs");
+        fs::write(specs_dir.join("design.adoc"), "= Design").unwrap();
+        fs::write(specs_dir.join("notes.rst"), "Notes").unwrap();
+
+        let spec_files = |checker: HealthChecker| async move {
+            let report = checker
+                .check_selected(&[HealthCheckType::Specs], false)
+                .await
+                .unwrap();
+            report.checks[0].spec_coverage.as_ref().unwrap()["embeddenator-test1"].spec_files
+        };
+
+        assert_eq!(spec_files(HealthChecker::new(temp_dir.path())).await, 1);
+        assert_eq!(
+            spec_files(
+                HealthChecker::new(temp_dir.path())
+                    .with_spec_extensions(&["md".to_string(), ".ADOC".to_string()])
+            )
+            .await,
+            2
+        );
+        assert_eq!(
+            spec_files(
+                HealthChecker::new(temp_dir.path()).with_spec_extensions(&["rst".to_string()])
+            )
+            .await,
+            1
+        );
+    }
+
+    #[tokio::test]
+    async fn test_spec_coverage_info_only() {
+        let temp_dir = create_test_workspace();
+        let checker = HealthChecker::new(temp_dir.path()).with_specs_info_only(true);
+
+        let report = checker
+            .check_selected(&[HealthCheckType::Specs], false)
+            .await
+            .unwrap();
+
+        let spec_check = &report.checks[0];
+        assert_eq!(spec_check.status, HealthStatus::Pass);
+        assert!(spec_check.message.contains("50.0%"));
+        assert!(spec_check.message.contains("informational"));
+    }
+
+    #[tokio::test]
+    async fn test_spec_coverage_threshold() {
+        let temp_dir = create_test_workspace();
+        let status = |threshold: f64| {
+            let checker = HealthChecker::new(temp_dir.path()).with_spec_threshold(threshold);
+            async move {
+                let report = checker
+                    .check_selected(&[HealthCheckType::Specs], false)
+                    .await
+                    .unwrap();
+                (report.checks[0].status, report.checks[0].message.clone())
+            }
+        };
+
+        // 50% coverage meets a 50% threshold even though a package lacks specs
+        let (at, message) = status(50.0).await;
+        assert_eq!(at, HealthStatus::Pass);
+        assert!(message.contains("50% required"));
+        assert_eq!(status(80.0).await.0, HealthStatus::Warn);
+        assert_eq!(status(500.0).await.0, HealthStatus::Warn);
+    }
+
+    #[tokio::test]
+    async fn test_with_packages_limits_checks() {
+        let temp_dir = create_test_workspace();
+        let checker =
+            HealthChecker::new(temp_dir.path()).with_packages(&["embeddenator-test1".to_string()]);
+
+        let report = checker
+            .check_selected(&[HealthCheckType::Specs], false)
+            .await
+            .unwrap();
+        let coverage = report.checks[0].spec_coverage.as_ref().unwrap();
+        assert_eq!(
+            coverage.keys().collect::<Vec<_>>(),
+            vec!["embeddenator-test1"]
+        );
+        assert_eq!(report.checks[0].status, HealthStatus::Pass);
+
+        let err = HealthChecker::new(temp_dir.path())
+            .with_packages(&[
+                "embeddenator-test1".to_string(),
+                "embeddenator-nope".to_string(),
+            ])
+            .check_selected(&[HealthCheckType::Specs], false)
+            .await
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Package(s) not found in workspace: embeddenator-nope"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_changed_since_skips_unchanged_packages() {
+        let temp_dir = create_test_workspace();
+        let root = temp_dir.path();
+        let repo = git2::Repository::init(root).unwrap();
+        let mut index = repo.index().unwrap();
+        index
+            .add_all(["*"].iter(), git2::IndexAddOption::DEFAULT, None)
+            .unwrap();
+        index.write().unwrap();
+        let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+        let sig = git2::Signature::now("Test", "test@example.com").unwrap();
+        repo.commit(Some("HEAD"), &sig, &sig, "init", &tree, &[])
+            .unwrap();
+
+        fs::write(
+            root.join("embeddenator-test2/src/lib.rs"),
+            "pub fn changed() {}",
+        )
+        .unwrap();
+
+        let checker = HealthChecker::new(root).changed_since("HEAD").unwrap();
+        let report = checker
+            .check_selected(&[HealthCheckType::Specs], false)
+            .await
+            .unwrap();
+
+        assert_eq!(report.skipped, vec!["embeddenator-test1"]);
+        let coverage = report.checks[0].spec_coverage.as_ref().unwrap();
+        assert_eq!(
+            coverage.keys().collect::<Vec<_>>(),
+            vec!["embeddenator-test2"]
+        );
+        assert!(report
+            .to_markdown()
+            .contains("**Skipped (unchanged):** embeddenator-test1"));
+
+        assert!(HealthChecker::new(root)
+            .changed_since("no-such-ref")
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_with_packages_version_reports_selected_only() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        for (name, manifest) in [
+            (
+                "embeddenator-vsa",
+                "[package]\nname = \"embeddenator-vsa\"\nversion = \"0.2.0\"\n",
+            ),
+            (
+                "embeddenator-io",
+                "[package]\nname = \"embeddenator-io\"\nversion = \"0.2.0\"\n\n[dependencies]\nembeddenator-vsa = \"=0.1.0\"\n",
+            ),
+            (
+                "embeddenator-fs",
+                "[package]\nname = \"embeddenator-fs\"\nversion = \"0.2.0-dev.1\"\n",
+            ),
+        ] {
+            fs::create_dir_all(root.join(name)).unwrap();
+            fs::write(root.join(name).join("Cargo.toml"), manifest).unwrap();
+        }
+
+        let version_check = |packages: &[&str]| {
+            let packages: Vec<String> = packages.iter().map(|p| p.to_string()).collect();
+            let checker = HealthChecker::new(root).with_packages(&packages);
+            async move {
+                checker
+                    .check_selected(&[HealthCheckType::Version], false)
+                    .await
+                    .unwrap()
+                    .checks
+                    .remove(0)
+            }
+        };
+
+        // The mismatch is declared by embeddenator-io against the whole workspace
+        let io = version_check(&["embeddenator-io"]).await;
+        assert_eq!(io.status, HealthStatus::Fail);
+        assert_eq!(io.packages, vec!["embeddenator-io"]);
+        assert!(!io.details.iter().any(|d| d.contains("prerelease label")));
+
+        // The io -> vsa mismatch isn't fs's problem
+        let fs_only = version_check(&["embeddenator-fs"]).await;
+        assert_eq!(fs_only.status, HealthStatus::Warn);
+        assert_eq!(
+            fs_only.details,
+            vec!["embeddenator-fs 0.2.0-dev.1 uses prerelease label 'dev' (allowed: alpha, beta, rc)"]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_report_by_owner() {
+        use crate::CodeOwners;
+
+        let temp_dir = create_test_workspace();
+        let checker = HealthChecker::new(temp_dir.path());
+
+        let report = checker
+            .check_selected(&[HealthCheckType::Specs], false)
+            .await
+            .unwrap();
+        assert_eq!(report.checks[0].packages, vec!["embeddenator-test2"]);
+
+        let codeowners = CodeOwners::parse("/embeddenator-test2/ @tzervas/core @bob\n").unwrap();
+        let grouped = report.by_owner(&codeowners);
+        assert_eq!(
+            grouped.keys().collect::<Vec<_>>(),
+            vec!["@bob", "@tzervas/core"]
+        );
+        assert_eq!(grouped["@bob"][0].check_type, HealthCheckType::Specs);
+        assert_eq!(grouped["@bob"][0].packages, vec!["embeddenator-test2"]);
+
+        let grouped = report.by_owner(&CodeOwners::default());
+        assert_eq!(grouped.keys().collect::<Vec<_>>(), vec!["unowned"]);
+
+        // Passing checks produce no owner entries
+        let checker = HealthChecker::new(temp_dir.path()).with_specs_info_only(true);
+        let report = checker
+            .check_selected(&[HealthCheckType::Specs], false)
+            .await
+            .unwrap();
+        assert!(report.by_owner(&codeowners).is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_spec_coverage_map() {
+        let temp_dir = create_test_workspace();
+        fs::write(
+            temp_dir.path().join("embeddenator-test1/specs/empty.txt"),
+            "  \n",
+        )
+        .unwrap();
+        let checker = HealthChecker::new(temp_dir.path());
+
+        let report = checker
+            .check_selected(&[HealthCheckType::Specs], false)
+            .await
+            .unwrap();
+
+        let coverage = report.checks[0].spec_coverage.as_ref().unwrap();
+        assert_eq!(
+            coverage["embeddenator-test1"],
+            SpecCoverage {
+                has_dir: true,
+                spec_files: 2,
+                valid_files: 1,
+            }
+        );
+        assert!(!coverage["embeddenator-test2"].has_dir);
+
+        let json = serde_json::to_value(&report).unwrap();
+        assert_eq!(
+            json["checks"][0]["spec_coverage"]["embeddenator-test1"]["spec_files"],
+            2
+        );
+    }
+
     #[tokio::test]
     async fn test_version_check() {
         let temp_dir = create_test_workspace();
@@ -99,6 +364,56 @@ edition = "2021"
         assert_eq!(version_check.status, HealthStatus::Pass);
     }
 
+    #[tokio::test]
+    async fn test_version_check_reports_duplicate_names() {
+        let temp_dir = create_test_workspace();
+        let copy = temp_dir.path().join("copy");
+        fs::create_dir_all(&copy).unwrap();
+        fs::copy(
+            temp_dir.path().join("embeddenator-test1/Cargo.toml"),
+            copy.join("Cargo.toml"),
+        )
+        .unwrap();
+
+        let report = HealthChecker::new(temp_dir.path())
+            .check_selected(&[HealthCheckType::Version], false)
+            .await
+            .unwrap();
+        let check = &report.checks[0];
+        assert_eq!(check.status, HealthStatus::Fail);
+        assert!(check.message.contains("1 duplicate package name(s)"));
+        assert!(check
+            .details
+            .iter()
+            .any(|d| d.starts_with("embeddenator-test1: declared by ")
+                && d.contains("copy/Cargo.toml")
+                && d.contains("embeddenator-test1/Cargo.toml")));
+        assert!(check.packages.contains(&"copy".to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_version_check_reports_unparsable_manifests() {
+        let temp_dir = create_test_workspace();
+        fs::write(
+            temp_dir.path().join("Cargo.toml"),
+            "[workspace]\nmembers = [\"embeddenator-*\"]\n",
+        )
+        .unwrap();
+        let broken = temp_dir.path().join("embeddenator-broken");
+        fs::create_dir_all(&broken).unwrap();
+        fs::write(broken.join("Cargo.toml"), "[package\nname = ").unwrap();
+
+        let report = HealthChecker::new(temp_dir.path())
+            .check_selected(&[HealthCheckType::Version], false)
+            .await
+            .unwrap();
+        let check = &report.checks[0];
+        assert_eq!(check.status, HealthStatus::Fail);
+        assert!(check.message.contains("1 manifest(s) failed to parse"));
+        assert!(check.details[0].starts_with("embeddenator-broken/Cargo.toml: failed to parse"));
+        assert_eq!(check.packages, vec!["embeddenator-broken"]);
+    }
+
     #[tokio::test]
     async fn test_parallel_checks() {
         let temp_dir = create_test_workspace();
@@ -149,6 +464,67 @@ edition = "2021"
         assert!(markdown.contains("## Check Results"));
     }
 
+    #[tokio::test]
+    async fn test_html_generation() {
+        let temp_dir = create_test_workspace();
+        let checker = HealthChecker::new(temp_dir.path());
+
+        let mut report = checker
+            .check_selected(&[HealthCheckType::Version], false)
+            .await
+            .unwrap();
+        report.checks[0].status = HealthStatus::Fail;
+        report.checks[0]
+            .details
+            .push("pkg: <script>alert(\"x\")</script> & more".to_string());
+        report.score = 42;
+
+        let html = report.to_html();
+
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(html.contains("<style>"));
+        assert!(html.contains("<span class=\"badge fail\">fail</span> version check"));
+        assert!(html.contains("<strong>Score:</strong> 42/100"));
+        assert!(html.contains("<details open>"));
+        assert!(html.contains("&lt;script&gt;alert(&quot;x&quot;)&lt;/script&gt; &amp; more"));
+        assert!(!html.contains("<script>"));
+        // Self-contained: no external stylesheets or scripts
+        assert!(!html.contains("<link") && !html.contains("src="));
+    }
+
+    #[tokio::test]
+    async fn test_junit_xml_generation() {
+        let temp_dir = create_test_workspace();
+        let checker = HealthChecker::new(temp_dir.path());
+
+        let mut report = checker
+            .check_selected(
+                &[
+                    HealthCheckType::Version,
+                    HealthCheckType::Specs,
+                    HealthCheckType::Members,
+                ],
+                false,
+            )
+            .await
+            .unwrap();
+        report.checks[0].status = HealthStatus::Fail;
+        report.checks[0].details = vec!["a < b".to_string()];
+        report.checks[1].status = HealthStatus::Warn;
+        report.checks[2].status = HealthStatus::Pass;
+
+        let xml = report.to_junit_xml();
+
+        assert!(xml.starts_with("<?xml version=\"1.0\" encoding=\"UTF-8\"?>"));
+        assert!(xml.contains("tests=\"3\" failures=\"1\" errors=\"0\" skipped=\"1\""));
+        assert!(xml.contains("<testcase name=\"version\" classname=\"health.version\">"));
+        assert!(xml.contains("type=\"fail\">a &lt; b</failure>"));
+        assert!(xml.contains("<testcase name=\"specs\""));
+        assert!(xml.contains("<skipped message="));
+        assert_eq!(xml.matches("<testcase ").count(), 3);
+        assert_eq!(xml.matches("</testcase>").count(), 3);
+    }
+
     #[tokio::test]
     async fn test_json_serialization() {
         let temp_dir = create_test_workspace();
@@ -165,6 +541,342 @@ edition = "2021"
         assert!(json.contains("overall_status"));
     }
 
+    #[tokio::test]
+    async fn test_timestamp_is_rfc3339() {
+        let temp_dir = create_test_workspace();
+        let checker = HealthChecker::new(temp_dir.path());
+
+        let report = checker
+            .check_selected(&[HealthCheckType::Version], false)
+            .await
+            .unwrap();
+
+        let parsed = chrono::DateTime::parse_from_rfc3339(&report.timestamp).unwrap();
+        assert_eq!(
+            parsed.to_rfc3339_opts(chrono::SecondsFormat::Secs, false),
+            report.timestamp
+        );
+    }
+
+    #[tokio::test]
+    async fn test_dependency_cycles_check() {
+        let temp_dir = create_test_workspace();
+        let checker = HealthChecker::new(temp_dir.path());
+
+        let report = checker
+            .check_selected(&[HealthCheckType::Cycles], false)
+            .await
+            .unwrap();
+        assert_eq!(report.checks[0].status, HealthStatus::Pass);
+
+        for (pkg, dep) in [
+            ("embeddenator-test1", "embeddenator-test2"),
+            ("embeddenator-test2", "embeddenator-test1"),
+        ] {
+            let manifest = temp_dir.path().join(pkg).join("Cargo.toml");
+            fs::write(
+                &manifest,
+                format!(
+                    "[package]\nname = \"{}\"\nversion = \"0.20.0-alpha.1\"\n\n[dependencies]\n{} = \"0.20.0-alpha.1\"\n",
+                    pkg, dep
+                ),
+            )
+            .unwrap();
+        }
+
+        let report = checker
+            .check_selected(&[HealthCheckType::Cycles], false)
+            .await
+            .unwrap();
+        let check = &report.checks[0];
+        assert_eq!(check.status, HealthStatus::Fail);
+        assert_eq!(
+            check.packages,
+            vec!["embeddenator-test1", "embeddenator-test2"]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_workspace_members_check() {
+        let temp_dir = create_test_workspace();
+        let checker = HealthChecker::new(temp_dir.path());
+
+        // Without a root [workspace] the check is skipped
+        let report = checker
+            .check_selected(&[HealthCheckType::Members], false)
+            .await
+            .unwrap();
+        assert_eq!(report.checks[0].status, HealthStatus::Pass);
+
+        fs::write(
+            temp_dir.path().join("Cargo.toml"),
+            "[workspace]\nmembers = [\"embeddenator-test*\"]\n",
+        )
+        .unwrap();
+        let report = checker
+            .check_selected(&[HealthCheckType::Members], false)
+            .await
+            .unwrap();
+        assert_eq!(report.checks[0].status, HealthStatus::Pass);
+
+        fs::write(
+            temp_dir.path().join("Cargo.toml"),
+            "[workspace]\nmembers = [\"embeddenator-test1\", \"embeddenator-gone\"]\n",
+        )
+        .unwrap();
+        let report = checker
+            .check_selected(&[HealthCheckType::Members], false)
+            .await
+            .unwrap();
+        let check = &report.checks[0];
+        assert_eq!(check.status, HealthStatus::Fail);
+        assert!(check
+            .details
+            .iter()
+            .any(|d| d.contains("embeddenator-test2: not listed")));
+        assert!(check
+            .details
+            .iter()
+            .any(|d| d.contains("embeddenator-gone: workspace member missing")));
+    }
+
+    #[tokio::test]
+    async fn test_workspace_members_check_uses_scan_depth() {
+        let temp_dir = create_test_workspace();
+        fs::write(
+            temp_dir.path().join("Cargo.toml"),
+            "[workspace]\nmembers = [\"embeddenator-test*\"]\n",
+        )
+        .unwrap();
+        let deep = temp_dir.path().join("a/b/embeddenator-deep");
+        fs::create_dir_all(&deep).unwrap();
+        fs::write(
+            deep.join("Cargo.toml"),
+            "[package]\nname = \"embeddenator-deep\"\nversion = \"0.20.0\"\n",
+        )
+        .unwrap();
+
+        let report = HealthChecker::new(temp_dir.path())
+            .check_selected(&[HealthCheckType::Members], false)
+            .await
+            .unwrap();
+        assert_eq!(report.checks[0].status, HealthStatus::Pass);
+
+        let report = HealthChecker::new(temp_dir.path())
+            .with_max_depth(4)
+            .check_selected(&[HealthCheckType::Members], false)
+            .await
+            .unwrap();
+        let check = &report.checks[0];
+        assert_eq!(check.status, HealthStatus::Warn);
+        assert_eq!(
+            check.details,
+            vec!["a/b/embeddenator-deep: not listed in workspace members"]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_max_depth_finds_nested_repos() {
+        let temp_dir = TempDir::new().unwrap();
+        let deep = temp_dir.path().join("a/b/embeddenator-deep");
+        fs::create_dir_all(&deep).unwrap();
+        let repo = git2::Repository::init(&deep).unwrap();
+        commit_file(&repo, "README.md", "deep");
+        fs::write(
+            deep.join("Cargo.toml"),
+            "[package]\nname = \"embeddenator-deep\"\nversion = \"0.20.0\"\n",
+        )
+        .unwrap();
+
+        let report = HealthChecker::new(temp_dir.path())
+            .check_selected(&[HealthCheckType::Git], false)
+            .await
+            .unwrap();
+        assert_eq!(report.checks[0].status, HealthStatus::Pass);
+
+        let report = HealthChecker::new(temp_dir.path())
+            .with_max_depth(4)
+            .check_selected(&[HealthCheckType::Git], false)
+            .await
+            .unwrap();
+        let check = &report.checks[0];
+        assert_eq!(check.status, HealthStatus::Fail);
+        assert!(check
+            .details
+            .iter()
+            .any(|d| d.contains("embeddenator-deep")));
+    }
+
+    #[tokio::test]
+    async fn test_default_members_scope() {
+        let temp_dir = create_test_workspace();
+        fs::write(
+            temp_dir.path().join("Cargo.toml"),
+            "[workspace]\nmembers = [\"embeddenator-*\"]\ndefault-members = [\"embeddenator-test1\"]\n",
+        )
+        .unwrap();
+
+        let checker = HealthChecker::new(temp_dir.path()).with_default_members(true);
+        let report = checker
+            .check_selected(&[HealthCheckType::Specs], false)
+            .await
+            .unwrap();
+
+        // Only embeddenator-test1 (which has specs) is in scope
+        assert_eq!(report.checks[0].status, HealthStatus::Pass);
+        assert!(report.checks[0].message.contains("1/1"));
+    }
+
+    #[tokio::test]
+    async fn test_json_output_is_stable() {
+        let temp_dir = create_test_workspace();
+        let checker = HealthChecker::new(temp_dir.path());
+
+        let mut forward = checker
+            .check_selected(&[HealthCheckType::Version, HealthCheckType::Specs], false)
+            .await
+            .unwrap();
+        let mut reverse = checker
+            .check_selected(&[HealthCheckType::Specs, HealthCheckType::Version], false)
+            .await
+            .unwrap();
+        for check in forward.checks.iter_mut().chain(reverse.checks.iter_mut()) {
+            check.duration_ms = 0;
+        }
+
+        // Checks are ordered by type regardless of request order
+        let types: Vec<_> = forward.checks.iter().map(|c| c.check_type).collect();
+        assert_eq!(
+            types,
+            vec![HealthCheckType::Version, HealthCheckType::Specs]
+        );
+
+        assert_eq!(
+            serde_json::to_string(&forward.checks).unwrap(),
+            serde_json::to_string(&reverse.checks).unwrap()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_msrv_check() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        fs::write(
+            root.join("Cargo.toml"),
+            "[workspace]\nmembers = [\"*\"]\n\n[workspace.package]\nrust-version = \"1.70\"\n",
+        )
+        .unwrap();
+
+        let write_package = |name: &str, msrv: &str| {
+            let pkg = root.join(name);
+            fs::create_dir_all(&pkg).unwrap();
+            fs::write(
+                pkg.join("Cargo.toml"),
+                format!(
+                    "[package]\nname = \"{}\"\nversion = \"0.1.0\"\n{}\n",
+                    name, msrv
+                ),
+            )
+            .unwrap();
+        };
+        write_package("embeddenator-a", "rust-version = \"1.70\"");
+        write_package("embeddenator-b", "rust-version.workspace = true");
+
+        let checker = HealthChecker::new(root);
+        let report = checker
+            .check_selected(&[HealthCheckType::Msrv], false)
+            .await
+            .unwrap();
+        assert_eq!(report.checks[0].status, HealthStatus::Pass);
+        assert_eq!(report.checks[0].message, "All 2 packages require Rust 1.70");
+
+        write_package("embeddenator-c", "rust-version = \"1.75\"");
+        write_package("embeddenator-d", "");
+        let report = checker
+            .check_selected(&[HealthCheckType::Msrv], false)
+            .await
+            .unwrap();
+        let check = &report.checks[0];
+        assert_eq!(check.status, HealthStatus::Warn);
+        assert!(check.message.contains("2 different rust-version values"));
+        assert!(check.details.contains(&"embeddenator-b: 1.70".to_string()));
+        assert!(check.details.contains(&"embeddenator-c: 1.75".to_string()));
+        assert!(check
+            .details
+            .contains(&"embeddenator-d: no rust-version".to_string()));
+        assert_eq!(check.packages, vec!["embeddenator-c", "embeddenator-d"]);
+    }
+
+    #[tokio::test]
+    async fn test_checks_use_package_prefix() {
+        let temp_dir = create_test_workspace();
+        let pkg = temp_dir.path().join("myorg-core");
+        fs::create_dir_all(pkg.join("specs")).unwrap();
+        fs::write(
+            pkg.join("Cargo.toml"),
+            "[package]\nname = \"myorg-core\"\nversion = \"0.1.0\"\n",
+        )
+        .unwrap();
+        fs::write(pkg.join("specs/design.md"), "# Design").unwrap();
+
+        let report = HealthChecker::new(temp_dir.path())
+            .with_prefix("myorg-")
+            .check_selected(&[HealthCheckType::Specs], false)
+            .await
+            .unwrap();
+
+        let coverage = report.checks[0].spec_coverage.as_ref().unwrap();
+        assert_eq!(coverage.keys().collect::<Vec<_>>(), vec!["myorg-core"]);
+    }
+
+    #[tokio::test]
+    async fn test_check_timeout_reports_failure() {
+        let temp_dir = create_test_workspace();
+        let checker = HealthChecker::new(temp_dir.path()).with_timeout(Duration::from_millis(1));
+
+        // Running cargo test takes far longer than the timeout
+        let report = checker
+            .check_selected(&[HealthCheckType::Tests], false)
+            .await
+            .unwrap();
+
+        assert_eq!(report.checks.len(), 1);
+        assert_eq!(report.checks[0].status, HealthStatus::Fail);
+        assert_eq!(report.checks[0].message, "tests check timed out after 1ms");
+        assert!(report.checks[0].duration_ms >= 1);
+        assert_eq!(report.overall_status, HealthStatus::Fail);
+    }
+
+    #[tokio::test]
+    async fn test_max_concurrency_does_not_change_results() {
+        let temp_dir = create_test_workspace();
+        let checks = [
+            HealthCheckType::Specs,
+            HealthCheckType::Members,
+            HealthCheckType::Version,
+        ];
+
+        let mut parallel = HealthChecker::new(temp_dir.path())
+            .check_selected(&checks, false)
+            .await
+            .unwrap();
+        let mut serial = HealthChecker::new(temp_dir.path())
+            .with_max_concurrency(1)
+            .check_selected(&checks, false)
+            .await
+            .unwrap();
+
+        // Timings naturally differ between runs
+        for check in parallel.checks.iter_mut().chain(serial.checks.iter_mut()) {
+            check.duration_ms = 0;
+        }
+        assert_eq!(
+            serde_json::to_string(&parallel.checks).unwrap(),
+            serde_json::to_string(&serial.checks).unwrap()
+        );
+        assert_eq!(parallel.overall_status, serial.overall_status);
+    }
+
     #[test]
     fn test_health_check_type_from_str() {
         assert_eq!("git".parse::<HealthCheckType>(), Ok(HealthCheckType::Git));
@@ -172,6 +884,11 @@ edition = "2021"
             "version".parse::<HealthCheckType>(),
             Ok(HealthCheckType::Version)
         );
+        assert_eq!("msrv".parse::<HealthCheckType>(), Ok(HealthCheckType::Msrv));
+        assert_eq!(
+            "build".parse::<HealthCheckType>(),
+            Ok(HealthCheckType::Build)
+        );
         assert_eq!(
             "tests".parse::<HealthCheckType>(),
             Ok(HealthCheckType::Tests)
@@ -181,9 +898,748 @@ edition = "2021"
             "specs".parse::<HealthCheckType>(),
             Ok(HealthCheckType::Specs)
         );
+        assert_eq!(
+            "yanked".parse::<HealthCheckType>(),
+            Ok(HealthCheckType::Yanked)
+        );
+        assert_eq!(
+            "members".parse::<HealthCheckType>(),
+            Ok(HealthCheckType::Members)
+        );
+        assert_eq!(
+            "cycles".parse::<HealthCheckType>(),
+            Ok(HealthCheckType::Cycles)
+        );
+        assert_eq!(
+            "clippy".parse::<HealthCheckType>(),
+            Ok(HealthCheckType::Clippy)
+        );
+        assert_eq!("fmt".parse::<HealthCheckType>(), Ok(HealthCheckType::Fmt));
+        assert_eq!(
+            "audit".parse::<HealthCheckType>(),
+            Ok(HealthCheckType::Audit)
+        );
         assert!("invalid".parse::<HealthCheckType>().is_err());
     }
 
+    #[test]
+    fn test_parse_yanked_warnings() {
+        let stderr = "\
+    Updating crates.io index
+warning: package `foo v1.2.3` in Cargo.lock is yanked in registry `crates-io`, consider updating to a version that is not yanked
+warning: package `bar v0.1.0` in Cargo.lock is yanked in registry `crates-io`, consider updating to a version that is not yanked
+warning: package `foo v1.2.3` in Cargo.lock is yanked in registry `crates-io`, consider updating to a version that is not yanked
+";
+
+        let found = HealthChecker::parse_yanked_warnings(stderr);
+        assert_eq!(found, vec!["bar v0.1.0", "foo v1.2.3"]);
+
+        assert!(HealthChecker::parse_yanked_warnings("    Finished dev").is_empty());
+    }
+
+    #[test]
+    fn test_registry_packages() {
+        let metadata = r#"{"packages": [
+            {"name": "serde", "version": "1.0.200", "source": "registry+https://github.com/rust-lang/crates.io-index"},
+            {"name": "embeddenator-vsa", "version": "0.20.0", "source": null},
+            {"name": "anyhow", "version": "1.0.80", "source": "sparse+https://index.crates.io/"},
+            {"name": "fork", "version": "0.1.0", "source": "git+https://github.com/tzervas/fork#abc"}
+        ]}"#;
+
+        assert_eq!(
+            HealthChecker::registry_packages(metadata),
+            vec!["anyhow v1.0.80", "serde v1.0.200"]
+        );
+        assert!(HealthChecker::registry_packages("not json").is_empty());
+    }
+
+    #[test]
+    fn test_unknown_yank_status() {
+        assert_eq!(HealthChecker::index_cache_path("a"), Path::new("1/a"));
+        assert_eq!(HealthChecker::index_cache_path("cc"), Path::new("2/cc"));
+        assert_eq!(HealthChecker::index_cache_path("Syn"), Path::new("3/s/syn"));
+        assert_eq!(
+            HealthChecker::index_cache_path("serde"),
+            Path::new("se/rd/serde")
+        );
+
+        let temp_dir = TempDir::new().unwrap();
+        let cache = temp_dir
+            .path()
+            .join("registry/index/index.crates.io-1949cf8c6b5b557f/.cache");
+        fs::create_dir_all(cache.join("se/rd")).unwrap();
+        fs::write(cache.join("se/rd/serde"), "").unwrap();
+        fs::create_dir_all(cache.join("an/yh")).unwrap();
+        let stale = fs::File::create(cache.join("an/yh/anyhow")).unwrap();
+        stale
+            .set_modified(std::time::SystemTime::now() - Duration::from_secs(30 * 24 * 60 * 60))
+            .unwrap();
+
+        let packages = vec![
+            "anyhow v1.0.80".to_string(),
+            "rand v0.8.5".to_string(),
+            "serde v1.0.200".to_string(),
+        ];
+        assert_eq!(
+            HealthChecker::unknown_yank_status(temp_dir.path(), &packages, INDEX_CACHE_MAX_AGE),
+            vec!["anyhow v1.0.80", "rand v0.8.5"]
+        );
+
+        // Without any index cache nothing can be verified
+        let empty = TempDir::new().unwrap();
+        assert_eq!(
+            HealthChecker::unknown_yank_status(empty.path(), &packages, INDEX_CACHE_MAX_AGE),
+            packages
+        );
+    }
+
+    #[test]
+    fn test_parse_clippy_counts() {
+        let stderr = "\
+    Checking embeddenator-vsa v0.20.0
+error: this `if` has identical blocks
+  --> src/lib.rs:10:5
+   |
+   = help: for further information visit https://rust-lang.github.io/rust-clippy/master/index.html#if_same_then_else
+   = note: `-D clippy::if-same-then-else` implied by `-D warnings`
+error: unused variable: `x`
+  --> src/lib.rs:20:9
+   |
+   = note: `-D unused-variables` implied by `-D warnings`
+error[E0425]: cannot find value `y` in this scope
+  --> src/lib.rs:30:5
+warning: `embeddenator-vsa` (lib) generated 1 warning
+error: could not compile `embeddenator-vsa` (lib) due to 3 previous errors
+";
+
+        assert_eq!(HealthChecker::parse_clippy_counts(stderr), (2, 1));
+        assert_eq!(
+            HealthChecker::parse_clippy_counts("    Finished dev [unoptimized] target(s)"),
+            (0, 0)
+        );
+    }
+
+    #[test]
+    fn test_parse_fmt_diff_files() {
+        let stdout = "\
+Diff in /ws/embeddenator-vsa/src/lib.rs:1:
+-pub fn a( ) {}
++pub fn a() {}
+Diff in /ws/embeddenator-vsa/src/lib.rs:12:
+Diff in /ws/embeddenator-vsa/src/ops.rs at line 4:
+";
+
+        assert_eq!(
+            HealthChecker::parse_fmt_diff_files(stdout),
+            vec![
+                "/ws/embeddenator-vsa/src/lib.rs",
+                "/ws/embeddenator-vsa/src/ops.rs"
+            ]
+        );
+        assert!(HealthChecker::parse_fmt_diff_files("").is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_fmt_check_passes_without_rust_files() {
+        let temp_dir = TempDir::new().unwrap();
+        let pkg = temp_dir.path().join("embeddenator-empty");
+        fs::create_dir_all(&pkg).unwrap();
+        fs::write(
+            pkg.join("Cargo.toml"),
+            "[package]\nname = \"embeddenator-empty\"\nversion = \"0.1.0\"\n",
+        )
+        .unwrap();
+
+        let checker = HealthChecker::new(temp_dir.path());
+        let report = checker
+            .check_selected(&[HealthCheckType::Fmt], false)
+            .await
+            .unwrap();
+
+        assert_eq!(report.checks.len(), 1);
+        assert_eq!(report.checks[0].status, HealthStatus::Pass);
+        assert!(report.checks[0].packages.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_build_check_reports_compile_errors() {
+        let temp_dir = TempDir::new().unwrap();
+        for (name, source) in [
+            ("embeddenator-good", "pub fn ok() {}\n"),
+            ("embeddenator-bad", "pub fn broken() -> u32 { \"nope\" }\n"),
+        ] {
+            let pkg = temp_dir.path().join(name);
+            fs::create_dir_all(pkg.join("src")).unwrap();
+            fs::write(
+                pkg.join("Cargo.toml"),
+                format!(
+                    "[package]\nname = \"{}\"\nversion = \"0.1.0\"\nedition = \"2021\"\n\n[workspace]\n",
+                    name
+                ),
+            )
+            .unwrap();
+            fs::write(pkg.join("src/lib.rs"), source).unwrap();
+        }
+
+        let report = HealthChecker::new(temp_dir.path())
+            .check_selected(&[HealthCheckType::Build], false)
+            .await
+            .unwrap();
+
+        let check = &report.checks[0];
+        assert_eq!(check.status, HealthStatus::Fail);
+        assert_eq!(check.packages, vec!["embeddenator-bad"]);
+        assert_eq!(check.details[0], "embeddenator-bad: failed to build");
+        assert!(check.details[1].contains("error[E0308]"));
+        assert!(check.message.contains("1 compiled, 1 failed"));
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_cargo_path_and_test_args() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let temp_dir = create_test_workspace();
+        let log = temp_dir.path().join("args.log");
+        let cargo = temp_dir.path().join("fake-cargo");
+        fs::write(
+            &cargo,
+            format!(
+                "#!/bin/sh\necho \"$@\" >> {}\necho 'test result: ok. 1 passed; 0 failed; 0 ignored; 0 measured; 0 filtered out'\n",
+                log.display()
+            ),
+        )
+        .unwrap();
+        fs::set_permissions(&cargo, fs::Permissions::from_mode(0o755)).unwrap();
+
+        let report = HealthChecker::new(temp_dir.path())
+            .with_cargo_path(&cargo)
+            .with_test_args(vec!["--no-default-features".to_string()])
+            .check_selected(&[HealthCheckType::Tests], false)
+            .await
+            .unwrap();
+
+        assert_eq!(report.checks[0].status, HealthStatus::Pass);
+        assert_eq!(
+            report.checks[0].message,
+            "Tests: 2 passed, 0 failed across 2 packages"
+        );
+        let calls = fs::read_to_string(&log).unwrap();
+        assert_eq!(calls.lines().count(), 2);
+        assert!(calls.lines().all(|l| l.starts_with("test ")
+            && l.contains("--no-default-features")
+            && !l.contains("--all-features")));
+    }
+
+    #[tokio::test]
+    async fn test_missing_cargo_path_fails_checks() {
+        let temp_dir = create_test_workspace();
+        let report = HealthChecker::new(temp_dir.path())
+            .with_cargo_path(temp_dir.path().join("no-such-cargo"))
+            .check_selected(&[HealthCheckType::Build], false)
+            .await
+            .unwrap();
+
+        assert_eq!(report.checks[0].status, HealthStatus::Fail);
+        assert!(report.checks[0].details[0]
+            .starts_with("embeddenator-test1: failed to run cargo check"));
+    }
+
+    #[tokio::test]
+    async fn test_print_terminal_to_writer() {
+        let temp_dir = create_test_workspace();
+        let report = HealthChecker::new(temp_dir.path())
+            .check_selected(&[HealthCheckType::Members], false)
+            .await
+            .unwrap();
+
+        let mut out = Vec::new();
+        report.print_terminal_to(&mut out, false).unwrap();
+        let out = String::from_utf8(out).unwrap();
+        assert!(out.contains("Workspace Health Report"));
+        assert!(out.contains(&format!(
+            "({:.1}s)",
+            report.checks[0].duration_ms as f64 / 1000.0
+        )));
+        assert!(out.contains(&report.checks[0].message));
+    }
+
+    #[tokio::test]
+    async fn test_print_checklist_suggests_fixes() {
+        let temp_dir = create_test_workspace();
+        let checker = HealthChecker::new(temp_dir.path());
+        let checks = [HealthCheckType::Version, HealthCheckType::Members];
+
+        let report = checker.check_selected(&checks, false).await.unwrap();
+        assert!(report.checks.iter().all(|c| c.remediation().is_none()));
+        let mut out = Vec::new();
+        report.print_checklist_to(&mut out).unwrap();
+        assert!(String::from_utf8(out)
+            .unwrap()
+            .contains("No problems found"));
+
+        fs::write(
+            temp_dir.path().join("Cargo.toml"),
+            "[workspace]\nmembers = [\"embeddenator-test1\", \"embeddenator-gone\"]\n",
+        )
+        .unwrap();
+        let report = checker.check_selected(&checks, false).await.unwrap();
+        let members = report
+            .checks
+            .iter()
+            .find(|c| c.check_type == HealthCheckType::Members)
+            .unwrap();
+        assert_eq!(members.status, HealthStatus::Fail);
+        let fix = members.remediation().unwrap();
+        assert!(fix.contains("[workspace] members"));
+
+        let mut out = Vec::new();
+        report.print_checklist_to(&mut out).unwrap();
+        let out = String::from_utf8(out).unwrap();
+        assert!(out.contains("1 problem(s) to fix"));
+        assert!(out.contains(&members.message));
+        assert!(out.contains(fix));
+        assert!(!out.contains("consistent versions"));
+    }
+
+    #[test]
+    fn test_report_diff() {
+        use crate::health::{HealthCheckResult, HealthReport};
+        use crate::StatusChange;
+
+        let result = |check_type, status, details: &[&str]| HealthCheckResult {
+            check_type,
+            status,
+            message: String::new(),
+            details: details.iter().map(|d| d.to_string()).collect(),
+            spec_coverage: None,
+            doc_coverage: None,
+            test_counts: None,
+            duration_ms: 0,
+            packages: Vec::new(),
+        };
+        let report = |timestamp: &str, checks| HealthReport {
+            timestamp: timestamp.to_string(),
+            workspace_root: "/ws".into(),
+            checks,
+            overall_status: HealthStatus::Pass,
+            skipped: Vec::new(),
+            weights: Default::default(),
+            score: 0,
+        };
+
+        let previous = report(
+            "yesterday",
+            vec![
+                result(HealthCheckType::Git, HealthStatus::Fail, &["vsa: dirty"]),
+                result(HealthCheckType::Fmt, HealthStatus::Pass, &[]),
+                result(
+                    HealthCheckType::Docs,
+                    HealthStatus::Warn,
+                    &["fs: 2 warnings"],
+                ),
+            ],
+        );
+        let current = report(
+            "today",
+            vec![
+                result(HealthCheckType::Git, HealthStatus::Warn, &[]),
+                result(
+                    HealthCheckType::Fmt,
+                    HealthStatus::Fail,
+                    &["io: src/lib.rs"],
+                ),
+                result(
+                    HealthCheckType::Docs,
+                    HealthStatus::Warn,
+                    &["fs: 2 warnings"],
+                ),
+                result(HealthCheckType::Build, HealthStatus::Pass, &[]),
+            ],
+        );
+
+        let diff = current.diff(&previous);
+        assert_eq!(diff.previous_timestamp, "yesterday");
+        let changes: Vec<_> = diff.checks.iter().map(|c| c.change).collect();
+        assert_eq!(
+            changes,
+            vec![
+                StatusChange::Improved,
+                StatusChange::Regressed,
+                StatusChange::Unchanged,
+                StatusChange::Unchanged,
+            ]
+        );
+        assert_eq!(diff.checks[0].removed_details, vec!["vsa: dirty"]);
+        assert_eq!(diff.checks[1].added_details, vec!["io: src/lib.rs"]);
+        assert!(diff.checks[2].added_details.is_empty());
+        assert_eq!(diff.checks[3].previous, None);
+        assert!(diff.has_regressions());
+        assert!(!previous.diff(&previous).has_regressions());
+    }
+
+    #[test]
+    fn test_lists_nightly() {
+        let toolchains = "stable-x86_64-unknown-linux-gnu (default)\n\
+                          nightly-2026-01-01-x86_64-unknown-linux-gnu\n";
+        assert!(HealthChecker::lists_nightly(toolchains));
+        assert!(!HealthChecker::lists_nightly(
+            "stable-x86_64-unknown-linux-gnu (default)\n"
+        ));
+        assert!(!HealthChecker::lists_nightly(""));
+    }
+
+    #[test]
+    fn test_parse_doc_coverage() {
+        let json = r#"{"src/lib.rs":{"total":5,"with_docs":2,"total_examples":2,"with_examples":0},"src/io.rs":{"total":3,"with_docs":3,"total_examples":1,"with_examples":1}}"#;
+        let coverage = HealthChecker::parse_doc_coverage(json).unwrap();
+        assert_eq!(coverage.documented, 5);
+        assert_eq!(coverage.total, 8);
+        assert!((coverage.percent - 62.5).abs() < f64::EPSILON);
+
+        let empty = HealthChecker::parse_doc_coverage("{}").unwrap();
+        assert_eq!(empty.total, 0);
+        assert_eq!(empty.percent, 100.0);
+
+        assert!(HealthChecker::parse_doc_coverage("error: nightly only").is_err());
+    }
+
+    #[test]
+    fn test_parse_test_counts() {
+        let stdout = "\
+running 3 tests
+...
+test result: ok. 3 passed; 0 failed; 1 ignored; 0 measured; 0 filtered out; finished in 0.01s
+
+running 2 tests
+.F
+failures:
+    tests::broken
+test result: FAILED. 1 passed; 1 failed; 0 ignored; 0 measured; 0 filtered out; finished in 0.00s
+
+running 0 tests
+test result: ok. 0 passed; 0 failed; 0 ignored; 0 measured; 0 filtered out; finished in 0.00s
+";
+        assert_eq!(
+            HealthChecker::parse_test_counts(stdout),
+            TestCounts {
+                passed: 4,
+                failed: 1,
+                ignored: 1,
+            }
+        );
+        assert_eq!(HealthChecker::parse_test_counts(""), TestCounts::default());
+    }
+
+    #[test]
+    fn test_report_score() {
+        use crate::health::{HealthCheckResult, HealthReport};
+
+        let result = |check_type, status| HealthCheckResult {
+            check_type,
+            status,
+            message: String::new(),
+            details: Vec::new(),
+            spec_coverage: None,
+            doc_coverage: None,
+            test_counts: None,
+            duration_ms: 0,
+            packages: Vec::new(),
+        };
+        let mut report = HealthReport {
+            timestamp: String::new(),
+            workspace_root: "/ws".into(),
+            checks: vec![
+                result(HealthCheckType::Tests, HealthStatus::Pass),
+                result(HealthCheckType::Specs, HealthStatus::Warn),
+                result(HealthCheckType::Git, HealthStatus::Fail),
+            ],
+            overall_status: HealthStatus::Fail,
+            skipped: Vec::new(),
+            weights: Default::default(),
+            score: 0,
+        };
+
+        // (3 + 0.5 * 1 + 0) / (3 + 1 + 2)
+        assert_eq!(report.score(), 58);
+
+        report.weights.insert(HealthCheckType::Git, 0);
+        assert_eq!(report.score(), 88);
+
+        report.checks.clear();
+        assert_eq!(report.score(), 100);
+    }
+
+    #[tokio::test]
+    async fn test_with_weights() {
+        let temp_dir = create_test_workspace();
+        let checks = [HealthCheckType::Specs, HealthCheckType::Members];
+
+        let report = HealthChecker::new(temp_dir.path())
+            .check_selected(&checks, false)
+            .await
+            .unwrap();
+        assert_eq!(report.score, report.score());
+        assert!(report.weights.is_empty());
+
+        let weighted = HealthChecker::new(temp_dir.path())
+            .with_weights(&[
+                (HealthCheckType::Specs, 0),
+                (
+                    HealthCheckType::Members,
+                    HealthCheckType::Members.default_weight(),
+                ),
+            ])
+            .check_selected(&checks, false)
+            .await
+            .unwrap();
+        assert_eq!(
+            weighted.weights.into_iter().collect::<Vec<_>>(),
+            vec![(HealthCheckType::Specs, 0)]
+        );
+        assert_eq!(weighted.checks[0].check_type, HealthCheckType::Specs);
+        assert_eq!(weighted.checks[0].status, HealthStatus::Warn);
+        assert!(weighted.score > report.score);
+    }
+
+    #[test]
+    fn test_parse_audit_advisories() {
+        let json = r#"{
+            "vulnerabilities": {
+                "found": true,
+                "count": 1,
+                "list": [{
+                    "advisory": {"id": "RUSTSEC-2023-0001", "package": "foo", "title": "Memory corruption"},
+                    "package": {"name": "foo", "version": "1.2.3"}
+                }]
+            },
+            "warnings": {}
+        }"#;
+
+        assert_eq!(
+            HealthChecker::parse_audit_advisories(json).unwrap(),
+            vec!["RUSTSEC-2023-0001: foo 1.2.3 (Memory corruption)"]
+        );
+
+        let clean = r#"{"vulnerabilities": {"found": false, "count": 0, "list": []}}"#;
+        assert!(HealthChecker::parse_audit_advisories(clean)
+            .unwrap()
+            .is_empty());
+
+        assert!(HealthChecker::parse_audit_advisories("").is_err());
+    }
+
+    #[test]
+    fn test_compile_errors() {
+        let stderr = "\
+   Compiling embeddenator-vsa v0.20.0
+error[E0433]: failed to resolve: could not find `arch` in `std`
+warning: unused import
+error: could not compile `embeddenator-vsa` (lib) due to 1 previous error
+";
+
+        let errors = HealthChecker::compile_errors(stderr);
+        assert_eq!(errors.len(), 2);
+        assert!(errors[0].starts_with("error[E0433]"));
+
+        assert!(HealthChecker::compile_errors("error: missing documentation").is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_worst_status() {
+        let temp_dir = create_test_workspace();
+        let mut report = HealthChecker::new(temp_dir.path())
+            .check_selected(&[HealthCheckType::Version, HealthCheckType::Specs], false)
+            .await
+            .unwrap();
+
+        report.checks[0].status = HealthStatus::Pass;
+        report.checks[1].status = HealthStatus::Pass;
+        assert_eq!(report.worst_status(), HealthStatus::Pass);
+
+        report.checks[1].status = HealthStatus::Warn;
+        assert_eq!(report.worst_status(), HealthStatus::Warn);
+        assert!(!report.has_failures());
+
+        report.checks[0].status = HealthStatus::Fail;
+        assert_eq!(report.worst_status(), HealthStatus::Fail);
+
+        report.checks.clear();
+        assert_eq!(report.worst_status(), HealthStatus::Pass);
+    }
+
+    #[test]
+    fn test_health_status_order_and_parse() {
+        assert!(HealthStatus::Pass < HealthStatus::Warn);
+        assert!(HealthStatus::Warn < HealthStatus::Fail);
+        assert_eq!("warn".parse::<HealthStatus>(), Ok(HealthStatus::Warn));
+        assert_eq!("FAIL".parse::<HealthStatus>(), Ok(HealthStatus::Fail));
+        assert!("error".parse::<HealthStatus>().is_err());
+    }
+
+    #[test]
+    fn test_git_status_categories() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        let repo = git2::Repository::init(root).unwrap();
+
+        fs::write(root.join("committed.txt"), "v1").unwrap();
+        let mut index = repo.index().unwrap();
+        index.add_path(Path::new("committed.txt")).unwrap();
+        let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+        let sig = git2::Signature::now("Test", "test@example.com").unwrap();
+        repo.commit(Some("HEAD"), &sig, &sig, "init", &tree, &[])
+            .unwrap();
+
+        fs::write(root.join("committed.txt"), "v2").unwrap();
+        fs::write(root.join("staged.txt"), "new").unwrap();
+        index.add_path(Path::new("staged.txt")).unwrap();
+        index.write().unwrap();
+        fs::write(root.join("untracked.txt"), "?").unwrap();
+
+        let status = HealthChecker::get_git_status_static(root).unwrap();
+        assert!(status.is_dirty);
+        assert_eq!(status.dirty_files.len(), 3);
+        assert_eq!(status.staged, vec!["staged.txt"]);
+        assert_eq!(status.modified, vec!["committed.txt"]);
+        assert_eq!(status.untracked, vec!["untracked.txt"]);
+        assert_eq!(status.change_summary(), "1 staged, 1 modified, 1 untracked");
+        assert_eq!(status.stash_count, 0);
+    }
+
+    #[test]
+    fn test_git_status_ignores_ignored_files() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        let repo = git2::Repository::init(root).unwrap();
+
+        fs::write(root.join(".gitignore"), "target/\n*.log\n").unwrap();
+        let mut index = repo.index().unwrap();
+        index.add_path(Path::new(".gitignore")).unwrap();
+        index.write().unwrap();
+        let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+        let sig = git2::Signature::now("Test", "test@example.com").unwrap();
+        repo.commit(Some("HEAD"), &sig, &sig, "init", &tree, &[])
+            .unwrap();
+
+        fs::create_dir_all(root.join("target/debug")).unwrap();
+        fs::write(root.join("target/debug/app"), "bin").unwrap();
+        fs::write(root.join("build.log"), "log").unwrap();
+
+        let status = HealthChecker::get_git_status_static(root).unwrap();
+        assert!(!status.is_dirty);
+        assert!(status.dirty_files.is_empty());
+        assert!(status.untracked.is_empty());
+    }
+
+    fn commit_file(repo: &git2::Repository, name: &str, content: &str) {
+        let workdir = repo.workdir().unwrap();
+        fs::write(workdir.join(name), content).unwrap();
+        let mut index = repo.index().unwrap();
+        index.add_path(Path::new(name)).unwrap();
+        index.write().unwrap();
+        let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+        let sig = git2::Signature::now("Test", "test@example.com").unwrap();
+        let parent = repo.head().ok().and_then(|h| h.peel_to_commit().ok());
+        let parents: Vec<&git2::Commit> = parent.iter().collect();
+        repo.commit(Some("HEAD"), &sig, &sig, content, &tree, &parents)
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_git_check_fetch_updates_behind() {
+        let temp_dir = TempDir::new().unwrap();
+        let origin_path = temp_dir.path().join("origin");
+        let origin = git2::Repository::init(&origin_path).unwrap();
+        commit_file(&origin, "lib.rs", "v1");
+
+        let workspace = temp_dir.path().join("ws");
+        let clone_path = workspace.join("embeddenator-vsa");
+        git2::Repository::clone(origin_path.to_str().unwrap(), &clone_path).unwrap();
+        commit_file(&origin, "lib.rs", "v2");
+
+        let status = HealthChecker::get_git_status_static(&clone_path).unwrap();
+        assert_eq!(status.behind, 0);
+
+        let report = HealthChecker::new(&workspace)
+            .with_fetch(true)
+            .check_selected(&[HealthCheckType::Git], false)
+            .await
+            .unwrap();
+        let check = &report.checks[0];
+        assert_eq!(check.status, HealthStatus::Warn);
+        assert!(check
+            .details
+            .iter()
+            .any(|d| d.contains("0 ahead, 1 behind upstream")));
+    }
+
+    #[tokio::test]
+    async fn test_git_check_fetch_failure_falls_back() {
+        let temp_dir = TempDir::new().unwrap();
+        let origin_path = temp_dir.path().join("origin");
+        let origin = git2::Repository::init(&origin_path).unwrap();
+        commit_file(&origin, "lib.rs", "v1");
+
+        let workspace = temp_dir.path().join("ws");
+        let clone_path = workspace.join("embeddenator-vsa");
+        let clone = git2::Repository::clone(origin_path.to_str().unwrap(), &clone_path).unwrap();
+        clone
+            .remote_set_url("origin", temp_dir.path().join("missing").to_str().unwrap())
+            .unwrap();
+
+        let report = HealthChecker::new(&workspace)
+            .with_fetch(true)
+            .check_selected(&[HealthCheckType::Git], false)
+            .await
+            .unwrap();
+        let check = &report.checks[0];
+        assert_eq!(check.status, HealthStatus::Warn);
+        assert!(check
+            .details
+            .iter()
+            .any(|d| d.starts_with("embeddenator-vsa: fetch failed")));
+    }
+
+    #[tokio::test]
+    async fn test_git_check_warns_on_stash() {
+        let temp_dir = TempDir::new().unwrap();
+        let pkg = temp_dir.path().join("embeddenator-vsa");
+        fs::create_dir_all(&pkg).unwrap();
+        let mut repo = git2::Repository::init(&pkg).unwrap();
+        let sig = git2::Signature::now("Test", "test@example.com").unwrap();
+
+        fs::write(pkg.join("lib.rs"), "v1").unwrap();
+        let mut index = repo.index().unwrap();
+        index.add_path(Path::new("lib.rs")).unwrap();
+        index.write().unwrap();
+        let tree_id = index.write_tree().unwrap();
+        {
+            let tree = repo.find_tree(tree_id).unwrap();
+            repo.commit(Some("HEAD"), &sig, &sig, "init", &tree, &[])
+                .unwrap();
+        }
+
+        for content in ["v2", "v3"] {
+            fs::write(pkg.join("lib.rs"), content).unwrap();
+            repo.stash_save(&sig, "wip", None).unwrap();
+        }
+
+        let status = HealthChecker::get_git_status_static(&pkg).unwrap();
+        assert_eq!(status.stash_count, 2);
+        assert!(!status.is_dirty);
+
+        let report = HealthChecker::new(temp_dir.path())
+            .check_selected(&[HealthCheckType::Git], false)
+            .await
+            .unwrap();
+        let check = &report.checks[0];
+        assert_eq!(check.status, HealthStatus::Warn);
+        assert!(check
+            .details
+            .contains(&"embeddenator-vsa: 2 stash(es) present".to_string()));
+    }
+
     #[test]
     fn test_health_status_is_critical() {
         assert!(!HealthStatus::Pass.is_critical());
@@ -4,7 +4,9 @@
 //! and synchronization across the embeddenator workspace.
 
 pub mod cargo;
+pub mod graph;
 pub mod health;
+pub mod owners;
 pub mod patch;
 pub mod version;
 pub mod workspace;
@@ -12,8 +14,20 @@ pub mod workspace;
 #[cfg(test)]
 mod health_tests;
 
-pub use cargo::CargoManifest;
-pub use health::{HealthCheckType, HealthChecker, HealthReport, HealthStatus};
-pub use patch::{GitDependency, PatchManager, PatchReport, ResetReport};
-pub use version::{BumpType, VersionManager};
-pub use workspace::WorkspaceScanner;
+pub use cargo::{CargoManifest, FormatStyle, WorkspaceDependencies};
+pub use graph::{DependencyGraph, GraphStats};
+pub use health::{
+    CheckDiff, DocCoverage, HealthCheckType, HealthChecker, HealthDiff, HealthReport, HealthStatus,
+    OwnerIssue, SpecCoverage, StatusChange, TestCounts, DEFAULT_CHECK_TIMEOUT, DEFAULT_SCAN_DEPTH,
+    DEFAULT_SPEC_THRESHOLD, DEFAULT_TEST_ARGS, INDEX_CACHE_MAX_AGE,
+};
+pub use owners::CodeOwners;
+pub use patch::{
+    DiscoveryNote, DiscoveryReason, GitDependency, GitRef, PatchEntry, PatchManager, PatchReport,
+    PatchStatus, PatchTarget, ResetReport, RoundtripReport,
+};
+pub use version::{
+    BumpType, CommitAnalysis, PackageBump, RequirementChange, RollbackReport, VersionChange,
+    VersionManager,
+};
+pub use workspace::{WorkspaceScanner, DEFAULT_PACKAGE_PREFIX};
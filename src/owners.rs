@@ -0,0 +1,132 @@
+//! CODEOWNERS parsing for routing results to teams.
+//!
+//! Supports the GitHub CODEOWNERS format: one `pattern owner...` rule per
+//! line, `#` comments, and the last matching rule winning.
+
+use anyhow::{Context, Result};
+use glob::{MatchOptions, Pattern};
+use std::path::{Path, PathBuf};
+
+/// Bucket used for paths that no rule assigns an owner to.
+pub const UNOWNED: &str = "unowned";
+
+/// Locations searched for a CODEOWNERS file, in GitHub's order.
+const CODEOWNERS_LOCATIONS: &[&str] = &[".github/CODEOWNERS", "CODEOWNERS", "docs/CODEOWNERS"];
+
+/// Parsed CODEOWNERS rules.
+#[derive(Debug, Clone, Default)]
+pub struct CodeOwners {
+    pub path: PathBuf,
+    rules: Vec<OwnerRule>,
+}
+
+#[derive(Debug, Clone)]
+struct OwnerRule {
+    patterns: Vec<Pattern>,
+    owners: Vec<String>,
+}
+
+impl CodeOwners {
+    /// Load the first CODEOWNERS file found under `workspace_root`.
+    ///
+    /// Returns `None` if there is no CODEOWNERS file.
+    pub fn load(workspace_root: impl AsRef<Path>) -> Result<Option<Self>> {
+        let workspace_root = workspace_root.as_ref();
+
+        for location in CODEOWNERS_LOCATIONS {
+            let path = workspace_root.join(location);
+            if path.is_file() {
+                let content = std::fs::read_to_string(&path)
+                    .with_context(|| format!("Failed to read {}", path.display()))?;
+                let mut owners = Self::parse(&content)?;
+                owners.path = path;
+                return Ok(Some(owners));
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// Parse CODEOWNERS content.
+    pub fn parse(content: &str) -> Result<Self> {
+        let mut rules = Vec::new();
+
+        for line in content.lines() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let mut parts = line.split_whitespace();
+            let Some(pattern) = parts.next() else {
+                continue;
+            };
+            let owners: Vec<String> = parts
+                .take_while(|p| !p.starts_with('#'))
+                .map(String::from)
+                .collect();
+
+            rules.push(OwnerRule {
+                patterns: Self::compile(pattern)?,
+                owners,
+            });
+        }
+
+        Ok(Self {
+            path: PathBuf::new(),
+            rules,
+        })
+    }
+
+    /// Owners of `path` (relative to the repository root).
+    ///
+    /// Empty if no rule matches, or the last matching rule lists no owners.
+    pub fn owners_of(&self, path: &Path) -> Vec<String> {
+        let options = MatchOptions {
+            require_literal_separator: true,
+            ..MatchOptions::new()
+        };
+
+        self.rules
+            .iter()
+            .rev()
+            .find(|rule| {
+                rule.patterns
+                    .iter()
+                    .any(|p| p.matches_path_with(path, options))
+            })
+            .map(|rule| rule.owners.clone())
+            .unwrap_or_default()
+    }
+
+    /// Translate a CODEOWNERS pattern into equivalent glob patterns.
+    ///
+    /// A leading `/` anchors to the root, otherwise the pattern matches at any
+    /// depth; a trailing `/` (or a plain name) also matches everything below.
+    fn compile(pattern: &str) -> Result<Vec<Pattern>> {
+        let anchored = pattern.starts_with('/') || pattern.trim_end_matches('/').contains('/');
+        let body = pattern.trim_start_matches('/');
+        let body = if anchored || body.starts_with("**") {
+            body.to_string()
+        } else {
+            format!("**/{}", body)
+        };
+
+        let globs = if let Some(dir) = body.strip_suffix('/') {
+            vec![format!("{}/**", dir)]
+        } else {
+            vec![body.clone(), format!("{}/**", body)]
+        };
+
+        globs
+            .iter()
+            .map(|g| {
+                Pattern::new(g).with_context(|| format!("Invalid CODEOWNERS pattern '{}'", pattern))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+#[path = "owners_tests.rs"]
+mod tests;
@@ -0,0 +1,72 @@
+use crate::owners::CodeOwners;
+use std::fs;
+use std::path::Path;
+use tempfile::TempDir;
+
+const CODEOWNERS: &str = r#"# Default owners
+*                       @tzervas/core
+
+/embeddenator-vsa/      @tzervas/vsa @alice
+embeddenator-io         @tzervas/io
+*.md                    @tzervas/docs
+/embeddenator-fs/specs/ @tzervas/specs
+/embeddenator-obj/
+"#;
+
+#[test]
+fn test_owners_last_match_wins() {
+    let owners = CodeOwners::parse(CODEOWNERS).unwrap();
+
+    assert_eq!(
+        owners.owners_of(Path::new("embeddenator-vsa/Cargo.toml")),
+        vec!["@tzervas/vsa", "@alice"]
+    );
+    assert_eq!(
+        owners.owners_of(Path::new("embeddenator-io/Cargo.toml")),
+        vec!["@tzervas/io"]
+    );
+    // Unanchored names match at any depth
+    assert_eq!(
+        owners.owners_of(Path::new("nested/embeddenator-io/src/lib.rs")),
+        vec!["@tzervas/io"]
+    );
+    assert_eq!(
+        owners.owners_of(Path::new("embeddenator-vsa/README.md")),
+        vec!["@tzervas/docs"]
+    );
+    assert_eq!(
+        owners.owners_of(Path::new("embeddenator-fs/specs/api.txt")),
+        vec!["@tzervas/specs"]
+    );
+    assert_eq!(
+        owners.owners_of(Path::new("embeddenator-fs/Cargo.toml")),
+        vec!["@tzervas/core"]
+    );
+    // A rule without owners removes ownership
+    assert!(owners
+        .owners_of(Path::new("embeddenator-obj/Cargo.toml"))
+        .is_empty());
+}
+
+#[test]
+fn test_owners_load_locations() {
+    let temp_dir = TempDir::new().unwrap();
+    assert!(CodeOwners::load(temp_dir.path()).unwrap().is_none());
+
+    fs::create_dir_all(temp_dir.path().join(".github")).unwrap();
+    fs::write(
+        temp_dir.path().join(".github/CODEOWNERS"),
+        "/embeddenator-vsa/ @tzervas/vsa\n",
+    )
+    .unwrap();
+
+    let owners = CodeOwners::load(temp_dir.path()).unwrap().unwrap();
+    assert!(owners.path.ends_with(".github/CODEOWNERS"));
+    assert_eq!(
+        owners.owners_of(Path::new("embeddenator-vsa/Cargo.toml")),
+        vec!["@tzervas/vsa"]
+    );
+    assert!(owners
+        .owners_of(Path::new("embeddenator-io/Cargo.toml"))
+        .is_empty());
+}
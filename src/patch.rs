@@ -5,24 +5,195 @@
 
 use anyhow::{Context, Result};
 use colored::Colorize;
-use std::collections::{HashMap, HashSet};
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::io::{self, Write};
 use std::path::{Path, PathBuf};
-use toml_edit::{value, DocumentMut, Item, Table};
+use toml_edit::{value, DocumentMut, Item, Table, TableLike};
 
-use crate::workspace::WorkspaceScanner;
+use crate::cargo::{
+    write_atomic, CargoManifest, DependencySource, DependencyType, ManifestTransaction,
+};
+use crate::workspace::{WorkspaceScanner, DEFAULT_PACKAGE_PREFIX};
 
 /// Information about a git dependency that can be patched.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct GitDependency {
     pub name: String,
     pub git_url: String,
     pub branch_or_tag: Option<String>,
+    /// Commit the dependency is pinned to with `rev = "..."`.
+    pub rev: Option<String>,
     pub local_path: PathBuf,
 }
 
+/// A git reference a dependency can be pinned to.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum GitRef {
+    Branch(String),
+    Tag(String),
+    Rev(String),
+}
+
+impl GitRef {
+    /// Dependency key that selects this kind of reference.
+    pub fn key(&self) -> &'static str {
+        match self {
+            Self::Branch(_) => "branch",
+            Self::Tag(_) => "tag",
+            Self::Rev(_) => "rev",
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        match self {
+            Self::Branch(name) | Self::Tag(name) | Self::Rev(name) => name,
+        }
+    }
+}
+
+/// Whether the environment variable `var` marks a CI run.
+///
+/// Any non-empty value other than `false` or `0` counts, matching how CI
+/// providers set `CI=true` (or `CI=1`).
+pub fn is_ci(var: &str) -> bool {
+    std::env::var(var)
+        .map(|v| {
+            let v = v.trim();
+            !v.is_empty() && v != "0" && !v.eq_ignore_ascii_case("false")
+        })
+        .unwrap_or(false)
+}
+
+/// Normalize a git URL so that spellings of the same repository compare equal.
+///
+/// SSH forms (`git@host:owner/repo`, `ssh://git@host/owner/repo`) become
+/// `https://host/owner/repo`; the host is lowercased and a trailing `/` or
+/// `.git` is dropped.
+///
+/// Cargo treats SSH and HTTPS URLs as different sources, so this is only
+/// for comparing repositories; patch tables are keyed by [`patch_source`].
+pub fn normalize_git_url(url: &str) -> String {
+    let url = url.trim();
+    let (authority, path) = match url.split_once("://") {
+        Some(("file", _)) => return url.to_string(),
+        Some((scheme, rest)) => {
+            let (authority, path) = rest.split_once('/').unwrap_or((rest, ""));
+            // ssh://git@host:22/... may carry a port that https doesn't use
+            let authority = match scheme {
+                "ssh" => authority.split(':').next().unwrap_or(authority),
+                _ => authority,
+            };
+            (authority, path)
+        }
+        // scp-like syntax: git@github.com:owner/repo.git
+        None => match url.split_once(':') {
+            Some(parts) => parts,
+            None => return url.to_string(),
+        },
+    };
+
+    let host = authority
+        .rsplit_once('@')
+        .map_or(authority, |(_, host)| host);
+    let path = path.trim_end_matches('/');
+    let path = path.strip_suffix(".git").unwrap_or(path);
+    format!("https://{}/{}", host.to_lowercase(), path)
+}
+
+/// The `[patch]` key for a dependency declared with `git = url`.
+///
+/// Cargo only applies a patch whose key matches the dependency's source,
+/// ignoring a trailing `/` or `.git`, so only those are dropped.
+pub fn patch_source(url: &str) -> String {
+    let url = url.trim().trim_end_matches('/');
+    url.strip_suffix(".git").unwrap_or(url).to_string()
+}
+
+/// Why a dependency examined during discovery was or wasn't patchable.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum DiscoveryReason {
+    /// A git dependency with a local checkout; it will be patched.
+    Patchable,
+    /// The dependency name doesn't start with the package prefix
+    /// (`embeddenator` unless set with [`PatchManager::with_prefix`]).
+    NotEmbeddenator,
+    /// The dependency has no `git` key (registry or path dependency).
+    NoGitSource,
+    /// No package with this name exists in the workspace.
+    NoLocalPackage,
+    /// The package exists, but not at `<workspace>/<name>/Cargo.toml`.
+    NameMismatch,
+    /// Left out by [`PatchManager::with_only`].
+    NotSelected,
+}
+
+impl DiscoveryReason {
+    pub fn describe(&self) -> &'static str {
+        match self {
+            Self::Patchable => "patchable",
+            Self::NotEmbeddenator => "name doesn't match the package prefix",
+            Self::NoGitSource => "no git source",
+            Self::NoLocalPackage => "no local copy in the workspace",
+            Self::NameMismatch => "local package directory doesn't match the crate name",
+            Self::NotSelected => "not selected",
+        }
+    }
+}
+
+/// A dependency examined during discovery and the outcome.
+#[derive(Debug, Clone, Serialize)]
+pub struct DiscoveryNote {
+    /// Package whose manifest declares the dependency.
+    pub package: String,
+    pub dependency: String,
+    /// Manifest section, e.g. `dependencies`.
+    pub section: String,
+    pub reason: DiscoveryReason,
+}
+
+/// File that holds the `[patch]` tables.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PatchTarget {
+    /// `.cargo/config.toml` under the workspace root.
+    #[default]
+    CargoConfig,
+    /// The workspace root `Cargo.toml`.
+    WorkspaceManifest,
+}
+
+impl PatchTarget {
+    /// Path of the patch file, relative to the workspace root.
+    pub fn relative_path(&self) -> &'static str {
+        match self {
+            Self::CargoConfig => ".cargo/config.toml",
+            Self::WorkspaceManifest => "Cargo.toml",
+        }
+    }
+}
+
+impl std::str::FromStr for PatchTarget {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "config" => Ok(Self::CargoConfig),
+            "manifest" => Ok(Self::WorkspaceManifest),
+            _ => Err(format!("Unknown patch target: {}", s)),
+        }
+    }
+}
+
 /// Manager for Cargo patch operations.
 pub struct PatchManager {
     workspace_root: PathBuf,
+    sections: Vec<DependencyType>,
+    only: Vec<String>,
+    target: PatchTarget,
+    prefix: String,
+    cargo: PathBuf,
 }
 
 impl PatchManager {
@@ -30,20 +201,78 @@ impl PatchManager {
     pub fn new(workspace_root: impl AsRef<Path>) -> Self {
         Self {
             workspace_root: workspace_root.as_ref().to_path_buf(),
+            sections: vec![
+                DependencyType::Normal,
+                DependencyType::Dev,
+                DependencyType::Build,
+            ],
+            only: Vec::new(),
+            target: PatchTarget::default(),
+            prefix: DEFAULT_PACKAGE_PREFIX.to_string(),
+            cargo: PathBuf::from("cargo"),
         }
     }
 
+    /// Limit discovery to the given dependency sections (default: all three).
+    pub fn with_sections(mut self, sections: &[DependencyType]) -> Self {
+        self.sections = sections.to_vec();
+        self
+    }
+
+    /// Only patch, and only reset the patches of, the named crates (default:
+    /// every crate).
+    pub fn with_only(mut self, names: &[String]) -> Self {
+        self.only = names.to_vec();
+        self
+    }
+
+    /// Only patch crates whose name starts with `prefix` (default:
+    /// [`DEFAULT_PACKAGE_PREFIX`]).
+    pub fn with_prefix(mut self, prefix: &str) -> Self {
+        self.prefix = prefix.to_string();
+        self
+    }
+
+    /// Run this cargo binary instead of `cargo` from `PATH` when verifying
+    /// patches and cleaning the cache.
+    pub fn with_cargo_path(mut self, cargo: impl Into<PathBuf>) -> Self {
+        self.cargo = cargo.into();
+        self
+    }
+
+    /// Write and remove patches in `target` instead of `.cargo/config.toml`.
+    pub fn with_target(mut self, target: PatchTarget) -> Self {
+        self.target = target;
+        self
+    }
+
+    /// The file patches are written to.
+    fn patch_file(&self) -> PathBuf {
+        self.workspace_root.join(self.target.relative_path())
+    }
+
+    fn is_selected(&self, name: &str) -> bool {
+        self.only.is_empty() || self.only.iter().any(|n| n == name)
+    }
+
     /// Discover all embeddenator repos and their git dependencies.
     pub fn discover_patchable_dependencies(&self) -> Result<Vec<GitDependency>> {
+        Ok(self.discover_with_notes()?.0)
+    }
+
+    /// Discover patchable dependencies, recording why each examined
+    /// dependency was or wasn't patchable.
+    pub fn discover_with_notes(&self) -> Result<(Vec<GitDependency>, Vec<DiscoveryNote>)> {
         let scanner = WorkspaceScanner::new(&self.workspace_root);
         let manifests = scanner.find_manifests()?;
 
         let mut git_deps: HashMap<String, GitDependency> = HashMap::new();
+        let mut notes = Vec::new();
         let mut available_repos: HashSet<String> = HashSet::new();
 
         // First pass: identify all available local repos
         for manifest in &manifests {
-            if manifest.package_name.starts_with("embeddenator") {
+            if manifest.package_name.starts_with(&self.prefix) {
                 available_repos.insert(manifest.package_name.clone());
             }
         }
@@ -53,27 +282,43 @@ impl PatchManager {
             let content = std::fs::read_to_string(&manifest.path)?;
             let doc: DocumentMut = content.parse()?;
 
-            // Check dependencies, dev-dependencies, build-dependencies
-            for section in &["dependencies", "dev-dependencies", "build-dependencies"] {
+            // Check the configured dependency sections
+            for section in self.sections.iter().map(DependencyType::section_name) {
                 if let Some(Item::Table(deps_table)) = doc.get(section) {
                     for (name, dep_item) in deps_table.iter() {
-                        if let Some(git_dep) = Self::parse_git_dependency(name, dep_item) {
+                        let reason = if !name.starts_with(&self.prefix) {
+                            DiscoveryReason::NotEmbeddenator
+                        } else if !self.is_selected(name) {
+                            DiscoveryReason::NotSelected
+                        } else if let Some(git_dep) = Self::parse_git_dependency(name, dep_item) {
                             // Check if we have this repo locally
-                            if available_repos.contains(name) {
-                                // Find the local path
-                                if let Some(local_path) = self.find_local_repo_path(name) {
-                                    git_deps.insert(
-                                        name.to_string(),
-                                        GitDependency {
-                                            name: name.to_string(),
-                                            git_url: git_dep.0,
-                                            branch_or_tag: git_dep.1,
-                                            local_path,
-                                        },
-                                    );
-                                }
+                            if !available_repos.contains(name) {
+                                DiscoveryReason::NoLocalPackage
+                            } else if let Some(local_path) = self.find_local_repo_path(name) {
+                                git_deps.insert(
+                                    name.to_string(),
+                                    GitDependency {
+                                        name: name.to_string(),
+                                        git_url: git_dep.0,
+                                        branch_or_tag: git_dep.1,
+                                        rev: git_dep.2,
+                                        local_path,
+                                    },
+                                );
+                                DiscoveryReason::Patchable
+                            } else {
+                                DiscoveryReason::NameMismatch
                             }
-                        }
+                        } else {
+                            DiscoveryReason::NoGitSource
+                        };
+
+                        notes.push(DiscoveryNote {
+                            package: manifest.package_name.clone(),
+                            dependency: name.to_string(),
+                            section: section.to_string(),
+                            reason,
+                        });
                     }
                 }
             }
@@ -81,11 +326,17 @@ impl PatchManager {
 
         let mut deps: Vec<GitDependency> = git_deps.into_values().collect();
         deps.sort_by(|a, b| a.name.cmp(&b.name));
-        Ok(deps)
+        notes.sort_by(|a, b| {
+            (&a.package, &a.section, &a.dependency).cmp(&(&b.package, &b.section, &b.dependency))
+        });
+        Ok((deps, notes))
     }
 
-    /// Parse git dependency from TOML item.
-    fn parse_git_dependency(_name: &str, item: &Item) -> Option<(String, Option<String>)> {
+    /// Parse git dependency from TOML item: its URL, branch or tag, and rev.
+    fn parse_git_dependency(
+        _name: &str,
+        item: &Item,
+    ) -> Option<(String, Option<String>, Option<String>)> {
         // Handle both inline tables and regular tables
         let git_url = item.get("git")?.as_str()?.to_string();
         let branch_or_tag = item
@@ -93,7 +344,90 @@ impl PatchManager {
             .or_else(|| item.get("tag"))
             .and_then(|v| v.as_str())
             .map(|s| s.to_string());
-        Some((git_url, branch_or_tag))
+        let rev = item
+            .get("rev")
+            .and_then(|v| v.as_str())
+            .map(|s| s.to_string());
+        Some((git_url, branch_or_tag, rev))
+    }
+
+    /// Point every git dependency on `name` at `new_ref`, in the manifests of
+    /// all workspace members.
+    ///
+    /// Any other `branch`, `tag` or `rev` key on the dependency is removed.
+    /// All manifests are written together, or none are. Returns the manifests
+    /// that changed.
+    pub fn retarget_git(&self, name: &str, new_ref: GitRef) -> Result<Vec<PathBuf>> {
+        let scanner = WorkspaceScanner::new(&self.workspace_root);
+        let mut transaction = ManifestTransaction::new();
+        let mut found = false;
+        let mut changed = Vec::new();
+
+        for manifest in scanner.find_manifests()? {
+            let content = std::fs::read_to_string(&manifest.path)?;
+            let mut doc: DocumentMut = content.parse()?;
+            let mut modified = false;
+
+            for section in self.sections.iter().map(DependencyType::section_name) {
+                let Some(dep) = doc
+                    .get_mut(section)
+                    .and_then(|deps| deps.get_mut(name))
+                    .and_then(|dep| dep.as_table_like_mut())
+                else {
+                    continue;
+                };
+                if dep.get("git").is_none() {
+                    continue;
+                }
+                found = true;
+                modified |= Self::set_git_ref(dep, &new_ref);
+            }
+
+            if modified {
+                transaction.stage(&manifest.path, doc.to_string());
+                changed.push(manifest.path);
+            }
+        }
+
+        if !found {
+            anyhow::bail!("No git dependency on '{}' found in the workspace", name);
+        }
+
+        transaction.commit()?;
+        changed.sort();
+        Ok(changed)
+    }
+
+    /// Replace the reference keys of a git dependency with `new_ref`.
+    ///
+    /// Returns `false` if the dependency already pointed at `new_ref` alone.
+    fn set_git_ref(dep: &mut dyn TableLike, new_ref: &GitRef) -> bool {
+        let mut modified = false;
+
+        for key in ["branch", "tag", "rev"] {
+            if key != new_ref.key() && dep.remove(key).is_some() {
+                modified = true;
+            }
+        }
+
+        match dep.get_mut(new_ref.key()) {
+            Some(item) if item.as_str() == Some(new_ref.name()) => {}
+            Some(item) => {
+                // Keep the surrounding whitespace and any trailing comment
+                let decor = item.as_value().map(|v| v.decor().clone());
+                *item = value(new_ref.name());
+                if let (Some(val), Some(decor)) = (item.as_value_mut(), decor) {
+                    *val.decor_mut() = decor;
+                }
+                modified = true;
+            }
+            None => {
+                dep.insert(new_ref.key(), value(new_ref.name()));
+                modified = true;
+            }
+        }
+
+        modified
     }
 
     /// Find the local path for a repository.
@@ -106,88 +440,273 @@ impl PatchManager {
         }
     }
 
-    /// Apply local patches to .cargo/config.toml
-    pub fn apply_patches(&self, deps: &[GitDependency], verify: bool) -> Result<PatchReport> {
-        let cargo_dir = self.workspace_root.join(".cargo");
-        let config_path = cargo_dir.join("config.toml");
-
-        // Create .cargo directory if it doesn't exist
-        if !cargo_dir.exists() {
-            std::fs::create_dir(&cargo_dir).context("Failed to create .cargo directory")?;
-        }
+    /// Apply local patches to .cargo/config.toml (or the configured target)
+    ///
+    /// With `dry_run`, nothing is written (and `verify` is ignored); the report
+    /// still carries the entries and the diff that patching would produce.
+    ///
+    /// The config is written to a temporary file and renamed into place, so
+    /// an interrupted run never leaves a truncated config behind.
+    pub fn apply_patches(
+        &self,
+        deps: &[GitDependency],
+        verify: bool,
+        dry_run: bool,
+    ) -> Result<PatchReport> {
+        let config_path = self.patch_file();
+        let relative = self.target.relative_path();
 
         // Load or create config.toml
-        let mut doc: DocumentMut = if config_path.exists() {
-            let content = std::fs::read_to_string(&config_path)?;
-            content.parse()?
+        let original = if config_path.exists() {
+            std::fs::read_to_string(&config_path)?
+        } else if self.target == PatchTarget::WorkspaceManifest {
+            anyhow::bail!("No Cargo.toml at {}", self.workspace_root.display());
         } else {
-            DocumentMut::new()
+            String::new()
         };
+        let mut doc: DocumentMut = original.parse()?;
 
-        let mut patched_count = 0;
+        let mut entries: Vec<PatchEntry> = deps
+            .iter()
+            .map(|dep| PatchEntry {
+                source: patch_source(&dep.git_url),
+                name: dep.name.clone(),
+                path: Some(dep.local_path.to_string_lossy().to_string()),
+            })
+            .collect();
+        entries.sort_by(|a, b| (&a.source, &a.name).cmp(&(&b.source, &b.name)));
+        let (patched_count, unchanged_count) = Self::insert_patch_entries(&mut doc, &entries);
+        let warnings = self.requirement_warnings(deps)?;
+
+        let updated = doc.to_string();
+        if !dry_run {
+            // Create .cargo directory if it doesn't exist
+            let cargo_dir = config_path.parent().unwrap_or(&self.workspace_root);
+            if !cargo_dir.exists() {
+                std::fs::create_dir(cargo_dir).context("Failed to create .cargo directory")?;
+            }
+            write_atomic(&config_path, &updated)
+                .with_context(|| format!("Failed to write {}", relative))?;
+        }
+
+        let config_diff = similar::TextDiff::from_lines(&original, &updated)
+            .unified_diff()
+            .header(&format!("a/{}", relative), &format!("b/{}", relative))
+            .to_string();
+
+        let mut report = PatchReport {
+            patched_count,
+            unchanged_count,
+            config_path: config_path.clone(),
+            verified: false,
+            verification_error: None,
+            config_diff,
+            entries,
+            dry_run,
+            warnings,
+        };
+
+        // Verify patches if requested
+        if verify && !dry_run {
+            match self.verify_patches() {
+                Ok(_) => report.verified = true,
+                Err(e) => report.verification_error = Some(e.to_string()),
+            }
+        }
+
+        Ok(report)
+    }
+
+    /// Remove the matching entries from a patch source table.
+    ///
+    /// Returns the number of entries removed.
+    fn remove_from(deps: &mut Table, matches: &dyn Fn(&str, &Item) -> bool) -> usize {
+        let before = deps.len();
+        deps.retain(|name, entry| !matches(name, entry));
+        before - deps.len()
+    }
+
+    /// The `path` of a patch entry, if it has one.
+    fn patch_path(entry: &Item) -> Option<&str> {
+        entry.get("path").and_then(|p| p.as_str())
+    }
+
+    /// Whether a patch `path` (relative paths resolved against the workspace
+    /// root) has no `Cargo.toml`.
+    fn is_dangling(&self, path: &str) -> bool {
+        !self.workspace_root.join(path).join("Cargo.toml").is_file()
+    }
 
-        // Group dependencies by git URL
-        let mut patches_by_url: HashMap<String, Vec<&GitDependency>> = HashMap::new();
+    /// Patches whose `path` no longer holds a crate, e.g. because the local
+    /// repository was deleted after patching. Returns `(crate, path)` pairs.
+    pub fn find_dangling_patches(&self) -> Result<Vec<(String, PathBuf)>> {
+        let config_path = self.patch_file();
+        if !config_path.exists() {
+            return Ok(Vec::new());
+        }
+
+        let content = std::fs::read_to_string(&config_path)?;
+        let doc: DocumentMut = content
+            .parse()
+            .with_context(|| format!("Failed to parse {}", config_path.display()))?;
+
+        Ok(Self::collect_patch_entries(&doc)
+            .into_iter()
+            .filter_map(|entry| {
+                let path = entry.path?;
+                self.is_dangling(&path)
+                    .then(|| (entry.name, PathBuf::from(path)))
+            })
+            .collect())
+    }
+
+    /// Warn about dependents whose version requirement the local copy of a
+    /// patched crate doesn't satisfy; cargo would fail to resolve them.
+    fn requirement_warnings(&self, deps: &[GitDependency]) -> Result<Vec<String>> {
+        let mut local_versions = HashMap::new();
         for dep in deps {
-            patches_by_url
-                .entry(dep.git_url.clone())
-                .or_default()
-                .push(dep);
+            let manifest_path = dep.local_path.join("Cargo.toml");
+            if let Ok(local) = CargoManifest::load(&manifest_path) {
+                local_versions.insert(dep.name.as_str(), (local.version, manifest_path));
+            }
+        }
+
+        let scanner = WorkspaceScanner::new(&self.workspace_root);
+        let mut warnings = Vec::new();
+        for manifest in scanner.find_manifests()? {
+            for dependency in &manifest.dependencies {
+                if !self.sections.contains(&dependency.dep_type)
+                    || !matches!(dependency.source, DependencySource::Git { .. })
+                {
+                    continue;
+                }
+                let (Some(req), Some((version, path))) = (
+                    &dependency.requirement,
+                    local_versions.get(dependency.name.as_str()),
+                ) else {
+                    continue;
+                };
+                if !req.matches(version) {
+                    warnings.push(format!(
+                        "{} requires {} {}, but the local copy at {} is {}",
+                        manifest.package_name,
+                        dependency.name,
+                        req,
+                        path.parent().unwrap_or(path).display(),
+                        version
+                    ));
+                }
+            }
+        }
+
+        warnings.sort();
+        warnings.dedup();
+        Ok(warnings)
+    }
+
+    /// Add a `path` patch for each entry, grouped by git URL.
+    ///
+    /// Entries that already patch the crate to the same path are left as they
+    /// are. Returns the number of entries written and the number left unchanged.
+    fn insert_patch_entries(doc: &mut DocumentMut, entries: &[PatchEntry]) -> (usize, usize) {
+        let mut patched_count = 0;
+        let mut unchanged_count = 0;
+
+        // Group entries by git URL
+        let mut patches_by_url: BTreeMap<&str, Vec<&PatchEntry>> = BTreeMap::new();
+        for entry in entries {
+            patches_by_url.entry(&entry.source).or_default().push(entry);
         }
 
         // Apply patches for each git URL
-        for (git_url, deps_for_url) in patches_by_url {
+        for (git_url, entries_for_url) in patches_by_url {
             let patch_key = format!("patch.\"{}\"", git_url);
 
+            // Skip entries already patched to the same path, in either the
+            // dotted or the nested [patch] form
+            let (unchanged, entries_for_url): (Vec<&PatchEntry>, Vec<&PatchEntry>) =
+                entries_for_url.into_iter().partition(|entry| {
+                    let existing = doc
+                        .get(&patch_key)
+                        .and_then(|t| t.get(&entry.name))
+                        .or_else(|| {
+                            doc.get("patch")
+                                .and_then(|p| p.get(git_url))
+                                .and_then(|t| t.get(&entry.name))
+                        })
+                        .and_then(|e| e.get("path"))
+                        .and_then(|p| p.as_str());
+                    existing.is_some() && existing == entry.path.as_deref()
+                });
+            unchanged_count += unchanged.len();
+            if entries_for_url.is_empty() {
+                continue;
+            }
+
             // Create patch section if it doesn't exist
             if doc.get(&patch_key).is_none() {
                 doc[&patch_key] = Item::Table(Table::new());
             }
 
             if let Some(Item::Table(patch_table)) = doc.get_mut(&patch_key) {
-                for dep in deps_for_url {
+                for entry in entries_for_url {
                     // Create patch entry
                     let mut dep_table = Table::new();
-                    dep_table.insert("path", value(dep.local_path.to_string_lossy().to_string()));
+                    if let Some(path) = &entry.path {
+                        dep_table.insert("path", value(path.as_str()));
+                    }
 
-                    patch_table.insert(&dep.name, Item::Table(dep_table));
+                    patch_table.insert(&entry.name, Item::Table(dep_table));
                     patched_count += 1;
                 }
             }
         }
 
-        // Save the config file
-        std::fs::write(&config_path, doc.to_string())
-            .context("Failed to write .cargo/config.toml")?;
-
-        let mut report = PatchReport {
-            patched_count,
-            config_path: config_path.clone(),
-            verified: false,
-            verification_error: None,
-        };
+        (patched_count, unchanged_count)
+    }
 
-        // Verify patches if requested
-        if verify {
-            match self.verify_patches() {
-                Ok(_) => report.verified = true,
-                Err(e) => report.verification_error = Some(e.to_string()),
-            }
-        }
+    /// Remove all patches from .cargo/config.toml, or only those of the crates
+    /// given to [`PatchManager::with_only`]. Patch tables left empty are
+    /// removed too.
+    ///
+    /// With `dry_run`, nothing is written or deleted; the report still carries
+    /// the config content that would result from the reset.
+    ///
+    /// A workspace manifest target is never deleted, even if nothing else is
+    /// left in it. Like [`PatchManager::apply_patches`], the config is
+    /// replaced atomically.
+    pub fn remove_patches(&self, dry_run: bool) -> Result<ResetReport> {
+        self.remove_matching(dry_run, self.only.is_empty(), &|name, _| {
+            self.is_selected(name)
+        })
+    }
 
-        Ok(report)
+    /// Remove only the dangling patches (see
+    /// [`PatchManager::find_dangling_patches`]), leaving valid ones in place.
+    pub fn prune_dangling_patches(&self, dry_run: bool) -> Result<ResetReport> {
+        self.remove_matching(dry_run, false, &|_, entry| {
+            Self::patch_path(entry).is_some_and(|path| self.is_dangling(path))
+        })
     }
 
-    /// Remove all patches from .cargo/config.toml
-    pub fn remove_patches(&self) -> Result<ResetReport> {
-        let cargo_dir = self.workspace_root.join(".cargo");
-        let config_path = cargo_dir.join("config.toml");
+    /// Remove the patch entries for which `matches(name, entry)` holds, along
+    /// with any patch table left empty. With `remove_all`, malformed patch
+    /// items that aren't tables are removed as well.
+    fn remove_matching(
+        &self,
+        dry_run: bool,
+        remove_all: bool,
+        matches: &dyn Fn(&str, &Item) -> bool,
+    ) -> Result<ResetReport> {
+        let config_path = self.patch_file();
 
         if !config_path.exists() {
             return Ok(ResetReport {
                 removed_count: 0,
                 config_path,
                 config_deleted: false,
+                resulting_config: None,
+                dry_run,
             });
         }
 
@@ -197,60 +716,289 @@ impl PatchManager {
         let mut removed_count = 0;
 
         // Find all patch.* sections (both dotted keys like patch."url" and nested [patch] table)
-        let mut keys_to_remove = Vec::new();
+        let patch_keys: Vec<String> = doc
+            .as_table()
+            .iter()
+            .map(|(key, _)| key)
+            .filter(|key| *key == "patch" || key.starts_with("patch."))
+            .map(String::from)
+            .collect();
+
+        for key in patch_keys {
+            let Some(Item::Table(table)) = doc.get_mut(&key) else {
+                if remove_all {
+                    doc.remove(&key);
+                }
+                continue;
+            };
 
-        for (key, _) in doc.as_table().iter() {
             if key == "patch" {
                 // Handle [patch] table with nested sources
-                if let Some(Item::Table(patch_table)) = doc.get("patch") {
-                    for (_source_url, dep_item) in patch_table.iter() {
-                        if let Item::Table(deps) = dep_item {
-                            removed_count += deps.len();
+                let sources: Vec<String> = table.iter().map(|(k, _)| k.to_string()).collect();
+                for source in sources {
+                    match table.get_mut(&source) {
+                        Some(Item::Table(deps)) => {
+                            removed_count += Self::remove_from(deps, matches);
+                            if deps.is_empty() {
+                                table.remove(&source);
+                            }
+                        }
+                        _ if remove_all => {
+                            table.remove(&source);
                         }
+                        _ => {}
                     }
                 }
-                keys_to_remove.push(key.to_string());
-            } else if key.starts_with("patch.") {
+            } else {
                 // Handle dotted keys like [patch."https://..."]
-                if let Some(Item::Table(patch_deps)) = doc.get(key) {
-                    removed_count += patch_deps.len();
-                }
-                keys_to_remove.push(key.to_string());
+                removed_count += Self::remove_from(table, matches);
             }
-        }
 
-        // Remove all patch sections
-        for key in keys_to_remove {
-            doc.remove(&key);
+            // Drop sections left without entries
+            if table.is_empty() {
+                doc.remove(&key);
+            }
         }
 
         // Check if the document is now empty or only has whitespace
-        let is_empty = doc.as_table().is_empty();
+        let is_empty = doc.as_table().is_empty() && self.target == PatchTarget::CargoConfig;
 
         if is_empty {
             // Delete the config file
-            std::fs::remove_file(&config_path)?;
+            if !dry_run {
+                std::fs::remove_file(&config_path)?;
+            }
             Ok(ResetReport {
                 removed_count,
                 config_path,
                 config_deleted: true,
+                resulting_config: None,
+                dry_run,
             })
         } else {
             // Save the modified config
-            std::fs::write(&config_path, doc.to_string())?;
+            let resulting_config = doc.to_string();
+            if !dry_run {
+                write_atomic(&config_path, &resulting_config)
+                    .with_context(|| format!("Failed to write {}", config_path.display()))?;
+            }
             Ok(ResetReport {
                 removed_count,
                 config_path,
                 config_deleted: false,
+                resulting_config: Some(resulting_config),
+                dry_run,
             })
         }
     }
 
+    /// List the patches currently in .cargo/config.toml and validate their paths.
+    ///
+    /// Flags paths that don't exist, can't be canonicalized, contain `..`, or
+    /// resolve outside the workspace root. Relative paths are resolved against
+    /// the workspace root, as cargo does for `.cargo/config.toml`.
+    pub fn patch_status(&self) -> Result<PatchStatus> {
+        let config_path = self.patch_file();
+
+        let mut status = PatchStatus {
+            config_path: config_path.clone(),
+            entries: Vec::new(),
+            dangling: Vec::new(),
+            warnings: Vec::new(),
+        };
+
+        if !config_path.exists() {
+            return Ok(status);
+        }
+
+        let content = std::fs::read_to_string(&config_path)?;
+        let doc: DocumentMut = content
+            .parse()
+            .with_context(|| format!("Failed to parse {}", config_path.display()))?;
+
+        status.entries = Self::collect_patch_entries(&doc);
+
+        let root = self.workspace_root.canonicalize().ok();
+        for entry in &status.entries {
+            let Some(path) = &entry.path else {
+                continue;
+            };
+            if self.is_dangling(path) {
+                status
+                    .dangling
+                    .push((entry.name.clone(), PathBuf::from(path)));
+            }
+            let label = format!("{} ({})", entry.name, path);
+            let raw = Path::new(path);
+
+            if raw
+                .components()
+                .any(|c| matches!(c, std::path::Component::ParentDir))
+            {
+                status
+                    .warnings
+                    .push(format!("{}: path is not normalized (contains '..')", label));
+            }
+
+            let resolved = self.workspace_root.join(raw);
+            if !resolved.exists() {
+                status
+                    .warnings
+                    .push(format!("{}: path does not exist", label));
+                continue;
+            }
+
+            match resolved.canonicalize() {
+                Ok(canonical) => {
+                    if let Some(root) = &root {
+                        if !canonical.starts_with(root) {
+                            status
+                                .warnings
+                                .push(format!("{}: path is outside the workspace root", label));
+                        }
+                    }
+                }
+                Err(e) => {
+                    status
+                        .warnings
+                        .push(format!("{}: path cannot be canonicalized: {}", label, e));
+                }
+            }
+        }
+
+        Ok(status)
+    }
+
+    /// Collect patch entries from both `[patch.<source>]` tables and the
+    /// quoted `["patch.<source>"]` keys written by older versions of this tool.
+    fn collect_patch_entries(doc: &DocumentMut) -> Vec<PatchEntry> {
+        let mut entries = Vec::new();
+
+        let mut push_deps = |source: &str, deps: &Item| {
+            if let Some(deps) = deps.as_table_like() {
+                for (name, dep) in deps.iter() {
+                    entries.push(PatchEntry {
+                        source: source.to_string(),
+                        name: name.to_string(),
+                        path: dep.get("path").and_then(|p| p.as_str()).map(String::from),
+                    });
+                }
+            }
+        };
+
+        for (key, item) in doc.as_table().iter() {
+            if key == "patch" {
+                if let Some(sources) = item.as_table_like() {
+                    for (source, deps) in sources.iter() {
+                        push_deps(source, deps);
+                    }
+                }
+            } else if let Some(source) = key.strip_prefix("patch.") {
+                push_deps(source.trim_matches('"'), item);
+            }
+        }
+
+        entries
+    }
+
+    /// Apply and then remove patches, checking the config ends up byte-identical.
+    ///
+    /// The original `.cargo/config.toml` is backed up first and always restored
+    /// afterwards, so the workspace is left as it was even if a step fails. A
+    /// `.cargo/` directory that only existed to hold the temporary config is removed.
+    pub fn verify_roundtrip(&self) -> Result<RoundtripReport> {
+        let cargo_dir = self.workspace_root.join(".cargo");
+        let config_path = self.patch_file();
+        let relative = self.target.relative_path();
+
+        let had_cargo_dir = cargo_dir.exists();
+        let original = if config_path.exists() {
+            Some(
+                std::fs::read(&config_path)
+                    .with_context(|| format!("Failed to back up {}", relative))?,
+            )
+        } else {
+            None
+        };
+
+        let result = self.roundtrip_once(&config_path, original.as_deref());
+
+        // Restore the original state regardless of the outcome
+        match &original {
+            Some(bytes) => std::fs::write(&config_path, bytes)
+                .with_context(|| format!("Failed to restore {}", relative))?,
+            None if config_path.exists() => std::fs::remove_file(&config_path)
+                .with_context(|| format!("Failed to remove {}", relative))?,
+            None => {}
+        }
+        if !had_cargo_dir && cargo_dir.exists() && std::fs::read_dir(&cargo_dir)?.next().is_none() {
+            std::fs::remove_dir(&cargo_dir).context("Failed to remove .cargo directory")?;
+        }
+
+        result
+    }
+
+    fn roundtrip_once(
+        &self,
+        config_path: &Path,
+        original: Option<&[u8]>,
+    ) -> Result<RoundtripReport> {
+        let deps = self.discover_patchable_dependencies()?;
+        let patch_report = self.apply_patches(&deps, false, false)?;
+        let reset_report = self.remove_patches(false)?;
+
+        let after = if config_path.exists() {
+            Some(std::fs::read(config_path)?)
+        } else {
+            None
+        };
+
+        let mut differences = Vec::new();
+        match (original, after.as_deref()) {
+            (None, Some(_)) => differences.push(format!(
+                "{} was left behind after reset",
+                config_path.display()
+            )),
+            (Some(_), None) => {
+                differences.push(format!("{} was deleted by reset", config_path.display()))
+            }
+            (Some(before), Some(after)) if before != after => {
+                let diff = similar::TextDiff::from_lines(
+                    &String::from_utf8_lossy(before).into_owned(),
+                    &String::from_utf8_lossy(after).into_owned(),
+                )
+                .unified_diff()
+                .header("before", "after")
+                .to_string();
+                differences.push(format!(
+                    "{} differs after reset:\n{}",
+                    config_path.display(),
+                    diff
+                ));
+            }
+            _ => {}
+        }
+
+        let applied = patch_report.patched_count + patch_report.unchanged_count;
+        if reset_report.removed_count != applied {
+            differences.push(format!(
+                "applied {} patches but reset removed {}",
+                applied, reset_report.removed_count
+            ));
+        }
+
+        Ok(RoundtripReport {
+            patched_count: patch_report.patched_count,
+            removed_count: reset_report.removed_count,
+            differences,
+        })
+    }
+
     /// Verify that patches are working by running cargo metadata.
     fn verify_patches(&self) -> Result<()> {
         use std::process::Command;
 
-        let output = Command::new("cargo")
+        let output = Command::new(&self.cargo)
             .arg("metadata")
             .arg("--format-version=1")
             .current_dir(&self.workspace_root)
@@ -271,7 +1019,7 @@ impl PatchManager {
 
         println!("{}", "  Cleaning cargo cache...".dimmed());
 
-        let output = Command::new("cargo")
+        let output = Command::new(&self.cargo)
             .arg("clean")
             .current_dir(&self.workspace_root)
             .output()
@@ -289,10 +1037,21 @@ impl PatchManager {
 /// Report from applying patches.
 #[derive(Debug)]
 pub struct PatchReport {
+    /// Entries added, or redirected to a different path.
     pub patched_count: usize,
+    /// Entries that already patched the crate to the same path.
+    pub unchanged_count: usize,
     pub config_path: PathBuf,
     pub verified: bool,
     pub verification_error: Option<String>,
+    /// Unified diff of the config file before and after patching.
+    pub config_diff: String,
+    /// Patch entries written, sorted by git URL and crate name.
+    pub entries: Vec<PatchEntry>,
+    /// Whether this was a dry run that left the config untouched.
+    pub dry_run: bool,
+    /// Dependents whose version requirement a local copy doesn't satisfy.
+    pub warnings: Vec<String>,
 }
 
 /// Report from removing patches.
@@ -301,52 +1060,236 @@ pub struct ResetReport {
     pub removed_count: usize,
     pub config_path: PathBuf,
     pub config_deleted: bool,
+    /// Config content after the reset, or `None` if the file is (or would be) deleted.
+    pub resulting_config: Option<String>,
+    /// Whether this was a dry run that left the config untouched.
+    pub dry_run: bool,
 }
 
 impl PatchReport {
+    /// Print the summary to stdout.
     pub fn print(&self) {
-        println!(
-            "\n{} {} patches written to {}",
+        let _ = self.print_to(&mut io::stdout().lock());
+    }
+
+    /// Write the summary to `w`.
+    pub fn print_to<W: Write>(&self, w: &mut W) -> io::Result<()> {
+        writeln!(
+            w,
+            "\n{} {} patches {} {}",
             "✓".green().bold(),
             self.patched_count,
+            if self.dry_run {
+                "would be written to"
+            } else {
+                "written to"
+            },
             self.config_path.display().to_string().bright_white()
-        );
+        )?;
+        if self.unchanged_count > 0 {
+            writeln!(
+                w,
+                "  {} already patched, unchanged",
+                self.unchanged_count.to_string().dimmed()
+            )?;
+        }
+        for warning in &self.warnings {
+            writeln!(w, "{} {}", "Warning:".yellow().bold(), warning)?;
+        }
 
         if self.verified {
-            println!("{} Patches verified successfully", "✓".green().bold());
+            writeln!(w, "{} Patches verified successfully", "✓".green().bold())?;
         } else if let Some(err) = &self.verification_error {
-            println!("{} Verification failed: {}", "✗".red().bold(), err);
-            println!(
+            writeln!(w, "{} Verification failed: {}", "✗".red().bold(), err)?;
+            writeln!(
+                w,
                 "\n{} Run 'cargo build' to diagnose the issue",
                 "Suggestion:".cyan().bold()
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl PatchReport {
+    /// The `[patch]` tables for the patched entries alone, as they are (or
+    /// would be) written to the config.
+    pub fn patch_blocks(&self) -> String {
+        let mut doc = DocumentMut::new();
+        PatchManager::insert_patch_entries(&mut doc, &self.entries);
+        doc.to_string()
+    }
+
+    /// Print the config diff with added/removed lines colorized.
+    pub fn print_diff(&self) {
+        if self.config_diff.is_empty() {
+            println!("{} Config unchanged", "Info:".blue().bold());
+            return;
+        }
+
+        println!("\n{}", "Config Changes:".cyan().bold());
+        for line in self.config_diff.lines() {
+            if line.starts_with("+++") || line.starts_with("---") {
+                println!("{}", line.bold());
+            } else if line.starts_with('+') {
+                println!("{}", line.green());
+            } else if line.starts_with('-') {
+                println!("{}", line.red());
+            } else if line.starts_with("@@") {
+                println!("{}", line.cyan());
+            } else {
+                println!("{}", line);
+            }
+        }
+    }
+}
+
+/// A single `[patch]` entry found in .cargo/config.toml.
+#[derive(Debug, Clone, Serialize)]
+pub struct PatchEntry {
+    pub source: String,
+    pub name: String,
+    pub path: Option<String>,
+}
+
+/// Current patches and any problems with their paths.
+#[derive(Debug)]
+pub struct PatchStatus {
+    pub config_path: PathBuf,
+    pub entries: Vec<PatchEntry>,
+    /// Entries whose path holds no `Cargo.toml`, as `(crate, path)`.
+    pub dangling: Vec<(String, PathBuf)>,
+    pub warnings: Vec<String>,
+}
+
+impl PatchStatus {
+    pub fn print(&self) {
+        if self.entries.is_empty() {
+            println!("{} No patches active", "Info:".blue().bold());
+            return;
+        }
+
+        println!(
+            "\n{} {} patch(es) in {} ({} valid, {} dangling)",
+            "Active:".green().bold(),
+            self.entries.len(),
+            self.config_path.display().to_string().bright_white(),
+            self.entries.len() - self.dangling.len(),
+            self.dangling.len()
+        );
+        for entry in &self.entries {
+            let dangling = self
+                .dangling
+                .iter()
+                .any(|(name, path)| *name == entry.name && entry.path.as_deref() == path.to_str());
+            let marker = if dangling { "✗".red() } else { "•".green() };
+            println!(
+                "  {} {} → {}{}",
+                marker,
+                entry.name.bright_white().bold(),
+                entry.path.as_deref().unwrap_or("(no path)").dimmed(),
+                if dangling { " (dangling)" } else { "" }
+            );
+        }
+        if !self.dangling.is_empty() {
+            println!(
+                "\n{} Run 'embeddenator-workspace patch-status --prune' to remove dangling patches",
+                "Suggestion:".cyan().bold()
             );
         }
+
+        if !self.warnings.is_empty() {
+            println!("\n{}", "Warnings:".yellow().bold());
+            for warning in &self.warnings {
+                println!("  {} {}", "•".yellow(), warning);
+            }
+        }
+    }
+}
+
+/// Report from a patch/reset round-trip verification.
+#[derive(Debug)]
+pub struct RoundtripReport {
+    pub patched_count: usize,
+    pub removed_count: usize,
+    pub differences: Vec<String>,
+}
+
+impl RoundtripReport {
+    /// Whether reset restored the config exactly.
+    pub fn is_lossless(&self) -> bool {
+        self.differences.is_empty()
+    }
+
+    pub fn print(&self) {
+        println!(
+            "\n{} {} patches applied, {} removed",
+            "Round-trip:".cyan().bold(),
+            self.patched_count,
+            self.removed_count
+        );
+
+        if self.is_lossless() {
+            println!("{} Patch round-trip is lossless", "✓".green().bold());
+        } else {
+            println!("{} Residual differences found:", "✗".red().bold());
+            for difference in &self.differences {
+                println!("  {} {}", "•".red(), difference);
+            }
+        }
     }
 }
 
 impl ResetReport {
+    /// Print the summary to stdout.
     pub fn print(&self) {
+        let _ = self.print_to(&mut io::stdout().lock());
+    }
+
+    /// Write the summary to `w`.
+    pub fn print_to<W: Write>(&self, w: &mut W) -> io::Result<()> {
         if self.removed_count == 0 {
-            println!("{} No patches found to remove", "Info:".blue().bold());
+            writeln!(w, "{} No patches found to remove", "Info:".blue().bold())?;
         } else {
-            println!(
-                "\n{} {} patches removed",
+            let (removed, deleted, updated) = if self.dry_run {
+                ("would be removed", "would be deleted", "would be updated")
+            } else {
+                ("removed", "deleted", "updated")
+            };
+
+            writeln!(
+                w,
+                "\n{} {} patches {}",
                 "✓".green().bold(),
-                self.removed_count
-            );
+                self.removed_count,
+                removed
+            )?;
 
             if self.config_deleted {
-                println!(
-                    "  {} deleted (empty)",
-                    self.config_path.display().to_string().dimmed()
-                );
+                writeln!(
+                    w,
+                    "  {} {} (empty)",
+                    self.config_path.display().to_string().dimmed(),
+                    deleted
+                )?;
             } else {
-                println!(
-                    "  {} updated",
-                    self.config_path.display().to_string().dimmed()
-                );
+                writeln!(
+                    w,
+                    "  {} {}",
+                    self.config_path.display().to_string().dimmed(),
+                    updated
+                )?;
             }
         }
+        Ok(())
+    }
+
+    /// Print the config content that remains after the reset.
+    pub fn print_resulting_config(&self) {
+        if let Some(content) = &self.resulting_config {
+            println!("\n{}", "Resulting Config:".cyan().bold());
+            print!("{}", content);
+        }
     }
 }
 
@@ -1,5 +1,6 @@
 //! Tests for patch management functionality.
 
+use crate::patch::{normalize_git_url, patch_source};
 use crate::*;
 use std::fs;
 use std::path::PathBuf;
@@ -99,7 +100,7 @@ fn test_apply_patches() {
     let manager = PatchManager::new(&root);
 
     let deps = manager.discover_patchable_dependencies().unwrap();
-    let report = manager.apply_patches(&deps, false).unwrap();
+    let report = manager.apply_patches(&deps, false, false).unwrap();
 
     assert_eq!(report.patched_count, 4);
     assert!(!report.verified); // verification skipped
@@ -134,13 +135,13 @@ fn test_remove_patches() {
 
     // First apply patches
     let deps = manager.discover_patchable_dependencies().unwrap();
-    manager.apply_patches(&deps, false).unwrap();
+    manager.apply_patches(&deps, false, false).unwrap();
 
     let config_path = root.join(".cargo/config.toml");
     assert!(config_path.exists());
 
     // Now remove patches
-    let report = manager.remove_patches().unwrap();
+    let report = manager.remove_patches(false).unwrap();
     assert_eq!(report.removed_count, 4);
     assert!(report.config_deleted); // Should be deleted as it's empty
 
@@ -167,7 +168,7 @@ embeddenator-vsa = { path = "embeddenator-vsa" }
     fs::write(&config_path, config_content).unwrap();
 
     // Remove patches
-    let report = manager.remove_patches().unwrap();
+    let report = manager.remove_patches(false).unwrap();
     assert_eq!(report.removed_count, 1);
     assert!(!report.config_deleted); // Should be preserved
 
@@ -187,7 +188,7 @@ fn test_remove_patches_when_none_exist() {
     let manager = PatchManager::new(&root);
 
     // Remove patches without applying any
-    let report = manager.remove_patches().unwrap();
+    let report = manager.remove_patches(false).unwrap();
     assert_eq!(report.removed_count, 0);
 }
 
@@ -231,7 +232,7 @@ embeddenator-fs = { git = "https://github.com/tzervas/embeddenator", branch = "m
     assert_eq!(deps.len(), 2);
 
     // Apply patches
-    let report = manager.apply_patches(&deps, false).unwrap();
+    let report = manager.apply_patches(&deps, false, false).unwrap();
     assert_eq!(report.patched_count, 2);
 
     // Verify both patches are in the same patch section
@@ -258,7 +259,7 @@ fn test_parse_git_dependency() {
 
     let result = PatchManager::parse_git_dependency("test-crate", &item);
     assert!(result.is_some());
-    let (url, tag) = result.unwrap();
+    let (url, tag, _) = result.unwrap();
     assert_eq!(url, "https://github.com/user/repo");
     assert_eq!(tag, Some("v1.0.0".to_string()));
 
@@ -270,12 +271,853 @@ fn test_parse_git_dependency() {
 
     let result = PatchManager::parse_git_dependency("test-crate", &item);
     assert!(result.is_some());
-    let (url, branch) = result.unwrap();
+    let (url, branch, _) = result.unwrap();
     assert_eq!(url, "https://github.com/user/repo");
     assert_eq!(branch, Some("main".to_string()));
 
+    // Test table format pinned to a rev
+    let mut table = toml_edit::Table::new();
+    table.insert("git", value("https://github.com/user/repo"));
+    table.insert("rev", value("abc123"));
+    let item = Item::Table(table);
+
+    let (url, branch_or_tag, rev) =
+        PatchManager::parse_git_dependency("test-crate", &item).unwrap();
+    assert_eq!(url, "https://github.com/user/repo");
+    assert_eq!(branch_or_tag, None);
+    assert_eq!(rev, Some("abc123".to_string()));
+
     // Test non-git dependency (version string)
     let item = value("1.0.0");
     let result = PatchManager::parse_git_dependency("test-crate", &item);
     assert!(result.is_none());
 }
+
+#[test]
+fn test_discover_with_sections() {
+    use crate::cargo::DependencyType;
+
+    let (_temp, root) = create_test_workspace();
+    let manager = PatchManager::new(&root).with_sections(&[DependencyType::Normal]);
+
+    let deps = manager.discover_patchable_dependencies().unwrap();
+
+    // embeddenator-retrieval is only a dev-dependency
+    assert_eq!(deps.len(), 3);
+    assert!(!deps.iter().any(|d| d.name == "embeddenator-retrieval"));
+
+    let manager = PatchManager::new(&root).with_sections(&[DependencyType::Dev]);
+    let deps = manager.discover_patchable_dependencies().unwrap();
+    assert_eq!(deps.len(), 1);
+    assert_eq!(deps[0].name, "embeddenator-retrieval");
+}
+
+#[test]
+fn test_apply_patches_config_diff() {
+    let (_temp, root) = create_test_workspace();
+    let manager = PatchManager::new(&root);
+
+    let cargo_dir = root.join(".cargo");
+    fs::create_dir_all(&cargo_dir).unwrap();
+    fs::write(
+        cargo_dir.join("config.toml"),
+        "[build]\ntarget-dir = \"custom-target\"\n",
+    )
+    .unwrap();
+
+    let deps = manager.discover_patchable_dependencies().unwrap();
+    let report = manager.apply_patches(&deps, false, false).unwrap();
+
+    let diff = &report.config_diff;
+    assert!(diff.starts_with("--- a/.cargo/config.toml"));
+    assert!(diff
+        .lines()
+        .any(|l| l.starts_with('+') && l.contains("path = ") && l.contains("embeddenator-vsa")));
+
+    // Existing settings are untouched: nothing is removed
+    assert!(!diff
+        .lines()
+        .any(|l| l.starts_with('-') && !l.starts_with("---")));
+}
+
+#[test]
+fn test_verify_roundtrip_lossless() {
+    let (_temp, root) = create_test_workspace();
+    let manager = PatchManager::new(&root);
+
+    // Without an existing config, nothing is left behind
+    let report = manager.verify_roundtrip().unwrap();
+    assert_eq!(report.patched_count, 4);
+    assert!(report.is_lossless(), "{:?}", report.differences);
+    assert!(!root.join(".cargo").exists());
+
+    // Existing config survives byte-for-byte
+    let cargo_dir = root.join(".cargo");
+    fs::create_dir_all(&cargo_dir).unwrap();
+    let config = "# local settings\n[build]\ntarget-dir = \"custom-target\"\n";
+    fs::write(cargo_dir.join("config.toml"), config).unwrap();
+
+    let report = manager.verify_roundtrip().unwrap();
+    assert!(report.is_lossless(), "{:?}", report.differences);
+    assert_eq!(
+        fs::read_to_string(cargo_dir.join("config.toml")).unwrap(),
+        config
+    );
+}
+
+#[test]
+fn test_verify_roundtrip_reports_preexisting_patches() {
+    let (_temp, root) = create_test_workspace();
+    let manager = PatchManager::new(&root);
+
+    let cargo_dir = root.join(".cargo");
+    fs::create_dir_all(&cargo_dir).unwrap();
+    let config = "[patch.\"https://example.com/other\"]\nother = { path = \"../other\" }\n";
+    fs::write(cargo_dir.join("config.toml"), config).unwrap();
+
+    // Reset removes every patch, including ones the tool didn't write
+    let report = manager.verify_roundtrip().unwrap();
+    assert!(!report.is_lossless());
+
+    // The original config is restored regardless
+    assert_eq!(
+        fs::read_to_string(cargo_dir.join("config.toml")).unwrap(),
+        config
+    );
+}
+
+#[test]
+fn test_patch_status_flags_bad_paths() {
+    let (_temp, root) = create_test_workspace();
+    let manager = PatchManager::new(&root);
+
+    let outside = TempDir::new().unwrap();
+    let cargo_dir = root.join(".cargo");
+    fs::create_dir_all(&cargo_dir).unwrap();
+    fs::write(
+        cargo_dir.join("config.toml"),
+        format!(
+            r#"[patch."https://github.com/tzervas/embeddenator-vsa"]
+embeddenator-vsa = {{ path = "embeddenator-vsa" }}
+embeddenator-fs = {{ path = "embeddenator-io/../embeddenator-fs" }}
+embeddenator-io = {{ path = "does-not-exist" }}
+embeddenator-retrieval = {{ path = "{}" }}
+"#,
+            outside.path().display()
+        ),
+    )
+    .unwrap();
+
+    let status = manager.patch_status().unwrap();
+    assert_eq!(status.entries.len(), 4);
+    assert_eq!(status.warnings.len(), 3, "{:?}", status.warnings);
+    assert!(
+        status.warnings[0].starts_with("embeddenator-fs")
+            && status.warnings[0].contains("not normalized")
+    );
+    assert!(
+        status.warnings[1].starts_with("embeddenator-io")
+            && status.warnings[1].contains("does not exist")
+    );
+    assert!(
+        status.warnings[2].starts_with("embeddenator-retrieval")
+            && status.warnings[2].contains("outside")
+    );
+}
+
+#[test]
+fn test_patch_status_reads_applied_patches() {
+    let (_temp, root) = create_test_workspace();
+    let manager = PatchManager::new(&root);
+
+    let deps = manager.discover_patchable_dependencies().unwrap();
+    manager.apply_patches(&deps, false, false).unwrap();
+
+    let status = manager.patch_status().unwrap();
+    assert_eq!(status.entries.len(), 4);
+    assert!(status.warnings.is_empty(), "{:?}", status.warnings);
+}
+
+#[test]
+fn test_remove_patches_dry_run() {
+    let (_temp, root) = create_test_workspace();
+    let manager = PatchManager::new(&root);
+
+    let cargo_dir = root.join(".cargo");
+    fs::create_dir_all(&cargo_dir).unwrap();
+
+    let config_content = r#"[build]
+target-dir = "custom-target"
+
+[patch."https://github.com/tzervas/embeddenator-vsa"]
+embeddenator-vsa = { path = "embeddenator-vsa" }
+"#;
+    let config_path = cargo_dir.join("config.toml");
+    fs::write(&config_path, config_content).unwrap();
+
+    let report = manager.remove_patches(true).unwrap();
+    assert!(report.dry_run);
+    assert_eq!(report.removed_count, 1);
+    assert!(!report.config_deleted);
+
+    // Nothing is written
+    assert_eq!(fs::read_to_string(&config_path).unwrap(), config_content);
+
+    let resulting = report.resulting_config.unwrap();
+    assert!(resulting.contains("target-dir = \"custom-target\""));
+    assert!(!resulting.contains("embeddenator-vsa"));
+}
+
+#[test]
+fn test_remove_patches_dry_run_reports_deletion() {
+    let (_temp, root) = create_test_workspace();
+    let manager = PatchManager::new(&root);
+
+    let deps = manager.discover_patchable_dependencies().unwrap();
+    manager.apply_patches(&deps, false, false).unwrap();
+
+    let report = manager.remove_patches(true).unwrap();
+    assert!(report.config_deleted);
+    assert!(report.resulting_config.is_none());
+    assert!(root.join(".cargo/config.toml").exists());
+}
+
+#[test]
+fn test_discover_with_notes() {
+    use crate::patch::DiscoveryReason;
+
+    let (_temp, root) = create_test_workspace();
+
+    // A package whose directory doesn't match its crate name
+    let renamed = root.join("vsa-renamed");
+    fs::create_dir_all(&renamed).unwrap();
+    fs::write(
+        renamed.join("Cargo.toml"),
+        "[package]\nname = \"embeddenator-obj\"\nversion = \"0.1.0\"\nedition = \"2021\"\n",
+    )
+    .unwrap();
+
+    let main = root.join("embeddenator/Cargo.toml");
+    let content = fs::read_to_string(&main).unwrap().replace(
+        "serde = \"1.0\"\n",
+        "serde = \"1.0\"\nembeddenator-obj = { git = \"https://github.com/tzervas/embeddenator-obj\" }\nembeddenator-missing = { git = \"https://github.com/tzervas/embeddenator-missing\" }\nembeddenator-cli = { path = \"../embeddenator-cli\" }\n",
+    );
+    fs::write(&main, content).unwrap();
+
+    let manager = PatchManager::new(&root);
+    let (deps, notes) = manager.discover_with_notes().unwrap();
+    assert_eq!(deps.len(), 4);
+
+    let reason = |dep: &str| notes.iter().find(|n| n.dependency == dep).unwrap().reason;
+    assert_eq!(reason("embeddenator-vsa"), DiscoveryReason::Patchable);
+    assert_eq!(reason("serde"), DiscoveryReason::NotEmbeddenator);
+    assert_eq!(reason("embeddenator-cli"), DiscoveryReason::NoGitSource);
+    assert_eq!(
+        reason("embeddenator-missing"),
+        DiscoveryReason::NoLocalPackage
+    );
+    assert_eq!(reason("embeddenator-obj"), DiscoveryReason::NameMismatch);
+
+    let retrieval = notes
+        .iter()
+        .find(|n| n.dependency == "embeddenator-retrieval")
+        .unwrap();
+    assert_eq!(retrieval.package, "embeddenator");
+    assert_eq!(retrieval.section, "dev-dependencies");
+}
+
+#[test]
+fn test_retarget_git() {
+    let (_temp, root) = create_test_workspace();
+    let manifest_path = root.join("embeddenator/Cargo.toml");
+    let original = fs::read_to_string(&manifest_path).unwrap();
+    let manager = PatchManager::new(&root);
+
+    let changed = manager
+        .retarget_git("embeddenator-vsa", GitRef::Tag("v0.2.0".to_string()))
+        .unwrap();
+    assert_eq!(changed, vec![manifest_path.clone()]);
+    let content = fs::read_to_string(&manifest_path).unwrap();
+    assert_eq!(
+        content,
+        original.replace("tag = \"v0.1.0\"", "tag = \"v0.2.0\"")
+    );
+
+    // Switching kinds drops the old reference
+    manager
+        .retarget_git("embeddenator-fs", GitRef::Rev("abc123".to_string()))
+        .unwrap();
+    let content = fs::read_to_string(&manifest_path).unwrap();
+    assert!(content.contains(
+        "embeddenator-fs = { git = \"https://github.com/tzervas/embeddenator-fs\", rev = \"abc123\" }"
+    ));
+
+    let deps = manager.discover_patchable_dependencies().unwrap();
+    let fs_dep = deps.iter().find(|d| d.name == "embeddenator-fs").unwrap();
+    assert_eq!(fs_dep.branch_or_tag, None);
+    assert_eq!(fs_dep.rev, Some("abc123".to_string()));
+
+    // Already at the requested reference
+    assert!(manager
+        .retarget_git("embeddenator-vsa", GitRef::Tag("v0.2.0".to_string()))
+        .unwrap()
+        .is_empty());
+
+    assert!(manager
+        .retarget_git("serde", GitRef::Tag("v1.0.0".to_string()))
+        .is_err());
+}
+
+#[test]
+fn test_discover_rev_pinned_dependency() {
+    let (_temp, root) = create_test_workspace();
+    let manifest_path = root.join("embeddenator/Cargo.toml");
+    let content = fs::read_to_string(&manifest_path).unwrap();
+    fs::write(
+        &manifest_path,
+        content.replace("tag = \"v0.1.1\"", "rev = \"9f8e7d6\""),
+    )
+    .unwrap();
+
+    let deps = PatchManager::new(&root)
+        .discover_patchable_dependencies()
+        .unwrap();
+    let io = deps.iter().find(|d| d.name == "embeddenator-io").unwrap();
+    assert_eq!(io.rev, Some("9f8e7d6".to_string()));
+    assert_eq!(io.branch_or_tag, None);
+
+    let vsa = deps.iter().find(|d| d.name == "embeddenator-vsa").unwrap();
+    assert_eq!(vsa.rev, None);
+}
+
+#[test]
+fn test_apply_patches_dry_run() {
+    let (_temp, root) = create_test_workspace();
+    let manager = PatchManager::new(&root);
+
+    let deps = manager.discover_patchable_dependencies().unwrap();
+    let report = manager.apply_patches(&deps, false, true).unwrap();
+
+    assert!(report.dry_run);
+    assert_eq!(report.patched_count, 4);
+    assert!(!root.join(".cargo").exists());
+
+    let names: Vec<&str> = report.entries.iter().map(|e| e.name.as_str()).collect();
+    assert_eq!(
+        names,
+        vec![
+            "embeddenator-fs",
+            "embeddenator-io",
+            "embeddenator-retrieval",
+            "embeddenator-vsa"
+        ]
+    );
+    assert!(report.config_diff.contains("+path = "));
+
+    // The blocks are exactly what a real run writes to a fresh config
+    let blocks = report.patch_blocks();
+    manager.apply_patches(&deps, false, false).unwrap();
+    assert_eq!(
+        fs::read_to_string(root.join(".cargo/config.toml")).unwrap(),
+        blocks
+    );
+}
+
+#[test]
+fn test_apply_patches_dry_run_keeps_existing_config() {
+    let (_temp, root) = create_test_workspace();
+    let config_path = root.join(".cargo/config.toml");
+    fs::create_dir_all(config_path.parent().unwrap()).unwrap();
+    let original = "[build]\ntarget-dir = \"custom\"\n";
+    fs::write(&config_path, original).unwrap();
+
+    let manager = PatchManager::new(&root);
+    let deps = manager.discover_patchable_dependencies().unwrap();
+    let report = manager.apply_patches(&deps, false, true).unwrap();
+
+    assert_eq!(fs::read_to_string(&config_path).unwrap(), original);
+    assert!(!report.patch_blocks().contains("[build]"));
+}
+
+#[test]
+fn test_patch_and_reset_selected_crates() {
+    let (_temp, root) = create_test_workspace();
+    let only = vec![
+        "embeddenator-vsa".to_string(),
+        "embeddenator-fs".to_string(),
+    ];
+    let manager = PatchManager::new(&root).with_only(&only);
+
+    let (deps, notes) = manager.discover_with_notes().unwrap();
+    let names: Vec<&str> = deps.iter().map(|d| d.name.as_str()).collect();
+    assert_eq!(names, vec!["embeddenator-fs", "embeddenator-vsa"]);
+    assert!(notes
+        .iter()
+        .any(|n| n.dependency == "embeddenator-io" && n.reason == DiscoveryReason::NotSelected));
+
+    let report = manager.apply_patches(&deps, false, false).unwrap();
+    assert_eq!(report.patched_count, 2);
+
+    // Resetting one crate removes its now-empty source table and keeps the rest
+    let reset = PatchManager::new(&root)
+        .with_only(&["embeddenator-vsa".to_string()])
+        .remove_patches(false)
+        .unwrap();
+    assert_eq!(reset.removed_count, 1);
+
+    let config_path = root.join(".cargo/config.toml");
+    let content = fs::read_to_string(&config_path).unwrap();
+    assert!(!content.contains("embeddenator-vsa"));
+    assert!(content.contains("embeddenator-fs"));
+
+    let reset = PatchManager::new(&root).remove_patches(false).unwrap();
+    assert_eq!(reset.removed_count, 1);
+    assert!(!config_path.exists());
+}
+
+#[test]
+fn test_reset_selected_crates_in_nested_patch_table() {
+    let (_temp, root) = create_test_workspace();
+    let config_path = root.join(".cargo/config.toml");
+    fs::create_dir_all(config_path.parent().unwrap()).unwrap();
+    fs::write(
+        &config_path,
+        r#"[build]
+jobs = 4
+
+[patch."https://github.com/tzervas/embeddenator-vsa"]
+embeddenator-vsa = { path = "../embeddenator-vsa" }
+
+[patch."https://github.com/tzervas/embeddenator-io"]
+embeddenator-io = { path = "../embeddenator-io" }
+embeddenator-io-macros = { path = "../embeddenator-io/macros" }
+"#,
+    )
+    .unwrap();
+
+    let only = vec![
+        "embeddenator-vsa".to_string(),
+        "embeddenator-io".to_string(),
+    ];
+    let reset = PatchManager::new(&root)
+        .with_only(&only)
+        .remove_patches(false)
+        .unwrap();
+    assert_eq!(reset.removed_count, 2);
+
+    let content = fs::read_to_string(&config_path).unwrap();
+    assert_eq!(
+        content,
+        r#"[build]
+jobs = 4
+
+[patch."https://github.com/tzervas/embeddenator-io"]
+embeddenator-io-macros = { path = "../embeddenator-io/macros" }
+"#
+    );
+}
+
+#[test]
+fn test_patch_workspace_manifest() {
+    let (_temp, root) = create_test_workspace();
+    let manifest_path = root.join("Cargo.toml");
+    let original = r#"# Workspace root
+[workspace]
+members = ["embeddenator", "embeddenator-vsa"]
+resolver = "2"
+
+[workspace.dependencies]
+serde = "1.0"
+
+[profile.release]
+lto = true
+"#;
+    fs::write(&manifest_path, original).unwrap();
+    let config_path = root.join(".cargo/config.toml");
+    fs::create_dir_all(config_path.parent().unwrap()).unwrap();
+    fs::write(&config_path, "[build]\njobs = 4\n").unwrap();
+
+    let manager = PatchManager::new(&root).with_target(PatchTarget::WorkspaceManifest);
+    let deps = manager.discover_patchable_dependencies().unwrap();
+    let report = manager.apply_patches(&deps, false, false).unwrap();
+
+    assert_eq!(report.patched_count, 4);
+    assert_eq!(report.config_path, manifest_path);
+    assert!(report.config_diff.starts_with("--- a/Cargo.toml"));
+    let patched = fs::read_to_string(&manifest_path).unwrap();
+    assert!(patched.starts_with(original));
+    assert!(patched.contains("https://github.com/tzervas/embeddenator-vsa"));
+    assert_eq!(
+        fs::read_to_string(&config_path).unwrap(),
+        "[build]\njobs = 4\n"
+    );
+    assert_eq!(manager.patch_status().unwrap().entries.len(), 4);
+
+    let reset = manager.remove_patches(false).unwrap();
+    assert_eq!(reset.removed_count, 4);
+    assert!(!reset.config_deleted);
+    assert_eq!(fs::read_to_string(&manifest_path).unwrap(), original);
+}
+
+#[test]
+fn test_patch_workspace_manifest_requires_root_manifest() {
+    let (_temp, root) = create_test_workspace();
+    let manager = PatchManager::new(&root).with_target(PatchTarget::WorkspaceManifest);
+
+    let deps = manager.discover_patchable_dependencies().unwrap();
+    assert!(manager.apply_patches(&deps, false, false).is_err());
+    assert!(!root.join("Cargo.toml").exists());
+}
+
+#[test]
+fn test_apply_patches_twice_is_idempotent() {
+    let (_temp, root) = create_test_workspace();
+    let manager = PatchManager::new(&root);
+    let config_path = root.join(".cargo/config.toml");
+
+    let deps = manager.discover_patchable_dependencies().unwrap();
+    let first = manager.apply_patches(&deps, false, false).unwrap();
+    assert_eq!((first.patched_count, first.unchanged_count), (4, 0));
+    let content = fs::read_to_string(&config_path).unwrap();
+
+    let second = manager.apply_patches(&deps, false, false).unwrap();
+    assert_eq!((second.patched_count, second.unchanged_count), (0, 4));
+    assert!(second.config_diff.is_empty());
+    assert_eq!(fs::read_to_string(&config_path).unwrap(), content);
+
+    // A redirected entry counts as patched again
+    let mut moved = deps.clone();
+    moved[0].local_path = root.join("elsewhere");
+    let third = manager.apply_patches(&moved, false, false).unwrap();
+    assert_eq!((third.patched_count, third.unchanged_count), (1, 3));
+}
+
+#[test]
+fn test_apply_patches_warns_on_unsatisfied_requirement() {
+    let (_temp, root) = create_test_workspace();
+    let manifest_path = root.join("embeddenator/Cargo.toml");
+    let content = fs::read_to_string(&manifest_path).unwrap();
+    fs::write(
+        &manifest_path,
+        content
+            .replace("tag = \"v0.1.0\"", "tag = \"v0.2.0\", version = \"0.2\"")
+            .replace("branch = \"main\"", "branch = \"main\", version = \"0.1\""),
+    )
+    .unwrap();
+
+    let manager = PatchManager::new(&root);
+    let deps = manager.discover_patchable_dependencies().unwrap();
+    let report = manager.apply_patches(&deps, false, true).unwrap();
+
+    assert_eq!(report.warnings.len(), 1);
+    assert!(report.warnings[0].starts_with("embeddenator requires embeddenator-vsa ^0.2"));
+    assert!(report.warnings[0].ends_with("is 0.1.0"));
+}
+
+#[test]
+fn test_find_and_prune_dangling_patches() {
+    let (_temp, root) = create_test_workspace();
+    let config_path = root.join(".cargo/config.toml");
+    fs::create_dir_all(config_path.parent().unwrap()).unwrap();
+    fs::write(
+        &config_path,
+        r#"[patch."https://github.com/tzervas/embeddenator-vsa"]
+embeddenator-vsa = { path = "embeddenator-vsa" }
+
+[patch."https://github.com/tzervas/embeddenator-gone"]
+embeddenator-gone = { path = "embeddenator-gone" }
+"#,
+    )
+    .unwrap();
+
+    let manager = PatchManager::new(&root);
+    let dangling = manager.find_dangling_patches().unwrap();
+    assert_eq!(
+        dangling,
+        vec![(
+            "embeddenator-gone".to_string(),
+            PathBuf::from("embeddenator-gone")
+        )]
+    );
+
+    let status = manager.patch_status().unwrap();
+    assert_eq!(status.entries.len(), 2);
+    assert_eq!(status.dangling, dangling);
+
+    let report = manager.prune_dangling_patches(false).unwrap();
+    assert_eq!(report.removed_count, 1);
+    assert_eq!(
+        fs::read_to_string(&config_path).unwrap(),
+        "[patch.\"https://github.com/tzervas/embeddenator-vsa\"]\nembeddenator-vsa = { path = \"embeddenator-vsa\" }\n"
+    );
+    assert!(manager.find_dangling_patches().unwrap().is_empty());
+}
+
+#[test]
+fn test_normalize_git_url() {
+    let expected = "https://github.com/tzervas/embeddenator-vsa";
+    for url in [
+        "https://github.com/tzervas/embeddenator-vsa",
+        "https://github.com/tzervas/embeddenator-vsa.git",
+        "https://github.com/tzervas/embeddenator-vsa/",
+        "https://GitHub.com/tzervas/embeddenator-vsa",
+        "git@github.com:tzervas/embeddenator-vsa.git",
+        "ssh://git@github.com/tzervas/embeddenator-vsa.git",
+        "ssh://git@github.com:22/tzervas/embeddenator-vsa",
+    ] {
+        assert_eq!(normalize_git_url(url), expected, "{}", url);
+    }
+
+    assert_eq!(
+        normalize_git_url("file:///srv/git/repo"),
+        "file:///srv/git/repo"
+    );
+}
+
+#[test]
+fn test_patch_source_keeps_scheme() {
+    assert_eq!(
+        patch_source("https://github.com/tzervas/embeddenator-vsa.git/"),
+        "https://github.com/tzervas/embeddenator-vsa"
+    );
+    assert_eq!(
+        patch_source("git@github.com:tzervas/embeddenator-vsa.git"),
+        "git@github.com:tzervas/embeddenator-vsa"
+    );
+    assert_eq!(
+        patch_source("ssh://git@github.com/tzervas/embeddenator-vsa"),
+        "ssh://git@github.com/tzervas/embeddenator-vsa"
+    );
+}
+
+#[test]
+fn test_patch_sections_keep_url_scheme() {
+    let temp_dir = TempDir::new().unwrap();
+    let root = temp_dir.path();
+
+    for repo in &["embeddenator-vsa", "embeddenator-fs", "embeddenator-io"] {
+        let repo_path = root.join(repo);
+        fs::create_dir_all(&repo_path).unwrap();
+        let manifest = format!(
+            r#"[package]
+name = "{}"
+version = "0.1.0"
+edition = "2021"
+"#,
+            repo
+        );
+        fs::write(repo_path.join("Cargo.toml"), manifest).unwrap();
+    }
+
+    // Consumers reference the same repository through different URL forms
+    for (pkg, dep, url) in [
+        (
+            "consumer-a",
+            "embeddenator-vsa",
+            "git@github.com:tzervas/embeddenator.git",
+        ),
+        (
+            "consumer-b",
+            "embeddenator-fs",
+            "https://github.com/tzervas/embeddenator.git",
+        ),
+        (
+            "consumer-c",
+            "embeddenator-io",
+            "https://github.com/tzervas/embeddenator/",
+        ),
+    ] {
+        let pkg_path = root.join(pkg);
+        fs::create_dir_all(&pkg_path).unwrap();
+        let manifest = format!(
+            r#"[package]
+name = "{}"
+version = "0.1.0"
+edition = "2021"
+
+[dependencies]
+{} = {{ git = "{}", branch = "main" }}
+"#,
+            pkg, dep, url
+        );
+        fs::write(pkg_path.join("Cargo.toml"), manifest).unwrap();
+    }
+
+    let manager = PatchManager::new(root);
+    let deps = manager.discover_patchable_dependencies().unwrap();
+    assert_eq!(deps.len(), 3);
+
+    let report = manager.apply_patches(&deps, false, false).unwrap();
+    assert_eq!(report.patched_count, 3);
+
+    let content = fs::read_to_string(root.join(".cargo/config.toml")).unwrap();
+    let doc: DocumentMut = content.parse().unwrap();
+
+    let patch_keys: Vec<&str> = doc
+        .iter()
+        .map(|(key, _)| key)
+        .filter(|key| key.starts_with("patch"))
+        .collect();
+    // Cargo won't apply an HTTPS-keyed patch to an SSH dependency, but
+    // ignores `.git` and a trailing slash
+    assert_eq!(
+        patch_keys,
+        vec![
+            "patch.\"git@github.com:tzervas/embeddenator\"",
+            "patch.\"https://github.com/tzervas/embeddenator\"",
+        ]
+    );
+
+    let ssh_section = doc.get(patch_keys[0]).unwrap();
+    assert!(ssh_section.get("embeddenator-vsa").is_some());
+    let https_section = doc.get(patch_keys[1]).unwrap();
+    assert!(https_section.get("embeddenator-fs").is_some());
+    assert!(https_section.get("embeddenator-io").is_some());
+    assert!(https_section.get("embeddenator-vsa").is_none());
+}
+
+#[test]
+fn test_apply_and_remove_patches_leave_no_temp_files() {
+    let (_temp, root) = create_test_workspace();
+    let manager = PatchManager::new(&root);
+    let cargo_dir = root.join(".cargo");
+    let list_cargo_dir = || -> Vec<String> {
+        fs::read_dir(&cargo_dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().to_string_lossy().into_owned())
+            .collect()
+    };
+
+    // Keep an unrelated setting around so reset rewrites rather than deletes
+    fs::create_dir_all(&cargo_dir).unwrap();
+    fs::write(cargo_dir.join("config.toml"), "[build]\njobs = 4\n").unwrap();
+
+    let deps = manager.discover_patchable_dependencies().unwrap();
+    manager.apply_patches(&deps, false, false).unwrap();
+    assert_eq!(list_cargo_dir(), vec!["config.toml"]);
+
+    manager.remove_patches(false).unwrap();
+    assert_eq!(list_cargo_dir(), vec!["config.toml"]);
+    assert_eq!(
+        fs::read_to_string(cargo_dir.join("config.toml")).unwrap(),
+        "[build]\njobs = 4\n"
+    );
+}
+
+#[test]
+fn test_discover_with_custom_prefix() {
+    let temp_dir = TempDir::new().unwrap();
+    let root = temp_dir.path();
+
+    let core = root.join("myorg-core");
+    fs::create_dir_all(&core).unwrap();
+    fs::write(
+        core.join("Cargo.toml"),
+        "[package]\nname = \"myorg-core\"\nversion = \"0.1.0\"\nedition = \"2021\"\n",
+    )
+    .unwrap();
+
+    let app = root.join("myorg-app");
+    fs::create_dir_all(&app).unwrap();
+    fs::write(
+        app.join("Cargo.toml"),
+        r#"[package]
+name = "myorg-app"
+version = "0.1.0"
+edition = "2021"
+
+[dependencies]
+myorg-core = { git = "https://github.com/myorg/myorg-core", branch = "main" }
+"#,
+    )
+    .unwrap();
+
+    let default_deps = PatchManager::new(root)
+        .discover_patchable_dependencies()
+        .unwrap();
+    assert!(default_deps.is_empty());
+
+    let deps = PatchManager::new(root)
+        .with_prefix("myorg-")
+        .discover_patchable_dependencies()
+        .unwrap();
+    assert_eq!(deps.len(), 1);
+    assert_eq!(deps[0].name, "myorg-core");
+    assert_eq!(deps[0].local_path, core);
+}
+
+#[test]
+fn test_verify_roundtrip_with_custom_prefix() {
+    let temp_dir = TempDir::new().unwrap();
+    let root = temp_dir.path();
+
+    let core = root.join("myorg-core");
+    fs::create_dir_all(&core).unwrap();
+    fs::write(
+        core.join("Cargo.toml"),
+        "[package]\nname = \"myorg-core\"\nversion = \"0.1.0\"\nedition = \"2021\"\n",
+    )
+    .unwrap();
+
+    let app = root.join("myorg-app");
+    fs::create_dir_all(&app).unwrap();
+    fs::write(
+        app.join("Cargo.toml"),
+        r#"[package]
+name = "myorg-app"
+version = "0.1.0"
+edition = "2021"
+
+[dependencies]
+myorg-core = { git = "https://github.com/myorg/myorg-core", branch = "main" }
+"#,
+    )
+    .unwrap();
+
+    let report = PatchManager::new(root)
+        .with_prefix("myorg-")
+        .verify_roundtrip()
+        .unwrap();
+    assert_eq!(report.patched_count, 1);
+    assert_eq!(report.removed_count, 1);
+    assert!(report.is_lossless(), "{:?}", report.differences);
+    assert!(!root.join(".cargo").exists());
+}
+
+#[test]
+fn test_reports_print_to_writer() {
+    colored::control::set_override(false);
+    let (_temp, root) = create_test_workspace();
+    let manager = PatchManager::new(&root);
+
+    let deps = manager.discover_patchable_dependencies().unwrap();
+    let report = manager.apply_patches(&deps, false, true).unwrap();
+    let mut out = Vec::new();
+    report.print_to(&mut out).unwrap();
+    let out = String::from_utf8(out).unwrap();
+    assert!(out.contains("4 patches would be written to"));
+
+    let reset = manager.remove_patches(false).unwrap();
+    let mut out = Vec::new();
+    reset.print_to(&mut out).unwrap();
+    assert_eq!(
+        String::from_utf8(out).unwrap(),
+        "Info: No patches found to remove\n"
+    );
+}
+
+#[test]
+fn test_verify_uses_configured_cargo_path() {
+    let (_temp, root) = create_test_workspace();
+    let manager = PatchManager::new(&root).with_cargo_path(root.join("no-such-cargo"));
+
+    let deps = manager.discover_patchable_dependencies().unwrap();
+    let report = manager.apply_patches(&deps, true, false).unwrap();
+
+    assert!(!report.verified);
+    assert_eq!(
+        report.verification_error.as_deref(),
+        Some("Failed to run cargo metadata")
+    );
+    assert!(manager.clean_cache().is_err());
+}
@@ -2,10 +2,14 @@
 
 use anyhow::{Context, Result};
 use semver::Version;
-use std::collections::HashMap;
-use std::path::Path;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::path::{Path, PathBuf};
 
-use crate::cargo::CargoManifest;
+use crate::cargo::{
+    CargoManifest, DependencyType, FormatStyle, ManifestTransaction, WorkspaceDependencies,
+};
+use crate::health::HealthChecker;
 use crate::workspace::WorkspaceScanner;
 
 /// Type of version bump to perform.
@@ -14,28 +18,142 @@ pub enum BumpType {
     Major,
     Minor,
     Patch,
+    /// Increment the prerelease: the rightmost numeric identifier goes up by
+    /// one (`alpha.1` → `alpha.2`, `x.7.z.92` → `x.7.z.93`); if there is none,
+    /// `.1` is appended (`alpha` → `alpha.1`, `alpha.beta` → `alpha.beta.1`).
+    /// A release version starts at `<label>.1`.
     Prerelease,
+    /// Move to the next prerelease stage: `alpha.N` → `beta.1` → `rc.1` → release.
+    PromotePrerelease,
 }
 
 /// Manages version updates across the workspace.
 pub struct VersionManager {
     scanner: WorkspaceScanner,
+    clear_build: bool,
+    build_metadata: Option<String>,
+    allowed_prerelease_labels: Vec<String>,
+    dependency_kinds: Vec<DependencyType>,
+    allow_downgrade: bool,
+    prerelease_label: String,
+    format_style: FormatStyle,
+    journal: bool,
 }
 
+/// Where [`VersionManager::with_journal`] records the last bump, relative to
+/// the workspace root.
+pub const JOURNAL_PATH: &str = ".embeddenator/last-bump.json";
+
+/// Initial content of a changelog created by [`VersionManager::prepend_changelog`].
+const CHANGELOG_SKELETON: &str = "# Changelog
+
+All notable changes to this project will be documented in this file.
+
+The format is based on [Keep a Changelog](https://keepachangelog.com/en/1.1.0/),
+and this project adheres to [Semantic Versioning](https://semver.org/spec/v2.0.0.html).
+
+## [Unreleased]
+";
+
+/// Prerelease labels allowed by default (`X.Y.Z-<label>.N`).
+pub const DEFAULT_PRERELEASE_LABELS: &[&str] = &["alpha", "beta", "rc"];
+
 impl VersionManager {
     /// Create a new version manager for the workspace.
     pub fn new(workspace_root: impl AsRef<Path>) -> Self {
         Self {
             scanner: WorkspaceScanner::new(workspace_root),
+            clear_build: false,
+            build_metadata: None,
+            allowed_prerelease_labels: DEFAULT_PRERELEASE_LABELS
+                .iter()
+                .map(|s| s.to_string())
+                .collect(),
+            dependency_kinds: vec![
+                DependencyType::Normal,
+                DependencyType::Dev,
+                DependencyType::Build,
+            ],
+            allow_downgrade: false,
+            prerelease_label: "alpha".to_string(),
+            format_style: FormatStyle::default(),
+            journal: false,
         }
     }
 
+    /// Restrict operations to the workspace's `default-members`, if declared.
+    pub fn with_default_members(mut self, enabled: bool) -> Self {
+        self.scanner = self.scanner.with_default_members(enabled);
+        self
+    }
+
+    /// Manage packages (and dependencies) whose name starts with `prefix`
+    /// (default: [`DEFAULT_PACKAGE_PREFIX`](crate::workspace::DEFAULT_PACKAGE_PREFIX)).
+    pub fn with_prefix(mut self, prefix: &str) -> Self {
+        self.scanner = self.scanner.with_prefix(prefix);
+        self
+    }
+
+    /// Set the prerelease labels that satisfy the workspace version convention.
+    pub fn with_allowed_prerelease_labels(mut self, labels: &[&str]) -> Self {
+        self.allowed_prerelease_labels = labels.iter().map(|s| s.to_string()).collect();
+        self
+    }
+
+    /// Limit the consistency check to the given dependency kinds (default: all).
+    pub fn with_dependency_kinds(mut self, kinds: &[DependencyType]) -> Self {
+        self.dependency_kinds = kinds.to_vec();
+        self
+    }
+
+    /// Clear build metadata (`+...`) when bumping.
+    ///
+    /// By default every bump keeps the current build metadata, since it often
+    /// encodes a git SHA or other traceability data.
+    pub fn with_clear_build(mut self, clear: bool) -> Self {
+        self.clear_build = clear;
+        self
+    }
+
+    /// Replace build metadata with `build` (e.g. `"git.abc123"`) when bumping.
+    ///
+    /// Takes precedence over [`VersionManager::with_clear_build`].
+    pub fn with_build_metadata(mut self, build: Option<&str>) -> Self {
+        self.build_metadata = build.map(String::from);
+        self
+    }
+
+    /// Set the label used when a prerelease bump starts from a release
+    /// version (default: `alpha`, giving `X.Y.Z-alpha.1`).
+    pub fn with_prerelease_label(mut self, label: &str) -> Self {
+        self.prerelease_label = label.to_string();
+        self
+    }
+
+    /// Set the whitespace style for rewritten versions, to match the
+    /// formatter a team runs on its manifests.
+    pub fn with_format_style(mut self, style: FormatStyle) -> Self {
+        self.format_style = style;
+        self
+    }
+
+    /// Allow [`VersionManager::set_versions`] and
+    /// [`VersionManager::apply_version_map`] to move packages to a lower version.
+    pub fn with_allow_downgrade(mut self, allow: bool) -> Self {
+        self.allow_downgrade = allow;
+        self
+    }
+
+    /// Record the versions a bump replaces in [`JOURNAL_PATH`] so it can be
+    /// undone with [`VersionManager::rollback`].
+    pub fn with_journal(mut self, enabled: bool) -> Self {
+        self.journal = enabled;
+        self
+    }
+
     /// Bump versions across all embeddenator packages.
     pub fn bump_versions(&self, bump_type: BumpType, dry_run: bool) -> Result<Vec<VersionChange>> {
-        let mut manifests = self
-            .scanner
-            .find_embeddenator_packages()
-            .context("Failed to find packages")?;
+        let mut manifests = self.packages_for_update()?;
 
         if manifests.is_empty() {
             anyhow::bail!("No embeddenator packages found in workspace");
@@ -64,15 +182,671 @@ impl VersionManager {
         if !dry_run {
             self.update_dependencies(&mut manifests, &changes)?;
 
-            // Save all changes
-            for manifest in manifests {
-                manifest.save()?;
+            // Save all changes, or none
+            let mut transaction = ManifestTransaction::new();
+            for manifest in &manifests {
+                transaction.stage_manifest(manifest);
             }
+            self.update_workspace_dependencies(&mut transaction, &changes)?;
+            transaction.commit()?;
+
+            self.write_journal(&changes)?;
         }
 
         Ok(changes)
     }
 
+    /// Set every embeddenator package to `target` and update inter-dependencies.
+    ///
+    /// Fails if `target` is lower than any package's current version, unless
+    /// downgrades were allowed with [`VersionManager::with_allow_downgrade`].
+    /// Build metadata is ignored when comparing.
+    pub fn set_versions(&self, target: &Version, dry_run: bool) -> Result<Vec<VersionChange>> {
+        let mut manifests = self.packages_for_update()?;
+
+        if manifests.is_empty() {
+            anyhow::bail!("No embeddenator packages found in workspace");
+        }
+
+        let changes: Vec<VersionChange> = manifests
+            .iter()
+            .map(|manifest| VersionChange {
+                package: manifest.package_name.clone(),
+                path: manifest.path.clone(),
+                old_version: manifest.version.clone(),
+                new_version: target.clone(),
+            })
+            .collect();
+
+        self.ensure_no_downgrades(&changes)?;
+
+        if !dry_run {
+            self.write_changes(&mut manifests, &changes)?;
+        }
+
+        Ok(changes)
+    }
+
+    /// Raise every package, and every inter-dependency, to the highest version
+    /// found among the embeddenator packages.
+    ///
+    /// Returns the target version and the packages that were raised. Fails if
+    /// packages share the highest version but disagree on build metadata.
+    pub fn align_to_highest(&self, dry_run: bool) -> Result<(Version, Vec<VersionChange>)> {
+        let mut manifests = self.packages_for_update()?;
+
+        let precedence = |v: &Version| (v.major, v.minor, v.patch, v.pre.clone());
+        let highest = manifests
+            .iter()
+            .map(|m| precedence(&m.version))
+            .max()
+            .ok_or_else(|| anyhow::anyhow!("No embeddenator packages found in workspace"))?;
+
+        let candidates: Vec<&CargoManifest> = manifests
+            .iter()
+            .filter(|m| precedence(&m.version) == highest)
+            .collect();
+        let target = candidates[0].version.clone();
+        if candidates.iter().any(|m| m.version.build != target.build) {
+            let conflicting: Vec<String> = candidates
+                .iter()
+                .map(|m| format!("{} ({})", m.package_name, m.version))
+                .collect();
+            anyhow::bail!(
+                "Cannot align: the highest version has conflicting build metadata: {}",
+                conflicting.join(", ")
+            );
+        }
+
+        let all: Vec<VersionChange> = manifests
+            .iter()
+            .map(|m| VersionChange {
+                package: m.package_name.clone(),
+                path: m.path.clone(),
+                old_version: m.version.clone(),
+                new_version: target.clone(),
+            })
+            .collect();
+        let raised: Vec<VersionChange> = all
+            .iter()
+            .filter(|c| c.old_version != target)
+            .cloned()
+            .collect();
+
+        if !dry_run {
+            for manifest in &mut manifests {
+                if manifest.version != target {
+                    manifest.set_version(&target)?;
+                }
+            }
+
+            // Requirements on packages already at the target may be stale too
+            self.update_dependencies(&mut manifests, &all)?;
+
+            let mut transaction = ManifestTransaction::new();
+            for manifest in &manifests {
+                transaction.stage_manifest(manifest);
+            }
+            self.update_workspace_dependencies(&mut transaction, &all)?;
+            transaction.commit()?;
+
+            self.write_journal(&raised)?;
+        }
+
+        Ok((target, raised))
+    }
+
+    /// Move each package named in `map` to its mapped version and update
+    /// inter-dependencies.
+    ///
+    /// Packages not in the map keep their version, and mapped packages that
+    /// are already at their version are not reported. Fails if the map names
+    /// an unknown package or, unless downgrades are allowed, lowers a version.
+    pub fn apply_version_map(
+        &self,
+        map: &BTreeMap<String, Version>,
+        dry_run: bool,
+    ) -> Result<Vec<VersionChange>> {
+        let mut manifests = self.packages_for_update()?;
+
+        let unknown: Vec<&str> = map
+            .keys()
+            .filter(|name| !manifests.iter().any(|m| &m.package_name == *name))
+            .map(String::as_str)
+            .collect();
+        if !unknown.is_empty() {
+            anyhow::bail!("Unknown package(s) in version map: {}", unknown.join(", "));
+        }
+
+        let changes: Vec<VersionChange> = manifests
+            .iter()
+            .filter_map(|manifest| {
+                let new_version = map.get(&manifest.package_name)?;
+                (*new_version != manifest.version).then(|| VersionChange {
+                    package: manifest.package_name.clone(),
+                    path: manifest.path.clone(),
+                    old_version: manifest.version.clone(),
+                    new_version: new_version.clone(),
+                })
+            })
+            .collect();
+
+        self.ensure_no_downgrades(&changes)?;
+
+        if !dry_run && !changes.is_empty() {
+            self.write_changes(&mut manifests, &changes)?;
+        }
+
+        Ok(changes)
+    }
+
+    /// Point dependency requirements on the packages in `versions` at the
+    /// given versions, across every manifest under the workspace root.
+    ///
+    /// Package versions are never touched, which suits downstream repositories
+    /// that consume released embeddenator crates. Dependencies without a
+    /// version (path or git only) and inherited `workspace = true` entries are
+    /// skipped; the latter are updated through `[workspace.dependencies]`.
+    pub fn align_dependencies_to(
+        &self,
+        versions: &HashMap<String, Version>,
+        dry_run: bool,
+    ) -> Result<Vec<RequirementChange>> {
+        let mut manifests = self
+            .scanner
+            .find_manifests()
+            .context("Failed to find manifests")?;
+        let mut changes = Vec::new();
+        let mut transaction = ManifestTransaction::new();
+
+        for manifest in &mut manifests {
+            manifest.set_format_style(self.format_style);
+
+            let mut outdated: Vec<(String, String, Version)> = Vec::new();
+            for dep in &manifest.dependencies {
+                let (Some(target), Some(req)) = (versions.get(&dep.name), &dep.req) else {
+                    continue;
+                };
+                if dep.workspace
+                    || dep.version.as_ref() == Some(target)
+                    || outdated.iter().any(|(name, _, _)| name == &dep.name)
+                {
+                    continue;
+                }
+                outdated.push((dep.name.clone(), req.clone(), target.clone()));
+            }
+
+            if outdated.is_empty() {
+                continue;
+            }
+
+            for (dependency, old_req, new_version) in outdated {
+                if !dry_run {
+                    manifest.update_dependency(&dependency, &new_version)?;
+                }
+                changes.push(RequirementChange {
+                    package: manifest.package_name.clone(),
+                    path: manifest.path.clone(),
+                    dependency,
+                    old_req,
+                    new_version,
+                });
+            }
+
+            transaction.stage_manifest(manifest);
+        }
+
+        if let Some(mut root_deps) = self.staged_workspace_dependencies(&transaction)? {
+            root_deps.set_format_style(self.format_style);
+
+            let mut updated = false;
+            for (name, target) in versions {
+                let Some(old_req) = root_deps.req(name).map(String::from) else {
+                    continue;
+                };
+                if root_deps.version(name).as_ref() == Some(target) {
+                    continue;
+                }
+
+                if !dry_run {
+                    updated |= root_deps.set_version(name, target);
+                }
+                changes.push(RequirementChange {
+                    package: WORKSPACE_DEPENDENCIES.to_string(),
+                    path: self.scanner.root().join("Cargo.toml"),
+                    dependency: name.clone(),
+                    old_req,
+                    new_version: target.clone(),
+                });
+            }
+
+            if updated {
+                transaction.stage_workspace_dependencies(&root_deps);
+            }
+        }
+
+        if !dry_run {
+            transaction.commit()?;
+        }
+
+        changes.sort_by(|a, b| (&a.path, &a.dependency).cmp(&(&b.path, &b.dependency)));
+        Ok(changes)
+    }
+
+    /// Read a version map file: a TOML table of `name = "x.y.z"` entries.
+    pub fn load_version_map(path: impl AsRef<Path>) -> Result<BTreeMap<String, Version>> {
+        let path = path.as_ref();
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        let document: toml_edit::DocumentMut = content
+            .parse()
+            .with_context(|| format!("Failed to parse {}", path.display()))?;
+
+        document
+            .iter()
+            .map(|(name, item)| {
+                let version = item.as_str().ok_or_else(|| {
+                    anyhow::anyhow!(
+                        "{}: version for '{}' must be a string",
+                        path.display(),
+                        name
+                    )
+                })?;
+                let version = Version::parse(version).with_context(|| {
+                    format!("{}: invalid version for '{}'", path.display(), name)
+                })?;
+                Ok((name.to_string(), version))
+            })
+            .collect()
+    }
+
+    /// Fail if any change lowers a version, unless downgrades are allowed.
+    /// Build metadata is ignored when comparing.
+    fn ensure_no_downgrades(&self, changes: &[VersionChange]) -> Result<()> {
+        if self.allow_downgrade {
+            return Ok(());
+        }
+
+        let precedence = |v: &Version| (v.major, v.minor, v.patch, v.pre.clone());
+        let lowered: Vec<String> = changes
+            .iter()
+            .filter(|c| precedence(&c.old_version) > precedence(&c.new_version))
+            .map(|c| format!("{} ({}) to {}", c.package, c.old_version, c.new_version))
+            .collect();
+        if !lowered.is_empty() {
+            anyhow::bail!(
+                "Refusing to downgrade {} (allow downgrades to override)",
+                lowered.join(", ")
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Apply `changes` to the loaded manifests, update inter-dependencies and
+    /// save everything, including the root `[workspace.dependencies]`.
+    fn write_changes(
+        &self,
+        manifests: &mut [CargoManifest],
+        changes: &[VersionChange],
+    ) -> Result<()> {
+        for manifest in manifests.iter_mut() {
+            if let Some(change) = changes.iter().find(|c| c.path == manifest.path) {
+                manifest.set_version(&change.new_version)?;
+            }
+        }
+
+        self.update_dependencies(manifests, changes)?;
+
+        let mut transaction = ManifestTransaction::new();
+        for manifest in manifests.iter() {
+            transaction.stage_manifest(manifest);
+        }
+        self.update_workspace_dependencies(&mut transaction, changes)?;
+        transaction.commit()?;
+
+        self.write_journal(changes)
+    }
+
+    /// Bump a single package and update the manifests that depend on it.
+    ///
+    /// Dependents that reference the package without a version (path or git
+    /// only) are left untouched and listed in [`PackageBump::skipped`].
+    /// Inherited `workspace = true` dependencies are updated through the root
+    /// manifest.
+    pub fn bump_package(
+        &self,
+        package_name: &str,
+        bump_type: BumpType,
+        dry_run: bool,
+    ) -> Result<PackageBump> {
+        let mut manifests = self.packages_for_update()?;
+
+        let target = manifests
+            .iter()
+            .position(|m| m.package_name == package_name)
+            .ok_or_else(|| anyhow::anyhow!("Package '{}' not found in workspace", package_name))?;
+
+        let old_version = manifests[target].version.clone();
+        let new_version = self.calculate_new_version(&old_version, bump_type)?;
+        let changes = vec![VersionChange {
+            package: manifests[target].package_name.clone(),
+            path: manifests[target].path.clone(),
+            old_version,
+            new_version: new_version.clone(),
+        }];
+
+        let skipped = manifests
+            .iter()
+            .filter(|manifest| {
+                manifest
+                    .dependencies_with_prefix(self.scanner.prefix())
+                    .into_iter()
+                    .any(|d| d.name == package_name && d.version.is_none() && !d.workspace)
+            })
+            .map(|manifest| {
+                format!(
+                    "{}: depends on {} without a version",
+                    manifest.package_name, package_name
+                )
+            })
+            .collect();
+
+        if !dry_run {
+            manifests[target].set_version(&new_version)?;
+            self.update_dependencies(&mut manifests, &changes)?;
+
+            let mut transaction = ManifestTransaction::new();
+            for manifest in manifests.iter().filter(|m| {
+                m.package_name == package_name
+                    || m.dependencies.iter().any(|d| d.name == package_name)
+            }) {
+                transaction.stage_manifest(manifest);
+            }
+            self.update_workspace_dependencies(&mut transaction, &changes)?;
+            transaction.commit()?;
+
+            self.write_journal(&changes)?;
+        }
+
+        Ok(PackageBump { changes, skipped })
+    }
+
+    /// Undo the bump recorded in [`JOURNAL_PATH`] and delete the journal.
+    ///
+    /// Each package is restored to its old version, along with dependency
+    /// requirements and `[workspace.dependencies]` entries that still name the
+    /// bumped version. Manifests whose version has changed since the bump are
+    /// skipped rather than overwritten. Returns `None` if there is no journal.
+    pub fn rollback(&self) -> Result<Option<RollbackReport>> {
+        let journal_path = self.scanner.root().join(JOURNAL_PATH);
+        if !journal_path.is_file() {
+            return Ok(None);
+        }
+
+        let content = std::fs::read_to_string(&journal_path)
+            .with_context(|| format!("Failed to read {}", journal_path.display()))?;
+        let journal: BumpJournal = serde_json::from_str(&content)
+            .with_context(|| format!("Failed to parse {}", journal_path.display()))?;
+
+        let mut manifests = self.packages_for_update()?;
+        let mut touched = vec![false; manifests.len()];
+        let mut report = RollbackReport::default();
+
+        for entry in journal.changes {
+            let old_version = Version::parse(&entry.old_version)
+                .with_context(|| format!("Invalid version in journal for {}", entry.package))?;
+            let new_version = Version::parse(&entry.new_version)
+                .with_context(|| format!("Invalid version in journal for {}", entry.package))?;
+
+            let Some(index) = manifests.iter().position(|m| m.path == entry.path) else {
+                report.skipped.push(format!(
+                    "{}: {} is no longer in the workspace",
+                    entry.package,
+                    entry.path.display()
+                ));
+                continue;
+            };
+
+            let manifest = &mut manifests[index];
+            if manifest.version != new_version {
+                report.skipped.push(format!(
+                    "{}: version is {}, expected {} (changed since the bump)",
+                    entry.package, manifest.version, new_version
+                ));
+                continue;
+            }
+
+            manifest.set_version(&old_version)?;
+            touched[index] = true;
+            report.restored.push(VersionChange {
+                package: entry.package,
+                path: entry.path,
+                old_version: new_version,
+                new_version: old_version,
+            });
+        }
+
+        // Only revert requirements that still point at the bumped version.
+        for (index, manifest) in manifests.iter_mut().enumerate() {
+            let deps_to_restore: Vec<(String, Version)> = manifest
+                .dependencies_with_prefix(self.scanner.prefix())
+                .iter()
+                .filter_map(|dep| {
+                    report
+                        .restored
+                        .iter()
+                        .find(|r| {
+                            r.package == dep.name
+                                && !dep.workspace
+                                && dep.version.as_ref() == Some(&r.old_version)
+                        })
+                        .map(|r| (dep.name.clone(), r.new_version.clone()))
+                })
+                .collect();
+
+            for (dep_name, version) in deps_to_restore {
+                manifest.update_dependency(&dep_name, &version)?;
+                touched[index] = true;
+            }
+        }
+
+        let mut transaction = ManifestTransaction::new();
+        for (manifest, touched) in manifests.iter().zip(touched) {
+            if touched {
+                transaction.stage_manifest(manifest);
+            }
+        }
+
+        if let Some(mut root_deps) = self.staged_workspace_dependencies(&transaction)? {
+            root_deps.set_format_style(self.format_style);
+
+            let mut updated = false;
+            for restored in &report.restored {
+                if root_deps.version(&restored.package).as_ref() == Some(&restored.old_version) {
+                    updated |= root_deps.set_version(&restored.package, &restored.new_version);
+                }
+            }
+
+            if updated {
+                transaction.stage_workspace_dependencies(&root_deps);
+            }
+        }
+
+        transaction.commit()?;
+
+        std::fs::remove_file(&journal_path)
+            .with_context(|| format!("Failed to remove {}", journal_path.display()))?;
+
+        Ok(Some(report))
+    }
+
+    /// Write the journal for `changes` if journaling is enabled.
+    ///
+    /// Written once the manifests are committed, so it only ever describes a
+    /// bump that actually happened.
+    fn write_journal(&self, changes: &[VersionChange]) -> Result<()> {
+        if !self.journal {
+            return Ok(());
+        }
+
+        let journal = BumpJournal {
+            changes: changes
+                .iter()
+                .map(|c| JournalEntry {
+                    package: c.package.clone(),
+                    path: c.path.clone(),
+                    old_version: c.old_version.to_string(),
+                    new_version: c.new_version.to_string(),
+                })
+                .collect(),
+        };
+
+        let journal_path = self.scanner.root().join(JOURNAL_PATH);
+        if let Some(parent) = journal_path.parent() {
+            std::fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create {}", parent.display()))?;
+        }
+        std::fs::write(&journal_path, serde_json::to_string_pretty(&journal)?)
+            .with_context(|| format!("Failed to write {}", journal_path.display()))?;
+
+        Ok(())
+    }
+
+    /// Infer the bump type from conventional commits since `since`.
+    ///
+    /// See [`VersionManager::analyze_commits`] for the rules.
+    pub fn infer_bump_from_commits(&self, since: Option<&str>) -> Result<BumpType> {
+        Ok(self.analyze_commits(since)?.bump)
+    }
+
+    /// Analyze conventional commits since `since` (or the latest tag) in every
+    /// package repository and return the strongest bump implied.
+    ///
+    /// `type!:` or a `BREAKING CHANGE` footer means major, `feat` minor, and
+    /// anything else patch. The commits responsible for the chosen bump are
+    /// listed in [`CommitAnalysis::drivers`].
+    pub fn analyze_commits(&self, since: Option<&str>) -> Result<CommitAnalysis> {
+        let manifests = self
+            .scanner
+            .find_embeddenator_packages()
+            .context("Failed to find packages")?;
+
+        let mut repo_paths: Vec<PathBuf> = Vec::new();
+        for manifest in &manifests {
+            let pkg_dir = manifest.path.parent().unwrap_or_else(|| Path::new("."));
+            if let Ok(repo) = git2::Repository::discover(pkg_dir) {
+                let path = repo.path().to_path_buf();
+                if !repo_paths.contains(&path) {
+                    repo_paths.push(path);
+                }
+            }
+        }
+
+        if repo_paths.is_empty() {
+            anyhow::bail!("No git repositories found for embeddenator packages");
+        }
+
+        let mut analysis = CommitAnalysis {
+            bump: BumpType::Patch,
+            commit_count: 0,
+            drivers: Vec::new(),
+        };
+
+        for repo_path in &repo_paths {
+            let repo = git2::Repository::open(repo_path)
+                .with_context(|| format!("Failed to open {}", repo_path.display()))?;
+
+            for summary in Self::commits_since(&repo, since)? {
+                analysis.commit_count += 1;
+                let bump = Self::conventional_bump(&summary);
+
+                if bump_rank(bump) > bump_rank(analysis.bump) {
+                    analysis.bump = bump;
+                    analysis.drivers.clear();
+                }
+                if bump == analysis.bump {
+                    analysis
+                        .drivers
+                        .push(summary.lines().next().unwrap_or_default().to_string());
+                }
+            }
+        }
+
+        if analysis.commit_count == 0 {
+            anyhow::bail!(
+                "No commits found since {}",
+                since.unwrap_or("the latest tag")
+            );
+        }
+
+        Ok(analysis)
+    }
+
+    /// Full messages of commits reachable from HEAD but not from `since`.
+    ///
+    /// Without `since`, walks back to the most recent tagged commit.
+    fn commits_since(repo: &git2::Repository, since: Option<&str>) -> Result<Vec<String>> {
+        let mut walk = repo.revwalk()?;
+        walk.push_head().context("Failed to read HEAD")?;
+
+        let mut tagged = std::collections::HashSet::new();
+        match since {
+            Some(reference) => {
+                let object = repo
+                    .revparse_single(reference)
+                    .with_context(|| format!("Unknown git ref '{}'", reference))?;
+                walk.hide(object.peel_to_commit()?.id())?;
+            }
+            None => {
+                for name in repo.tag_names(None)?.iter().flatten() {
+                    if let Ok(object) = repo.revparse_single(name) {
+                        if let Ok(commit) = object.peel_to_commit() {
+                            tagged.insert(commit.id());
+                        }
+                    }
+                }
+            }
+        }
+
+        let mut messages = Vec::new();
+        for oid in walk {
+            let oid = oid?;
+            if tagged.contains(&oid) {
+                break;
+            }
+            let commit = repo.find_commit(oid)?;
+            messages.push(commit.message().unwrap_or_default().to_string());
+        }
+
+        Ok(messages)
+    }
+
+    /// Bump implied by a single conventional commit message.
+    fn conventional_bump(message: &str) -> BumpType {
+        let subject = message.lines().next().unwrap_or_default();
+        let prefix = subject.split(':').next().unwrap_or_default();
+
+        let breaking = subject.contains(':') && prefix.ends_with('!')
+            || message.contains("BREAKING CHANGE")
+            || message.contains("BREAKING-CHANGE");
+
+        let commit_type = prefix
+            .trim_end_matches('!')
+            .split('(')
+            .next()
+            .unwrap_or_default()
+            .trim();
+
+        if breaking {
+            BumpType::Major
+        } else if subject.contains(':') && commit_type == "feat" {
+            BumpType::Minor
+        } else {
+            BumpType::Patch
+        }
+    }
+
     fn calculate_new_version(&self, current: &Version, bump_type: BumpType) -> Result<Version> {
         let mut new_version = current.clone();
 
@@ -94,26 +868,64 @@ impl VersionManager {
             }
             BumpType::Prerelease => {
                 if new_version.pre.is_empty() {
-                    // Start with alpha.1
-                    new_version.pre = "alpha.1".parse()?;
+                    // Start with <label>.1
+                    new_version.pre = format!("{}.1", self.prerelease_label)
+                        .parse()
+                        .with_context(|| {
+                            format!("Invalid prerelease label '{}'", self.prerelease_label)
+                        })?;
                 } else {
-                    // Increment prerelease number
-                    let pre_str = new_version.pre.as_str();
-
-                    // Parse "alpha.1" -> increment to "alpha.2"
-                    if let Some((prefix, num_str)) = pre_str.rsplit_once('.') {
-                        if let Ok(num) = num_str.parse::<u64>() {
-                            new_version.pre = format!("{}.{}", prefix, num + 1).parse()?;
-                        } else {
-                            // No number, add .1
-                            new_version.pre = format!("{}.1", pre_str).parse()?;
+                    let mut identifiers: Vec<String> =
+                        new_version.pre.split('.').map(String::from).collect();
+
+                    // Increment the rightmost numeric identifier, or append .1
+                    match identifiers
+                        .iter_mut()
+                        .rev()
+                        .find(|id| id.bytes().all(|b| b.is_ascii_digit()))
+                    {
+                        Some(id) => {
+                            let num: u64 = id.parse().with_context(|| {
+                                format!("Prerelease number too large in {}", current)
+                            })?;
+                            *id = (num + 1).to_string();
                         }
-                    } else {
-                        // No dot, add .1
-                        new_version.pre = format!("{}.1", pre_str).parse()?;
+                        None => identifiers.push("1".to_string()),
                     }
+
+                    new_version.pre = identifiers.join(".").parse()?;
                 }
             }
+            BumpType::PromotePrerelease => {
+                if new_version.pre.is_empty() {
+                    anyhow::bail!("Cannot promote {}: it is not a prerelease version", current);
+                }
+
+                let label = new_version
+                    .pre
+                    .as_str()
+                    .split('.')
+                    .next()
+                    .unwrap_or_default();
+                match label {
+                    "alpha" => new_version.pre = "beta.1".parse()?,
+                    "beta" => new_version.pre = "rc.1".parse()?,
+                    "rc" => new_version.pre = semver::Prerelease::EMPTY,
+                    _ => anyhow::bail!(
+                        "Cannot promote {}: unknown prerelease stage '{}' (expected alpha, beta, or rc)",
+                        current,
+                        label
+                    ),
+                }
+            }
+        }
+
+        // Build metadata carries through every bump unless replaced or cleared
+        if let Some(build) = &self.build_metadata {
+            new_version.build = semver::BuildMetadata::new(build)
+                .with_context(|| format!("Invalid build metadata '{}'", build))?;
+        } else if self.clear_build {
+            new_version.build = semver::BuildMetadata::EMPTY;
         }
 
         Ok(new_version)
@@ -132,7 +944,7 @@ impl VersionManager {
         for manifest in manifests {
             // Collect dependency names that need updating
             let deps_to_update: Vec<(String, Version)> = manifest
-                .embeddenator_dependencies()
+                .dependencies_with_prefix(self.scanner.prefix())
                 .iter()
                 .filter_map(|dep| {
                     version_map
@@ -150,23 +962,308 @@ impl VersionManager {
         Ok(())
     }
 
+    /// Stage updated `[workspace.dependencies]` entries in the root manifest
+    /// for the bumped packages.
+    fn update_workspace_dependencies(
+        &self,
+        transaction: &mut ManifestTransaction,
+        changes: &[VersionChange],
+    ) -> Result<()> {
+        let Some(mut root_deps) = self.staged_workspace_dependencies(transaction)? else {
+            return Ok(());
+        };
+        root_deps.set_format_style(self.format_style);
+
+        let mut updated = false;
+        for change in changes {
+            updated |= root_deps.set_version(&change.package, &change.new_version);
+        }
+
+        if updated {
+            transaction.stage_workspace_dependencies(&root_deps);
+        }
+
+        Ok(())
+    }
+
+    /// Load the root `[workspace.dependencies]`, starting from the staged
+    /// contents if the root is itself a package being rewritten.
+    fn staged_workspace_dependencies(
+        &self,
+        transaction: &ManifestTransaction,
+    ) -> Result<Option<WorkspaceDependencies>> {
+        let path = self.scanner.root().join("Cargo.toml");
+        match transaction.staged(&path) {
+            Some(contents) => WorkspaceDependencies::parse(&path, contents),
+            None => WorkspaceDependencies::load(&path),
+        }
+    }
+
+    /// Load the package manifests that a bump will rewrite.
+    fn packages_for_update(&self) -> Result<Vec<CargoManifest>> {
+        let mut manifests = self
+            .scanner
+            .find_embeddenator_packages()
+            .context("Failed to find packages")?;
+
+        for manifest in &mut manifests {
+            manifest.set_format_style(self.format_style);
+        }
+
+        Ok(manifests)
+    }
+
+    /// The single version shared by every embeddenator package, if there is one.
+    ///
+    /// Returns `None` when packages diverge or no packages are found.
+    pub fn common_version(&self) -> Result<Option<Version>> {
+        let manifests = self
+            .scanner
+            .find_embeddenator_packages()
+            .context("Failed to find packages")?;
+
+        let mut versions = manifests.iter().map(|m| &m.version);
+        let Some(first) = versions.next() else {
+            return Ok(None);
+        };
+
+        if versions.all(|v| v == first) {
+            Ok(Some(first.clone()))
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// Report each package's current version and whether it is already tagged.
+    ///
+    /// A package counts as tagged when its git repository has either a global
+    /// `v<version>` tag or a per-crate `<name>-v<version>` tag. A tagged version
+    /// indicates a release was cut without a subsequent bump.
+    pub fn untagged_packages(&self) -> Result<Vec<(String, Version, bool)>> {
+        let manifests = self
+            .scanner
+            .find_embeddenator_packages()
+            .context("Failed to find packages")?;
+
+        let mut packages = Vec::new();
+
+        for manifest in &manifests {
+            let pkg_dir = manifest.path.parent().unwrap_or_else(|| Path::new("."));
+            let tags = Self::repo_tags(pkg_dir)?;
+
+            let global_tag = format!("v{}", manifest.version);
+            let crate_tag = format!("{}-v{}", manifest.package_name, manifest.version);
+            let tagged = tags.iter().any(|t| *t == global_tag || *t == crate_tag);
+
+            packages.push((
+                manifest.package_name.clone(),
+                manifest.version.clone(),
+                tagged,
+            ));
+        }
+
+        Ok(packages)
+    }
+
+    /// Add a `## [<version>] - <date>` section to `CHANGELOG.md` at the
+    /// workspace root, creating a Keep a Changelog skeleton if needed.
+    ///
+    /// The section goes above the newest release (below `## [Unreleased]`).
+    /// Returns `false` without writing if the version already has an entry.
+    pub fn prepend_changelog(&self, version: &Version, date: &str) -> Result<bool> {
+        let path = self.scanner.root().join("CHANGELOG.md");
+        let content = if path.exists() {
+            std::fs::read_to_string(&path)
+                .with_context(|| format!("Failed to read {}", path.display()))?
+        } else {
+            CHANGELOG_SKELETON.to_string()
+        };
+
+        let heading = format!("## [{}]", version);
+        if content
+            .lines()
+            .any(|line| line == heading || line.starts_with(&format!("{} ", heading)))
+        {
+            return Ok(false);
+        }
+
+        let entry = format!(
+            "{} - {}\n\n### Changed\n- Bumped version to {}\n\n",
+            heading, date, version
+        );
+
+        // Insert before the first release heading, or append
+        let mut offset = 0;
+        let mut insert_at = None;
+        for line in content.split_inclusive('\n') {
+            if line.starts_with("## [") && !line.starts_with("## [Unreleased]") {
+                insert_at = Some(offset);
+                break;
+            }
+            offset += line.len();
+        }
+
+        let mut updated = content.clone();
+        match insert_at {
+            Some(index) => updated.insert_str(index, &entry),
+            None => {
+                if !updated.ends_with("\n\n") {
+                    updated.push_str(if updated.ends_with('\n') {
+                        "\n"
+                    } else {
+                        "\n\n"
+                    });
+                }
+                updated.push_str(entry.trim_end());
+                updated.push('\n');
+            }
+        }
+
+        std::fs::write(&path, updated)
+            .with_context(|| format!("Failed to write {}", path.display()))?;
+
+        Ok(true)
+    }
+
+    /// Fail unless the workspace root repository has a clean working tree.
+    ///
+    /// The bump journal is ignored, since it is never committed. `action`
+    /// names what is being refused in the error.
+    pub fn ensure_clean_tree(&self, action: &str) -> Result<()> {
+        let root = self.scanner.root();
+        let status = HealthChecker::get_git_status_static(root)
+            .with_context(|| format!("Failed to read git status of {}", root.display()))?;
+        let dirty = status
+            .dirty_files
+            .iter()
+            .filter(|f| !Path::new(JOURNAL_PATH).starts_with(f.trim_end_matches('/')))
+            .count();
+        if dirty > 0 {
+            anyhow::bail!(
+                "Refusing to {}: working tree of {} is dirty ({} file(s)); commit the changes first",
+                action,
+                root.display(),
+                dirty
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Commit every modified tracked file, plus `CHANGELOG.md`, in the
+    /// workspace root repository.
+    ///
+    /// Used to record a bump before tagging it. Returns the new commit id.
+    pub fn commit_release(&self, message: &str) -> Result<git2::Oid> {
+        let root = self.scanner.root();
+        let repo = git2::Repository::open(root).context("Failed to open git repository")?;
+
+        let mut index = repo.index().context("Failed to read git index")?;
+        index
+            .update_all(["*"], None)
+            .context("Failed to stage modified files")?;
+        let changelog = Path::new("CHANGELOG.md");
+        if root.join(changelog).exists() && !repo.status_should_ignore(changelog)? {
+            index
+                .add_path(changelog)
+                .context("Failed to stage CHANGELOG.md")?;
+        }
+        index.write().context("Failed to write git index")?;
+
+        let tree = repo
+            .find_tree(index.write_tree().context("Failed to write tree")?)
+            .context("Failed to find tree")?;
+        let parent = repo
+            .head()
+            .and_then(|h| h.peel_to_commit())
+            .context("Failed to resolve HEAD")?;
+        let author = repo
+            .signature()
+            .context("Failed to determine author; set git user.name and user.email")?;
+
+        repo.commit(Some("HEAD"), &author, &author, message, &tree, &[&parent])
+            .context("Failed to commit release")
+    }
+
+    /// Create an annotated `v<version>` tag at HEAD of the workspace root
+    /// repository.
+    ///
+    /// Refuses to tag a dirty working tree, and fails if the tag exists.
+    /// Returns the tag name.
+    pub fn tag_release(&self, version: &Version, message: &str) -> Result<String> {
+        let root = self.scanner.root();
+        self.ensure_clean_tree("tag")?;
+
+        let repo = git2::Repository::open(root).context("Failed to open git repository")?;
+        let tag_name = format!("v{}", version);
+        if repo
+            .refname_to_id(&format!("refs/tags/{}", tag_name))
+            .is_ok()
+        {
+            anyhow::bail!("Tag {} already exists", tag_name);
+        }
+
+        let head = repo
+            .head()
+            .and_then(|h| h.peel_to_commit())
+            .context("Failed to resolve HEAD")?;
+        let tagger = repo
+            .signature()
+            .context("Failed to determine tagger; set git user.name and user.email")?;
+        repo.tag(&tag_name, head.as_object(), &tagger, message, false)
+            .with_context(|| format!("Failed to create tag {}", tag_name))?;
+
+        Ok(tag_name)
+    }
+
+    /// List tag names in the git repository containing `path`, if any.
+    fn repo_tags(path: &Path) -> Result<Vec<String>> {
+        let repo = match git2::Repository::discover(path) {
+            Ok(repo) => repo,
+            Err(_) => return Ok(Vec::new()),
+        };
+
+        let tags = repo
+            .tag_names(None)
+            .with_context(|| format!("Failed to list tags in {}", path.display()))?;
+
+        Ok(tags.iter().flatten().map(String::from).collect())
+    }
+
     /// Check for version inconsistencies across the workspace.
     pub fn check_consistency(&self) -> Result<VersionReport> {
+        self.check_consistency_where(|_| true)
+    }
+
+    /// Like [`check_consistency`](Self::check_consistency), but only report
+    /// problems involving packages for which `selected` returns true.
+    ///
+    /// Every package still counts when deciding what is inconsistent, so a
+    /// selected package that drifts from an unselected one is reported.
+    pub fn check_consistency_where(
+        &self,
+        selected: impl Fn(&CargoManifest) -> bool,
+    ) -> Result<VersionReport> {
         let manifests = self
             .scanner
             .find_embeddenator_packages()
             .context("Failed to find packages")?;
+        let selected_names: BTreeSet<&str> = manifests
+            .iter()
+            .filter(|m| selected(m))
+            .map(|m| m.package_name.as_str())
+            .collect();
 
         let mut report = VersionReport::default();
 
-        // Track package versions
-        let package_versions: HashMap<String, Version> = manifests
+        // Track package versions (ordered so report output is stable across runs)
+        let package_versions: BTreeMap<String, Version> = manifests
             .iter()
             .map(|m| (m.package_name.clone(), m.version.clone()))
             .collect();
 
         // Check for version drift
-        let mut versions_by_major: HashMap<u64, Vec<&str>> = HashMap::new();
+        let mut versions_by_major: BTreeMap<u64, Vec<&str>> = BTreeMap::new();
         for (name, version) in &package_versions {
             versions_by_major
                 .entry(version.major)
@@ -175,8 +1272,11 @@ impl VersionManager {
         }
 
         if versions_by_major.len() > 1 {
-            report.drift_detected = true;
             for (major, packages) in versions_by_major {
+                if !packages.iter().any(|p| selected_names.contains(p)) {
+                    continue;
+                }
+                report.drift_detected = true;
                 report.issues.push(format!(
                     "Version drift: {} package(s) on major version {}: {}",
                     packages.len(),
@@ -187,28 +1287,93 @@ impl VersionManager {
         }
 
         // Check dependency consistency
-        for manifest in &manifests {
-            for dep in manifest.embeddenator_dependencies() {
-                if let Some(dep_version) = &dep.version {
-                    if let Some(actual_version) = package_versions.get(&dep.name) {
-                        if dep_version != actual_version {
-                            report.inconsistencies.push(VersionInconsistency {
-                                package: manifest.package_name.clone(),
-                                dependency: dep.name.clone(),
-                                expected: actual_version.clone(),
-                                found: dep_version.clone(),
-                            });
-                        }
-                    }
+        for manifest in manifests
+            .iter()
+            .filter(|m| selected_names.contains(m.package_name.as_str()))
+        {
+            for dep in manifest
+                .dependencies_with_prefix(self.scanner.prefix())
+                .into_iter()
+                .filter(|d| self.dependency_kinds.contains(&d.dep_type))
+            {
+                let Some(actual_version) = package_versions.get(&dep.name) else {
+                    continue;
+                };
+
+                // Exact pins must match; other requirements must be satisfied.
+                // Inherited dependencies carry the root's version.
+                let mismatch = match (&dep.version, &dep.requirement) {
+                    (Some(version), _) => (version != actual_version).then(|| version.to_string()),
+                    (None, Some(req)) => (!req.matches(actual_version)).then(|| req.to_string()),
+                    (None, None) => continue,
+                };
+
+                match mismatch {
+                    Some(found) => report.inconsistencies.push(VersionInconsistency {
+                        package: manifest.package_name.clone(),
+                        dependency: dep.name.clone(),
+                        expected: actual_version.clone(),
+                        found,
+                    }),
+                    None => report.verified.push((
+                        manifest.package_name.clone(),
+                        dep.name.clone(),
+                        actual_version.clone(),
+                    )),
                 }
             }
         }
 
-        report.total_packages = manifests.len();
+        // Check prerelease labels against the workspace convention
+        for (name, version) in &package_versions {
+            if version.pre.is_empty() || !selected_names.contains(name.as_str()) {
+                continue;
+            }
+
+            let label = version.pre.as_str().split('.').next().unwrap_or_default();
+            if !self.allowed_prerelease_labels.iter().any(|l| l == label) {
+                report.convention_warnings.push(format!(
+                    "{} {} uses prerelease label '{}' (allowed: {})",
+                    name,
+                    version,
+                    label,
+                    self.allowed_prerelease_labels.join(", ")
+                ));
+            }
+        }
+
+        report.packages = manifests
+            .iter()
+            .map(|m| (m.package_name.clone(), m.version.clone(), m.path.clone()))
+            .collect();
+        report.packages.sort_by(|a, b| a.0.cmp(&b.0));
+        report.verified.sort();
+        report.total_packages = report.packages.len();
         Ok(report)
     }
 }
 
+/// Relative strength of a bump, for picking the strongest one.
+fn bump_rank(bump: BumpType) -> u8 {
+    match bump {
+        BumpType::Prerelease | BumpType::PromotePrerelease => 0,
+        BumpType::Patch => 1,
+        BumpType::Minor => 2,
+        BumpType::Major => 3,
+    }
+}
+
+/// Result of inferring a bump from conventional commits.
+#[derive(Debug, Clone)]
+pub struct CommitAnalysis {
+    /// Strongest bump implied by the commits.
+    pub bump: BumpType,
+    /// Number of commits analyzed.
+    pub commit_count: usize,
+    /// Subjects of the commits that implied `bump`.
+    pub drivers: Vec<String>,
+}
+
 /// Represents a version change for a package.
 #[derive(Debug, Clone)]
 pub struct VersionChange {
@@ -218,21 +1383,77 @@ pub struct VersionChange {
     pub new_version: Version,
 }
 
-/// Report of version consistency check.
+/// [`RequirementChange::package`] for entries in the root
+/// `[workspace.dependencies]` table.
+pub const WORKSPACE_DEPENDENCIES: &str = "[workspace.dependencies]";
+
+/// A dependency requirement rewritten by [`VersionManager::align_dependencies_to`].
+#[derive(Debug, Clone)]
+pub struct RequirementChange {
+    /// Package declaring the dependency, or [`WORKSPACE_DEPENDENCIES`].
+    pub package: String,
+    pub path: PathBuf,
+    pub dependency: String,
+    /// Requirement as written before the change.
+    pub old_req: String,
+    pub new_version: Version,
+}
+
+/// Result of [`VersionManager::bump_package`].
+#[derive(Debug, Default)]
+pub struct PackageBump {
+    pub changes: Vec<VersionChange>,
+    /// Dependents left untouched, with the reason.
+    pub skipped: Vec<String>,
+}
+
+/// Result of [`VersionManager::rollback`].
+///
+/// In `restored`, `old_version` is the bumped version that was undone and
+/// `new_version` the version the package is back at.
 #[derive(Debug, Default)]
+pub struct RollbackReport {
+    pub restored: Vec<VersionChange>,
+    /// Packages left alone, with the reason.
+    pub skipped: Vec<String>,
+}
+
+/// On-disk record of the last bump.
+#[derive(Debug, Serialize, Deserialize)]
+struct BumpJournal {
+    changes: Vec<JournalEntry>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct JournalEntry {
+    package: String,
+    path: PathBuf,
+    old_version: String,
+    new_version: String,
+}
+
+/// Report of version consistency check.
+#[derive(Debug, Default, Serialize)]
 pub struct VersionReport {
     pub total_packages: usize,
+    /// Every scanned package as `(name, version, manifest path)`, sorted by name.
+    pub packages: Vec<(String, Version, PathBuf)>,
     pub drift_detected: bool,
     pub issues: Vec<String>,
     pub inconsistencies: Vec<VersionInconsistency>,
+    /// Inter-dependencies whose version matched, as `(package, dependency, version)`.
+    pub verified: Vec<(String, String, Version)>,
+    /// Versions that parse but break workspace conventions (not counted as issues).
+    pub convention_warnings: Vec<String>,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct VersionInconsistency {
     pub package: String,
     pub dependency: String,
     pub expected: Version,
-    pub found: Version,
+    /// The pinned version or requirement that doesn't match `expected`.
+    pub found: String,
 }
 
 impl VersionReport {
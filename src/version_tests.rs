@@ -1,5 +1,73 @@
-use crate::version::{BumpType, VersionManager};
+use crate::version::{BumpType, VersionManager, JOURNAL_PATH};
 use semver::Version;
+use std::fs;
+use tempfile::TempDir;
+
+#[test]
+fn test_bump_preserves_build_metadata() {
+    let manager = VersionManager::new(".");
+    let current = Version::parse("0.20.0+abc").unwrap();
+
+    let bump = |bump_type| {
+        manager
+            .calculate_new_version(&current, bump_type)
+            .unwrap()
+            .to_string()
+    };
+    assert_eq!(bump(BumpType::Major), "1.0.0+abc");
+    assert_eq!(bump(BumpType::Minor), "0.21.0+abc");
+    assert_eq!(bump(BumpType::Patch), "0.20.1+abc");
+    assert_eq!(bump(BumpType::Prerelease), "0.20.0-alpha.1+abc");
+}
+
+#[test]
+fn test_bump_clear_or_replace_build_metadata() {
+    let current = Version::parse("0.20.0-alpha.1+build.5").unwrap();
+
+    let manager = VersionManager::new(".").with_clear_build(true);
+    let new = manager
+        .calculate_new_version(&current, BumpType::Minor)
+        .unwrap();
+    assert_eq!(new.to_string(), "0.21.0");
+
+    let manager = VersionManager::new(".").with_build_metadata(Some("git.def456"));
+    let new = manager
+        .calculate_new_version(&current, BumpType::Prerelease)
+        .unwrap();
+    assert_eq!(new.to_string(), "0.20.0-alpha.2+git.def456");
+
+    let manager = VersionManager::new(".").with_build_metadata(Some("not valid"));
+    assert!(manager
+        .calculate_new_version(&current, BumpType::Patch)
+        .is_err());
+}
+
+fn create_tagged_repo(dir: &TempDir, name: &str, version: &str, tag: Option<&str>) {
+    let pkg = dir.path().join(name);
+    fs::create_dir_all(&pkg).unwrap();
+    fs::write(
+        pkg.join("Cargo.toml"),
+        format!(
+            "[package]\nname = \"{}\"\nversion = \"{}\"\nedition = \"2021\"\n",
+            name, version
+        ),
+    )
+    .unwrap();
+
+    let repo = git2::Repository::init(&pkg).unwrap();
+    let mut index = repo.index().unwrap();
+    index.add_path(std::path::Path::new("Cargo.toml")).unwrap();
+    let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+    let sig = git2::Signature::now("test", "test@example.com").unwrap();
+    let commit = repo
+        .commit(Some("HEAD"), &sig, &sig, "initial", &tree, &[])
+        .unwrap();
+
+    if let Some(tag) = tag {
+        let obj = repo.find_object(commit, None).unwrap();
+        repo.tag_lightweight(tag, &obj, false).unwrap();
+    }
+}
 
 #[test]
 fn test_bump_major() {
@@ -60,3 +128,931 @@ fn test_bump_prerelease_beta() {
         .unwrap();
     assert_eq!(new.to_string(), "0.20.0-beta.4");
 }
+
+#[test]
+fn test_bump_prerelease_identifiers() {
+    let manager = VersionManager::new(".");
+    let bump = |v: &str| {
+        manager
+            .calculate_new_version(&Version::parse(v).unwrap(), BumpType::Prerelease)
+            .unwrap()
+            .to_string()
+    };
+
+    // No numeric identifier: append .1
+    assert_eq!(bump("0.20.0-alpha"), "0.20.0-alpha.1");
+    assert_eq!(bump("0.20.0-alpha.beta"), "0.20.0-alpha.beta.1");
+    // Otherwise the rightmost numeric identifier increments
+    assert_eq!(bump("1.0.0-x.7.z.92"), "1.0.0-x.7.z.93");
+    assert_eq!(bump("1.0.0-rc.1.hotfix"), "1.0.0-rc.2.hotfix");
+    assert_eq!(bump("1.0.0-9"), "1.0.0-10");
+    // Alphanumeric identifiers containing digits are not numeric
+    assert_eq!(bump("1.0.0-beta.2.rc3"), "1.0.0-beta.3.rc3");
+}
+
+#[test]
+fn test_untagged_packages() {
+    let temp_dir = TempDir::new().unwrap();
+    create_tagged_repo(&temp_dir, "embeddenator-vsa", "0.20.0", Some("v0.20.0"));
+    create_tagged_repo(
+        &temp_dir,
+        "embeddenator-io",
+        "0.20.1",
+        Some("embeddenator-io-v0.20.1"),
+    );
+    create_tagged_repo(&temp_dir, "embeddenator-fs", "0.21.0", Some("v0.20.0"));
+
+    let manager = VersionManager::new(temp_dir.path());
+    let packages = manager.untagged_packages().unwrap();
+
+    let tagged: Vec<(&str, bool)> = packages
+        .iter()
+        .map(|(name, _, tagged)| (name.as_str(), *tagged))
+        .collect();
+    assert_eq!(
+        tagged,
+        vec![
+            ("embeddenator-fs", false),
+            ("embeddenator-io", true),
+            ("embeddenator-vsa", true),
+        ]
+    );
+}
+
+#[test]
+fn test_common_version() {
+    let temp_dir = TempDir::new().unwrap();
+    create_tagged_repo(&temp_dir, "embeddenator-vsa", "0.20.0", None);
+    create_tagged_repo(&temp_dir, "embeddenator-io", "0.20.0", None);
+
+    let manager = VersionManager::new(temp_dir.path());
+    assert_eq!(
+        manager.common_version().unwrap(),
+        Some(Version::parse("0.20.0").unwrap())
+    );
+
+    create_tagged_repo(&temp_dir, "embeddenator-fs", "0.20.1", None);
+    assert_eq!(manager.common_version().unwrap(), None);
+}
+
+#[test]
+fn test_check_consistency_lists_packages() {
+    let temp_dir = TempDir::new().unwrap();
+    create_tagged_repo(&temp_dir, "embeddenator-vsa", "0.20.0", None);
+    create_tagged_repo(&temp_dir, "embeddenator-io", "0.21.0-alpha.1", None);
+
+    let report = VersionManager::new(temp_dir.path())
+        .check_consistency()
+        .unwrap();
+    assert_eq!(report.total_packages, report.packages.len());
+
+    let listed: Vec<(&str, String)> = report
+        .packages
+        .iter()
+        .map(|(name, version, _)| (name.as_str(), version.to_string()))
+        .collect();
+    assert_eq!(
+        listed,
+        vec![
+            ("embeddenator-io", "0.21.0-alpha.1".to_string()),
+            ("embeddenator-vsa", "0.20.0".to_string()),
+        ]
+    );
+    assert!(report.packages[1]
+        .2
+        .ends_with("embeddenator-vsa/Cargo.toml"));
+
+    let json = serde_json::to_value(&report).unwrap();
+    assert_eq!(json["packages"][0][1], "0.21.0-alpha.1");
+}
+
+#[test]
+fn test_prerelease_label_convention() {
+    let temp_dir = TempDir::new().unwrap();
+    create_tagged_repo(&temp_dir, "embeddenator-vsa", "0.20.0-dev.1", None);
+    create_tagged_repo(&temp_dir, "embeddenator-io", "0.20.0-rc.1", None);
+
+    let manager = VersionManager::new(temp_dir.path());
+    let report = manager.check_consistency().unwrap();
+    assert_eq!(report.convention_warnings.len(), 1);
+    assert!(report.convention_warnings[0].contains("embeddenator-vsa"));
+    assert!(report.convention_warnings[0].contains("'dev'"));
+
+    let manager =
+        VersionManager::new(temp_dir.path()).with_allowed_prerelease_labels(&["dev", "rc"]);
+    let report = manager.check_consistency().unwrap();
+    assert!(report.convention_warnings.is_empty());
+}
+
+#[test]
+fn test_infer_bump_from_commits() {
+    let temp_dir = TempDir::new().unwrap();
+    create_tagged_repo(&temp_dir, "embeddenator-vsa", "0.20.0", Some("v0.20.0"));
+
+    let repo = git2::Repository::open(temp_dir.path().join("embeddenator-vsa")).unwrap();
+    let commit = |message: &str| {
+        let sig = git2::Signature::now("test", "test@example.com").unwrap();
+        let head = repo.head().unwrap().peel_to_commit().unwrap();
+        let tree = head.tree().unwrap();
+        repo.commit(Some("HEAD"), &sig, &sig, message, &tree, &[&head])
+            .unwrap();
+    };
+
+    let manager = VersionManager::new(temp_dir.path());
+
+    commit("fix: handle empty input");
+    commit("chore: tidy");
+    assert_eq!(
+        manager.infer_bump_from_commits(None).unwrap(),
+        BumpType::Patch
+    );
+
+    commit("feat(io): add streaming reader");
+    let analysis = manager.analyze_commits(None).unwrap();
+    assert_eq!(analysis.bump, BumpType::Minor);
+    assert_eq!(analysis.commit_count, 3);
+    assert_eq!(analysis.drivers, vec!["feat(io): add streaming reader"]);
+
+    commit("refactor!: rename core types");
+    assert_eq!(
+        manager.infer_bump_from_commits(None).unwrap(),
+        BumpType::Major
+    );
+
+    // An explicit ref limits the range
+    assert_eq!(
+        manager.infer_bump_from_commits(Some("HEAD~2")).unwrap(),
+        BumpType::Major
+    );
+    assert_eq!(
+        manager.infer_bump_from_commits(Some("HEAD~3")).unwrap(),
+        BumpType::Major
+    );
+    assert_eq!(
+        manager
+            .analyze_commits(Some("HEAD~1"))
+            .unwrap()
+            .commit_count,
+        1
+    );
+    assert!(manager.infer_bump_from_commits(Some("HEAD")).is_err());
+}
+
+#[test]
+fn test_conventional_bump() {
+    assert_eq!(
+        VersionManager::conventional_bump("feat: add x"),
+        BumpType::Minor
+    );
+    assert_eq!(
+        VersionManager::conventional_bump("fix(core): y"),
+        BumpType::Patch
+    );
+    assert_eq!(
+        VersionManager::conventional_bump("feat(api)!: drop z"),
+        BumpType::Major
+    );
+    assert_eq!(
+        VersionManager::conventional_bump("fix: a\n\nBREAKING CHANGE: removed b"),
+        BumpType::Major
+    );
+    assert_eq!(
+        VersionManager::conventional_bump("Update README!"),
+        BumpType::Patch
+    );
+}
+
+#[test]
+fn test_check_consistency_requirement_operators() {
+    let temp_dir = TempDir::new().unwrap();
+    create_tagged_repo(&temp_dir, "embeddenator-vsa", "0.21.0", None);
+    create_tagged_repo(&temp_dir, "embeddenator-io", "0.21.0", None);
+    create_tagged_repo(&temp_dir, "embeddenator-fs", "0.21.0", None);
+    fs::write(
+        temp_dir.path().join("embeddenator-io/Cargo.toml"),
+        "[package]\nname = \"embeddenator-io\"\nversion = \"0.21.0\"\n\n[dependencies]\nembeddenator-vsa = \"^0.21\"\n",
+    )
+    .unwrap();
+    fs::write(
+        temp_dir.path().join("embeddenator-fs/Cargo.toml"),
+        "[package]\nname = \"embeddenator-fs\"\nversion = \"0.21.0\"\n\n[dependencies]\nembeddenator-vsa = \"~0.20.0\"\nembeddenator-io = \"^0.21\"\n",
+    )
+    .unwrap();
+
+    let report = VersionManager::new(temp_dir.path())
+        .check_consistency()
+        .unwrap();
+
+    // ~0.20.0 does not admit 0.21.0; the caret requirements do
+    assert_eq!(report.inconsistencies.len(), 1);
+    let inc = &report.inconsistencies[0];
+    assert_eq!(inc.package, "embeddenator-fs");
+    assert_eq!(inc.dependency, "embeddenator-vsa");
+    assert_eq!(inc.found, "~0.20.0");
+    assert_eq!(report.verified.len(), 2);
+}
+
+#[test]
+fn test_check_consistency_dependency_kinds() {
+    use crate::cargo::DependencyType;
+
+    let temp_dir = TempDir::new().unwrap();
+    create_tagged_repo(&temp_dir, "embeddenator-vsa", "0.21.0", None);
+    create_tagged_repo(&temp_dir, "embeddenator-io", "0.21.0", None);
+    fs::write(
+        temp_dir.path().join("embeddenator-io/Cargo.toml"),
+        "[package]\nname = \"embeddenator-io\"\nversion = \"0.21.0\"\n\n[dev-dependencies]\nembeddenator-vsa = \"0.20.0\"\n",
+    )
+    .unwrap();
+
+    let manager = VersionManager::new(temp_dir.path());
+    assert_eq!(
+        manager.check_consistency().unwrap().inconsistencies.len(),
+        1
+    );
+
+    let manager =
+        VersionManager::new(temp_dir.path()).with_dependency_kinds(&[DependencyType::Normal]);
+    assert!(manager
+        .check_consistency()
+        .unwrap()
+        .inconsistencies
+        .is_empty());
+}
+
+fn create_inheriting_workspace(dir: &TempDir) {
+    fs::write(
+        dir.path().join("Cargo.toml"),
+        "[workspace]\nmembers = [\"embeddenator-vsa\", \"embeddenator-io\", \"embeddenator-fs\"]\n\n[workspace.dependencies]\nembeddenator-vsa = { version = \"0.20.0\", path = \"embeddenator-vsa\" }\nserde = \"1.0\"\n",
+    )
+    .unwrap();
+
+    for (name, deps) in [
+        ("embeddenator-vsa", ""),
+        (
+            "embeddenator-io",
+            "\n[dependencies]\nembeddenator-vsa = { workspace = true }\n",
+        ),
+        (
+            "embeddenator-fs",
+            "\n[dependencies]\nembeddenator-vsa.workspace = true\n",
+        ),
+    ] {
+        let pkg = dir.path().join(name);
+        fs::create_dir_all(&pkg).unwrap();
+        fs::write(
+            pkg.join("Cargo.toml"),
+            format!(
+                "[package]\nname = \"{}\"\nversion = \"0.20.0\"\nedition = \"2021\"\n{}",
+                name, deps
+            ),
+        )
+        .unwrap();
+    }
+}
+
+#[test]
+fn test_check_consistency_workspace_inherited() {
+    let temp_dir = TempDir::new().unwrap();
+    create_inheriting_workspace(&temp_dir);
+
+    let manager = VersionManager::new(temp_dir.path());
+    let report = manager.check_consistency().unwrap();
+    assert!(report.inconsistencies.is_empty());
+    let verified: Vec<(&str, &str, String)> = report
+        .verified
+        .iter()
+        .map(|(p, d, v)| (p.as_str(), d.as_str(), v.to_string()))
+        .collect();
+    assert_eq!(
+        verified,
+        vec![
+            ("embeddenator-fs", "embeddenator-vsa", "0.20.0".to_string()),
+            ("embeddenator-io", "embeddenator-vsa", "0.20.0".to_string()),
+        ]
+    );
+
+    // Drift in the root pin is reported against every inheriting member
+    let root = temp_dir.path().join("Cargo.toml");
+    let content = fs::read_to_string(&root).unwrap();
+    fs::write(&root, content.replace("\"0.20.0\"", "\"0.19.0\"")).unwrap();
+
+    let report = manager.check_consistency().unwrap();
+    assert_eq!(report.inconsistencies.len(), 2);
+    assert!(report.verified.is_empty());
+    assert!(report
+        .inconsistencies
+        .iter()
+        .all(|i| i.dependency == "embeddenator-vsa" && i.found == "0.19.0"));
+}
+
+#[test]
+fn test_bump_updates_workspace_dependencies() {
+    let temp_dir = TempDir::new().unwrap();
+    create_inheriting_workspace(&temp_dir);
+
+    let io_before = fs::read_to_string(temp_dir.path().join("embeddenator-io/Cargo.toml")).unwrap();
+    let fs_before = fs::read_to_string(temp_dir.path().join("embeddenator-fs/Cargo.toml")).unwrap();
+
+    let manager = VersionManager::new(temp_dir.path());
+    manager.bump_versions(BumpType::Minor, false).unwrap();
+
+    let root = fs::read_to_string(temp_dir.path().join("Cargo.toml")).unwrap();
+    assert!(
+        root.contains("embeddenator-vsa = { version = \"0.21.0\", path = \"embeddenator-vsa\" }")
+    );
+    assert!(root.contains("serde = \"1.0\""));
+
+    // Member entries still inherit and are otherwise untouched
+    let io_after = fs::read_to_string(temp_dir.path().join("embeddenator-io/Cargo.toml")).unwrap();
+    let fs_after = fs::read_to_string(temp_dir.path().join("embeddenator-fs/Cargo.toml")).unwrap();
+    assert_eq!(io_after, io_before.replace("0.20.0", "0.21.0"));
+    assert_eq!(fs_after, fs_before.replace("0.20.0", "0.21.0"));
+
+    assert!(manager
+        .check_consistency()
+        .unwrap()
+        .inconsistencies
+        .is_empty());
+}
+
+#[test]
+fn test_workspace_inherited_renamed_dependency() {
+    let temp_dir = TempDir::new().unwrap();
+    fs::write(
+        temp_dir.path().join("Cargo.toml"),
+        "[workspace]\nmembers = [\"embeddenator-vsa\", \"embeddenator-io\"]\n\n[workspace.dependencies]\nvsa = { package = \"embeddenator-vsa\", version = \"0.19.0\" }\n",
+    )
+    .unwrap();
+    for (name, deps) in [
+        ("embeddenator-vsa", ""),
+        (
+            "embeddenator-io",
+            "\n[dependencies]\nvsa = { workspace = true }\n",
+        ),
+    ] {
+        let pkg = temp_dir.path().join(name);
+        fs::create_dir_all(&pkg).unwrap();
+        fs::write(
+            pkg.join("Cargo.toml"),
+            format!(
+                "[package]\nname = \"{}\"\nversion = \"0.20.0\"\n{}",
+                name, deps
+            ),
+        )
+        .unwrap();
+    }
+
+    let manager = VersionManager::new(temp_dir.path());
+    let report = manager.check_consistency().unwrap();
+    assert_eq!(report.inconsistencies.len(), 1);
+    assert_eq!(report.inconsistencies[0].package, "embeddenator-io");
+    assert_eq!(report.inconsistencies[0].dependency, "embeddenator-vsa");
+    assert_eq!(report.inconsistencies[0].found, "0.19.0");
+
+    manager.bump_versions(BumpType::Patch, false).unwrap();
+    let root = fs::read_to_string(temp_dir.path().join("Cargo.toml")).unwrap();
+    assert!(root.contains("vsa = { package = \"embeddenator-vsa\", version = \"0.20.1\" }"));
+    assert_eq!(manager.check_consistency().unwrap().verified.len(), 1);
+}
+
+#[test]
+fn test_bump_package() {
+    let temp_dir = TempDir::new().unwrap();
+    create_tagged_repo(&temp_dir, "embeddenator-vsa", "0.20.0", None);
+    create_tagged_repo(&temp_dir, "embeddenator-io", "0.20.0", None);
+    create_tagged_repo(&temp_dir, "embeddenator-fs", "0.20.0", None);
+    fs::write(
+        temp_dir.path().join("embeddenator-io/Cargo.toml"),
+        "[package]\nname = \"embeddenator-io\"\nversion = \"0.20.0\"\n\n[dependencies]\nembeddenator-vsa = \"0.20.0\"\n",
+    )
+    .unwrap();
+    let fs_manifest = "[package]\nname = \"embeddenator-fs\"\nversion = \"0.20.0\"\n\n[dependencies]\nembeddenator-vsa = { path = \"../embeddenator-vsa\" }\n";
+    fs::write(
+        temp_dir.path().join("embeddenator-fs/Cargo.toml"),
+        fs_manifest,
+    )
+    .unwrap();
+
+    let manager = VersionManager::new(temp_dir.path());
+    let bump = manager
+        .bump_package("embeddenator-vsa", BumpType::Patch, false)
+        .unwrap();
+    let changes = bump.changes;
+    assert_eq!(changes.len(), 1);
+    assert_eq!(changes[0].new_version.to_string(), "0.20.1");
+
+    let read =
+        |name: &str| fs::read_to_string(temp_dir.path().join(name).join("Cargo.toml")).unwrap();
+    assert!(read("embeddenator-vsa").contains("version = \"0.20.1\""));
+    // The dependent's own version stays; only its requirement moves
+    let io = read("embeddenator-io");
+    assert!(io.contains("version = \"0.20.0\""));
+    assert!(io.contains("embeddenator-vsa = \"0.20.1\""));
+    // Path-only dependents are left alone and reported
+    assert_eq!(read("embeddenator-fs"), fs_manifest);
+    assert_eq!(
+        bump.skipped,
+        vec!["embeddenator-fs: depends on embeddenator-vsa without a version"]
+    );
+}
+
+#[test]
+fn test_bump_package_unknown() {
+    let temp_dir = TempDir::new().unwrap();
+    create_tagged_repo(&temp_dir, "embeddenator-vsa", "0.20.0", None);
+
+    let manager = VersionManager::new(temp_dir.path());
+    let err = manager
+        .bump_package("embeddenator-missing", BumpType::Patch, true)
+        .unwrap_err();
+    assert!(err.to_string().contains("embeddenator-missing"));
+}
+
+#[test]
+fn test_set_versions() {
+    let temp_dir = TempDir::new().unwrap();
+    create_tagged_repo(&temp_dir, "embeddenator-vsa", "0.20.0", None);
+    create_tagged_repo(&temp_dir, "embeddenator-io", "0.21.0-alpha.3", None);
+    fs::write(
+        temp_dir.path().join("embeddenator-io/Cargo.toml"),
+        "[package]\nname = \"embeddenator-io\"\nversion = \"0.21.0-alpha.3\"\n\n[dependencies]\nembeddenator-vsa = \"0.20.0\"\n",
+    )
+    .unwrap();
+
+    let target = Version::parse("1.0.0-rc.1").unwrap();
+    let manager = VersionManager::new(temp_dir.path());
+    let changes = manager.set_versions(&target, false).unwrap();
+    assert_eq!(changes.len(), 2);
+    assert!(changes.iter().all(|c| c.new_version == target));
+
+    let io = fs::read_to_string(temp_dir.path().join("embeddenator-io/Cargo.toml")).unwrap();
+    assert!(io.contains("version = \"1.0.0-rc.1\""));
+    assert!(io.contains("embeddenator-vsa = \"1.0.0-rc.1\""));
+}
+
+#[test]
+fn test_set_versions_rejects_downgrade() {
+    let temp_dir = TempDir::new().unwrap();
+    create_tagged_repo(&temp_dir, "embeddenator-vsa", "0.20.0", None);
+    create_tagged_repo(&temp_dir, "embeddenator-io", "0.21.0", None);
+
+    let target = Version::parse("0.21.0-rc.1").unwrap();
+    let manager = VersionManager::new(temp_dir.path());
+    let err = manager.set_versions(&target, false).unwrap_err();
+    assert!(err.to_string().contains("embeddenator-io (0.21.0)"));
+
+    // Nothing was written
+    let vsa = fs::read_to_string(temp_dir.path().join("embeddenator-vsa/Cargo.toml")).unwrap();
+    assert!(vsa.contains("version = \"0.20.0\""));
+
+    let manager = VersionManager::new(temp_dir.path()).with_allow_downgrade(true);
+    assert_eq!(manager.set_versions(&target, true).unwrap().len(), 2);
+}
+
+#[test]
+fn test_promote_prerelease() {
+    let manager = VersionManager::new(".");
+    let promote = |v: &str| {
+        manager
+            .calculate_new_version(&Version::parse(v).unwrap(), BumpType::PromotePrerelease)
+            .map(|v| v.to_string())
+    };
+
+    assert_eq!(promote("0.20.0-alpha.3").unwrap(), "0.20.0-beta.1");
+    assert_eq!(
+        promote("0.20.0-beta.2+build.5").unwrap(),
+        "0.20.0-rc.1+build.5"
+    );
+    assert_eq!(promote("0.20.0-rc.4+build.5").unwrap(), "0.20.0+build.5");
+
+    let err = promote("0.20.0").unwrap_err();
+    assert!(err.to_string().contains("not a prerelease"));
+    assert!(promote("0.20.0-dev.1").is_err());
+}
+
+#[test]
+fn test_bump_prerelease_custom_label() {
+    let current = Version::parse("0.20.0").unwrap();
+
+    let manager = VersionManager::new(".").with_prerelease_label("dev");
+    let new = manager
+        .calculate_new_version(&current, BumpType::Prerelease)
+        .unwrap();
+    assert_eq!(new.to_string(), "0.20.0-dev.1");
+
+    // Existing prereleases keep their own label
+    let new = manager
+        .calculate_new_version(
+            &Version::parse("0.20.0-rc.2").unwrap(),
+            BumpType::Prerelease,
+        )
+        .unwrap();
+    assert_eq!(new.to_string(), "0.20.0-rc.3");
+
+    let manager = VersionManager::new(".").with_prerelease_label("not valid");
+    assert!(manager
+        .calculate_new_version(&current, BumpType::Prerelease)
+        .is_err());
+}
+
+#[test]
+fn test_rollback_restores_last_bump() {
+    let temp_dir = TempDir::new().unwrap();
+    create_inheriting_workspace(&temp_dir);
+
+    let paths = [
+        "Cargo.toml",
+        "embeddenator-vsa/Cargo.toml",
+        "embeddenator-io/Cargo.toml",
+        "embeddenator-fs/Cargo.toml",
+    ];
+    let read_all = || -> Vec<String> {
+        paths
+            .iter()
+            .map(|p| fs::read_to_string(temp_dir.path().join(p)).unwrap())
+            .collect()
+    };
+    let before = read_all();
+
+    let manager = VersionManager::new(temp_dir.path()).with_journal(true);
+    assert!(manager.rollback().unwrap().is_none());
+
+    manager.bump_versions(BumpType::Minor, false).unwrap();
+    assert!(temp_dir.path().join(JOURNAL_PATH).is_file());
+    assert_ne!(read_all(), before);
+
+    let report = manager.rollback().unwrap().unwrap();
+    assert_eq!(report.restored.len(), 3);
+    assert!(report.skipped.is_empty());
+    assert_eq!(read_all(), before);
+    assert!(!temp_dir.path().join(JOURNAL_PATH).exists());
+}
+
+#[test]
+fn test_rollback_skips_changed_manifests() {
+    let temp_dir = TempDir::new().unwrap();
+    create_tagged_repo(&temp_dir, "embeddenator-vsa", "0.20.0", None);
+    create_tagged_repo(&temp_dir, "embeddenator-io", "0.20.0", None);
+
+    let manager = VersionManager::new(temp_dir.path()).with_journal(true);
+    manager.bump_versions(BumpType::Patch, false).unwrap();
+
+    // Someone edits one package after the bump
+    let io = temp_dir.path().join("embeddenator-io/Cargo.toml");
+    let content = fs::read_to_string(&io).unwrap();
+    fs::write(&io, content.replace("0.20.1", "0.22.0")).unwrap();
+
+    let report = manager.rollback().unwrap().unwrap();
+    assert_eq!(report.restored.len(), 1);
+    assert_eq!(report.restored[0].package, "embeddenator-vsa");
+    assert_eq!(report.skipped.len(), 1);
+    assert!(report.skipped[0].contains("embeddenator-io"));
+
+    assert!(fs::read_to_string(&io)
+        .unwrap()
+        .contains("version = \"0.22.0\""));
+    assert!(
+        fs::read_to_string(temp_dir.path().join("embeddenator-vsa/Cargo.toml"))
+            .unwrap()
+            .contains("version = \"0.20.0\"")
+    );
+}
+
+#[test]
+fn test_bump_without_journal() {
+    let temp_dir = TempDir::new().unwrap();
+    create_tagged_repo(&temp_dir, "embeddenator-vsa", "0.20.0", None);
+
+    let manager = VersionManager::new(temp_dir.path());
+    manager.bump_versions(BumpType::Patch, false).unwrap();
+    assert!(!temp_dir.path().join(JOURNAL_PATH).exists());
+    assert!(manager.rollback().unwrap().is_none());
+}
+
+#[test]
+fn test_apply_version_map() {
+    let temp_dir = TempDir::new().unwrap();
+    create_tagged_repo(&temp_dir, "embeddenator-vsa", "0.20.0", None);
+    create_tagged_repo(&temp_dir, "embeddenator-io", "0.20.0", None);
+    create_tagged_repo(&temp_dir, "embeddenator-fs", "0.20.0", None);
+    fs::write(
+        temp_dir.path().join("embeddenator-fs/Cargo.toml"),
+        "[package]\nname = \"embeddenator-fs\"\nversion = \"0.20.0\"\n\n[dependencies]\nembeddenator-vsa = \"0.20.0\"\nembeddenator-io = \"0.20.0\"\n",
+    )
+    .unwrap();
+
+    let map_path = temp_dir.path().join("versions.toml");
+    fs::write(
+        &map_path,
+        "embeddenator-vsa = \"0.21.0\"\nembeddenator-io = \"0.20.0\"\n",
+    )
+    .unwrap();
+    let map = VersionManager::load_version_map(&map_path).unwrap();
+
+    let manager = VersionManager::new(temp_dir.path());
+    let changes = manager.apply_version_map(&map, false).unwrap();
+    // Already-matching entries are not changes
+    assert_eq!(changes.len(), 1);
+    assert_eq!(changes[0].package, "embeddenator-vsa");
+
+    let fs_manifest =
+        fs::read_to_string(temp_dir.path().join("embeddenator-fs/Cargo.toml")).unwrap();
+    assert!(fs_manifest.contains("version = \"0.20.0\""));
+    assert!(fs_manifest.contains("embeddenator-vsa = \"0.21.0\""));
+    assert!(fs_manifest.contains("embeddenator-io = \"0.20.0\""));
+}
+
+#[test]
+fn test_apply_version_map_rejects_unknown_and_downgrades() {
+    let temp_dir = TempDir::new().unwrap();
+    create_tagged_repo(&temp_dir, "embeddenator-vsa", "0.20.0", None);
+
+    let manager = VersionManager::new(temp_dir.path());
+    let version = |v: &str| Version::parse(v).unwrap();
+
+    let unknown = [("embeddenator-missing".to_string(), version("1.0.0"))].into();
+    let err = manager.apply_version_map(&unknown, true).unwrap_err();
+    assert!(err.to_string().contains("embeddenator-missing"));
+
+    let lower = [("embeddenator-vsa".to_string(), version("0.19.0"))].into();
+    let err = manager.apply_version_map(&lower, true).unwrap_err();
+    assert!(err
+        .to_string()
+        .contains("embeddenator-vsa (0.20.0) to 0.19.0"));
+
+    let manager = manager.with_allow_downgrade(true);
+    assert_eq!(manager.apply_version_map(&lower, true).unwrap().len(), 1);
+}
+
+#[test]
+fn test_load_version_map_invalid() {
+    let temp_dir = TempDir::new().unwrap();
+    let map_path = temp_dir.path().join("versions.toml");
+
+    fs::write(&map_path, "embeddenator-vsa = \"not-a-version\"\n").unwrap();
+    assert!(VersionManager::load_version_map(&map_path).is_err());
+
+    fs::write(&map_path, "embeddenator-vsa = 1\n").unwrap();
+    let err = VersionManager::load_version_map(&map_path).unwrap_err();
+    assert!(err.to_string().contains("must be a string"));
+}
+
+#[test]
+fn test_tag_release() {
+    let temp_dir = TempDir::new().unwrap();
+    fs::write(temp_dir.path().join("update_all.sh"), "#!/bin/sh\n").unwrap();
+
+    let repo = git2::Repository::init(temp_dir.path()).unwrap();
+    let mut config = repo.config().unwrap();
+    config.set_str("user.name", "test").unwrap();
+    config.set_str("user.email", "test@example.com").unwrap();
+    let mut index = repo.index().unwrap();
+    index
+        .add_path(std::path::Path::new("update_all.sh"))
+        .unwrap();
+    index.write().unwrap();
+    let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+    let sig = git2::Signature::now("test", "test@example.com").unwrap();
+    let head = repo
+        .commit(Some("HEAD"), &sig, &sig, "initial", &tree, &[])
+        .unwrap();
+
+    let manager = VersionManager::new(temp_dir.path());
+    let version = Version::parse("0.21.0").unwrap();
+    let tag = manager.tag_release(&version, "Release v0.21.0").unwrap();
+    assert_eq!(tag, "v0.21.0");
+
+    let tag_ref = repo.find_reference("refs/tags/v0.21.0").unwrap();
+    let annotated = tag_ref.peel_to_tag().unwrap();
+    assert_eq!(annotated.message(), Some("Release v0.21.0"));
+    assert_eq!(annotated.target_id(), head);
+
+    let err = manager.tag_release(&version, "again").unwrap_err();
+    assert!(err.to_string().contains("already exists"));
+
+    fs::write(temp_dir.path().join("update_all.sh"), "#!/bin/sh\necho\n").unwrap();
+    let err = manager
+        .tag_release(&Version::parse("0.22.0").unwrap(), "dirty")
+        .unwrap_err();
+    assert!(err.to_string().contains("dirty"));
+}
+
+#[test]
+fn test_commit_release_before_tag() {
+    let temp_dir = TempDir::new().unwrap();
+    fs::write(temp_dir.path().join("update_all.sh"), "#!/bin/sh\n").unwrap();
+
+    let repo = git2::Repository::init(temp_dir.path()).unwrap();
+    let mut config = repo.config().unwrap();
+    config.set_str("user.name", "test").unwrap();
+    config.set_str("user.email", "test@example.com").unwrap();
+    let mut index = repo.index().unwrap();
+    index
+        .add_path(std::path::Path::new("update_all.sh"))
+        .unwrap();
+    index.write().unwrap();
+    let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+    let sig = git2::Signature::now("test", "test@example.com").unwrap();
+    repo.commit(Some("HEAD"), &sig, &sig, "initial", &tree, &[])
+        .unwrap();
+
+    let manager = VersionManager::new(temp_dir.path());
+    manager.ensure_clean_tree("bump").unwrap();
+
+    fs::write(temp_dir.path().join("update_all.sh"), "#!/bin/sh\necho\n").unwrap();
+    fs::write(temp_dir.path().join("CHANGELOG.md"), "# Changelog\n").unwrap();
+    fs::create_dir_all(temp_dir.path().join(".embeddenator")).unwrap();
+    fs::write(temp_dir.path().join(JOURNAL_PATH), "{}").unwrap();
+    let err = manager.ensure_clean_tree("bump").unwrap_err();
+    assert!(err.to_string().contains("Refusing to bump"));
+    assert!(err.to_string().contains("2 file(s)"));
+
+    let commit = manager
+        .commit_release("chore: bump version to 0.21.0")
+        .unwrap();
+    manager.ensure_clean_tree("tag").unwrap();
+
+    let tag = manager
+        .tag_release(&Version::parse("0.21.0").unwrap(), "Release v0.21.0")
+        .unwrap();
+    let annotated = repo
+        .find_reference(&format!("refs/tags/{}", tag))
+        .unwrap()
+        .peel_to_tag()
+        .unwrap();
+    assert_eq!(annotated.target_id(), commit);
+
+    let committed = repo.find_commit(commit).unwrap();
+    assert_eq!(committed.message(), Some("chore: bump version to 0.21.0"));
+    assert!(committed
+        .tree()
+        .unwrap()
+        .get_path(std::path::Path::new("CHANGELOG.md"))
+        .is_ok());
+}
+
+#[test]
+fn test_prepend_changelog() {
+    let temp_dir = TempDir::new().unwrap();
+    let manager = VersionManager::new(temp_dir.path());
+    let changelog = temp_dir.path().join("CHANGELOG.md");
+    let version = |v: &str| Version::parse(v).unwrap();
+
+    // Created from the skeleton, below Unreleased
+    assert!(manager
+        .prepend_changelog(&version("0.20.0"), "2026-01-25")
+        .unwrap());
+    let content = fs::read_to_string(&changelog).unwrap();
+    assert!(content.starts_with("# Changelog\n"));
+    assert!(content.contains("## [Unreleased]\n\n## [0.20.0] - 2026-01-25\n"));
+
+    // Newer releases go above older ones
+    assert!(manager
+        .prepend_changelog(&version("0.21.0"), "2026-02-01")
+        .unwrap());
+    let content = fs::read_to_string(&changelog).unwrap();
+    let newer = content.find("## [0.21.0] - 2026-02-01").unwrap();
+    let older = content.find("## [0.20.0]").unwrap();
+    assert!(content.find("## [Unreleased]").unwrap() < newer);
+    assert!(newer < older);
+
+    // No duplicate entries
+    assert!(!manager
+        .prepend_changelog(&version("0.21.0"), "2026-02-02")
+        .unwrap());
+    assert_eq!(fs::read_to_string(&changelog).unwrap(), content);
+}
+
+#[test]
+fn test_align_dependencies_to() {
+    let temp_dir = TempDir::new().unwrap();
+    fs::write(
+        temp_dir.path().join("Cargo.toml"),
+        "[workspace]\nmembers = [\"app\", \"tool\"]\n\n[workspace.dependencies]\nembeddenator-vsa = \"0.20.0\"\n",
+    )
+    .unwrap();
+    for (name, deps) in [
+        (
+            "app",
+            "[dependencies]\nembeddenator-io = \"^0.20\"\nembeddenator-vsa = { workspace = true }\n\n[dev-dependencies]\nembeddenator-io = \"0.20.0\"\n",
+        ),
+        (
+            "tool",
+            "[dependencies]\nembeddenator-io = \"0.21.0\"\nembeddenator-fs = { path = \"../fs\" }\n",
+        ),
+    ] {
+        let pkg = temp_dir.path().join(name);
+        fs::create_dir_all(&pkg).unwrap();
+        fs::write(
+            pkg.join("Cargo.toml"),
+            format!(
+                "[package]\nname = \"{}\"\nversion = \"1.2.3\"\n\n{}",
+                name, deps
+            ),
+        )
+        .unwrap();
+    }
+
+    let target = Version::parse("0.21.0").unwrap();
+    let versions = ["embeddenator-io", "embeddenator-vsa", "embeddenator-fs"]
+        .into_iter()
+        .map(|name| (name.to_string(), target.clone()))
+        .collect();
+
+    let manager = VersionManager::new(temp_dir.path());
+    let changes = manager.align_dependencies_to(&versions, false).unwrap();
+    let summary: Vec<(&str, &str, &str)> = changes
+        .iter()
+        .map(|c| {
+            (
+                c.package.as_str(),
+                c.dependency.as_str(),
+                c.old_req.as_str(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        summary,
+        vec![
+            ("[workspace.dependencies]", "embeddenator-vsa", "0.20.0"),
+            ("app", "embeddenator-io", "^0.20"),
+        ]
+    );
+
+    let app = fs::read_to_string(temp_dir.path().join("app/Cargo.toml")).unwrap();
+    assert!(app.contains("version = \"1.2.3\""));
+    assert!(app.contains("[dependencies]\nembeddenator-io = \"0.21.0\""));
+    assert!(app.contains("[dev-dependencies]\nembeddenator-io = \"0.21.0\""));
+    assert!(app.contains("embeddenator-vsa = { workspace = true }"));
+    let root = fs::read_to_string(temp_dir.path().join("Cargo.toml")).unwrap();
+    assert!(root.contains("embeddenator-vsa = \"0.21.0\""));
+
+    assert!(manager
+        .align_dependencies_to(&versions, false)
+        .unwrap()
+        .is_empty());
+}
+
+#[test]
+fn test_align_to_highest() {
+    let temp_dir = TempDir::new().unwrap();
+    create_tagged_repo(&temp_dir, "embeddenator-vsa", "1.0.0", None);
+    create_tagged_repo(&temp_dir, "embeddenator-io", "0.20.0", None);
+    create_tagged_repo(&temp_dir, "embeddenator-fs", "1.0.0-rc.2", None);
+    fs::write(
+        temp_dir.path().join("embeddenator-vsa/Cargo.toml"),
+        "[package]\nname = \"embeddenator-vsa\"\nversion = \"1.0.0\"\n\n[dependencies]\nembeddenator-io = \"0.20.0\"\n",
+    )
+    .unwrap();
+    fs::write(
+        temp_dir.path().join("embeddenator-io/Cargo.toml"),
+        "[package]\nname = \"embeddenator-io\"\nversion = \"0.20.0\"\n\n[dependencies]\nembeddenator-vsa = \"0.9.0\"\n",
+    )
+    .unwrap();
+
+    let manager = VersionManager::new(temp_dir.path());
+    let (target, raised) = manager.align_to_highest(false).unwrap();
+    assert_eq!(target.to_string(), "1.0.0");
+    let names: Vec<&str> = raised.iter().map(|c| c.package.as_str()).collect();
+    assert_eq!(names, vec!["embeddenator-fs", "embeddenator-io"]);
+
+    // Stale requirements on the package already at the target move too
+    let io = fs::read_to_string(temp_dir.path().join("embeddenator-io/Cargo.toml")).unwrap();
+    assert!(io.contains("embeddenator-vsa = \"1.0.0\""));
+    let report = manager.check_consistency().unwrap();
+    assert!(!report.has_issues());
+}
+
+#[test]
+fn test_align_to_highest_conflicting_build() {
+    let temp_dir = TempDir::new().unwrap();
+    create_tagged_repo(&temp_dir, "embeddenator-vsa", "0.21.0+git.abc", None);
+    create_tagged_repo(&temp_dir, "embeddenator-io", "0.21.0+git.def", None);
+    create_tagged_repo(&temp_dir, "embeddenator-fs", "0.20.0", None);
+
+    let manager = VersionManager::new(temp_dir.path());
+    let err = manager.align_to_highest(true).unwrap_err();
+    assert!(err.to_string().contains("build metadata"));
+}
+
+#[test]
+fn test_check_consistency_with_prefix() {
+    let temp_dir = TempDir::new().unwrap();
+    create_tagged_repo(&temp_dir, "myorg-core", "0.3.0", None);
+    create_tagged_repo(&temp_dir, "myorg-cli", "0.3.0", None);
+    create_tagged_repo(&temp_dir, "embeddenator-vsa", "0.20.0", None);
+
+    let report = VersionManager::new(temp_dir.path())
+        .with_prefix("myorg-")
+        .check_consistency()
+        .unwrap();
+
+    let names: Vec<&str> = report
+        .packages
+        .iter()
+        .map(|(name, _, _)| name.as_str())
+        .collect();
+    assert_eq!(names, vec!["myorg-cli", "myorg-core"]);
+    assert!(!report.drift_detected);
+}
@@ -1,15 +1,24 @@
 //! Workspace scanning and repository discovery.
 
 use anyhow::{Context, Result};
-use std::path::{Path, PathBuf};
-use walkdir::WalkDir;
+use ignore::{overrides::OverrideBuilder, WalkBuilder};
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Component, Path, PathBuf};
+use toml_edit::DocumentMut;
 
-use crate::cargo::CargoManifest;
+use crate::cargo::{CargoManifest, DependencyType, WorkspaceDependencies};
+
+/// Package-name prefix used to pick out workspace packages by default.
+pub const DEFAULT_PACKAGE_PREFIX: &str = "embeddenator";
 
 /// Scans the workspace for Cargo.toml files.
 #[derive(Debug)]
 pub struct WorkspaceScanner {
     root: PathBuf,
+    default_members_only: bool,
+    prefix: String,
+    max_depth: Option<usize>,
+    excludes: Vec<String>,
 }
 
 impl WorkspaceScanner {
@@ -17,48 +26,209 @@ impl WorkspaceScanner {
     pub fn new(root: impl AsRef<Path>) -> Self {
         Self {
             root: root.as_ref().to_path_buf(),
+            default_members_only: false,
+            prefix: DEFAULT_PACKAGE_PREFIX.to_string(),
+            max_depth: None,
+            excludes: Vec::new(),
         }
     }
 
-    /// Find all Cargo.toml files in the workspace, excluding target/ and .git/ directories.
+    /// The workspace root directory.
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Only treat packages whose name starts with `prefix` as workspace
+    /// packages (default: [`DEFAULT_PACKAGE_PREFIX`]).
+    pub fn with_prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.prefix = prefix.into();
+        self
+    }
+
+    /// The package-name prefix workspace packages are matched by.
+    pub fn prefix(&self) -> &str {
+        &self.prefix
+    }
+
+    /// Only look for manifests up to `depth` directory levels below the root
+    /// (the root's own `Cargo.toml` is level 1). Unlimited by default.
+    pub fn with_max_depth(mut self, depth: usize) -> Self {
+        self.max_depth = Some(depth);
+        self
+    }
+
+    /// Skip paths matching any of these gitignore-style globs, relative to
+    /// the root (e.g. `fixtures/`, `**/generated`), in addition to
+    /// `.gitignore`d paths.
+    pub fn with_excludes<I, S>(mut self, patterns: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.excludes = patterns.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Restrict package discovery to `[workspace] default-members`.
+    ///
+    /// Has no effect when the root manifest doesn't declare `default-members`.
+    pub fn with_default_members(mut self, enabled: bool) -> Self {
+        self.default_members_only = enabled;
+        self
+    }
+
+    /// Find all Cargo.toml files in the workspace, excluding target/, .git/,
+    /// gitignored, and excluded paths.
+    ///
+    /// Manifests that fail to parse are skipped; use
+    /// [`find_manifests_with_errors`](Self::find_manifests_with_errors) to see them.
     pub fn find_manifests(&self) -> Result<Vec<CargoManifest>> {
+        let (manifests, errors) = self.find_manifests_with_errors();
+
+        // Unreadable directories and invalid excludes still fail the whole scan
+        for (_, error) in errors {
+            if error.downcast_ref::<ignore::Error>().is_some() {
+                return Err(error);
+            }
+        }
+
+        Ok(manifests)
+    }
+
+    /// Find all Cargo.toml files in the workspace, along with the path and
+    /// error of every manifest (or directory) that couldn't be read.
+    ///
+    /// Virtual manifests (a `[workspace]` without `[package]`) are not errors.
+    pub fn find_manifests_with_errors(
+        &self,
+    ) -> (Vec<CargoManifest>, Vec<(PathBuf, anyhow::Error)>) {
         let mut manifests = Vec::new();
+        let mut errors = Vec::new();
+        // An unreadable root is already reported when the walk reaches it
+        let root_deps = WorkspaceDependencies::load(self.root.join("Cargo.toml"))
+            .ok()
+            .flatten();
 
-        for entry in WalkDir::new(&self.root)
+        let mut walker = WalkBuilder::new(&self.root);
+        walker
             .follow_links(false)
-            .into_iter()
+            .max_depth(self.max_depth)
+            .hidden(false)
+            // Packages are often separate repositories under a plain directory
+            .require_git(false)
             .filter_entry(|e| {
                 let name = e.file_name().to_string_lossy();
                 // Skip target, .git, and other build directories
                 !matches!(name.as_ref(), "target" | ".git" | "node_modules" | ".cargo")
-            })
-        {
-            let entry = entry.context("Failed to read directory entry")?;
+            });
+
+        let mut overrides = OverrideBuilder::new(&self.root);
+        let excludes = self
+            .excludes
+            .iter()
+            .try_for_each(|pattern| overrides.add(&format!("!{}", pattern)).map(drop))
+            .and_then(|()| overrides.build());
+        match excludes {
+            Ok(excludes) => {
+                walker.overrides(excludes);
+            }
+            Err(e) => {
+                errors.push((
+                    self.root.clone(),
+                    anyhow::Error::new(e).context("Invalid exclude pattern"),
+                ));
+                return (manifests, errors);
+            }
+        }
 
-            if entry.file_type().is_file() && entry.file_name() == "Cargo.toml" {
-                match CargoManifest::load(entry.path()) {
-                    Ok(manifest) => manifests.push(manifest),
-                    Err(e) => {
-                        eprintln!("Warning: Failed to parse {}: {}", entry.path().display(), e);
+        let mut candidates = Vec::new();
+        for entry in walker.build() {
+            let entry = match entry {
+                Ok(entry) => entry,
+                Err(e) => {
+                    let path = error_path(&e).unwrap_or(&self.root).to_path_buf();
+                    errors.push((
+                        path,
+                        anyhow::Error::new(e).context("Failed to read directory entry"),
+                    ));
+                    continue;
+                }
+            };
+
+            if entry.file_type().is_some_and(|t| t.is_file()) && entry.file_name() == "Cargo.toml" {
+                candidates.push(entry.into_path());
+            }
+        }
+        candidates.sort();
+
+        for (path, loaded) in candidates.iter().zip(load_manifests(&candidates)) {
+            match loaded {
+                Ok(mut manifest) => {
+                    if let Some(root_deps) = &root_deps {
+                        manifest.inherit_workspace_dependencies(root_deps);
                     }
+                    manifests.push(manifest);
                 }
+                Err(_) if is_virtual_manifest(path) => {}
+                Err(e) => errors.push((path.clone(), e)),
             }
         }
 
-        Ok(manifests)
+        (manifests, errors)
     }
 
-    /// Find all embeddenator-* package manifests (excluding nested crates).
+    /// Find all package manifests matching the scanner's prefix.
+    ///
+    /// When the root Cargo.toml has a `[workspace]` table, only its resolved
+    /// [`members`](Self::workspace_members) count; otherwise nested crates
+    /// are excluded by path (see
+    /// [`find_packages_on_disk`](Self::find_packages_on_disk)).
     pub fn find_embeddenator_packages(&self) -> Result<Vec<CargoManifest>> {
-        let all_manifests = self.find_manifests()?;
+        let mut packages = match self.workspace_members()? {
+            Some(members) => {
+                let members: BTreeSet<PathBuf> =
+                    members.iter().map(|m| normalize_path(m)).collect();
+                self.find_manifests()?
+                    .into_iter()
+                    .filter(|m| {
+                        m.package_name.starts_with(&self.prefix)
+                            && m.path
+                                .parent()
+                                .is_some_and(|dir| members.contains(&normalize_path(dir)))
+                    })
+                    .collect()
+            }
+            None => self.find_packages_on_disk()?,
+        };
+
+        if self.default_members_only {
+            if let Some(defaults) = self.default_members()? {
+                let defaults: BTreeSet<PathBuf> =
+                    defaults.iter().map(|d| normalize_path(d)).collect();
+                packages.retain(|m| {
+                    m.path
+                        .parent()
+                        .is_some_and(|dir| defaults.contains(&normalize_path(dir)))
+                });
+            }
+        }
+
+        packages.sort_by(|a, b| a.package_name.cmp(&b.package_name));
+        Ok(packages)
+    }
 
-        // Filter for top-level embeddenator packages
+    /// Find all top-level package manifests matching the scanner's prefix,
+    /// whether or not they are `[workspace]` members.
+    ///
+    /// Crates nested under a `crates/` directory are excluded.
+    pub fn find_packages_on_disk(&self) -> Result<Vec<CargoManifest>> {
         // Exclude: embeddenator/crates/*, embeddenator/embeddenator-core/crates/*
-        let mut packages: Vec<CargoManifest> = all_manifests
+        let mut packages: Vec<CargoManifest> = self
+            .find_manifests()?
             .into_iter()
             .filter(|m| {
                 let path_str = m.path.to_string_lossy();
-                m.package_name.starts_with("embeddenator")
+                m.package_name.starts_with(&self.prefix)
                     && !path_str.contains("/crates/")
                     && !path_str.contains("/target/")
             })
@@ -67,4 +237,427 @@ impl WorkspaceScanner {
         packages.sort_by(|a, b| a.package_name.cmp(&b.package_name));
         Ok(packages)
     }
+
+    /// Map changed file paths to the embeddenator packages that own them.
+    ///
+    /// Relative paths are resolved against the workspace root. A file belongs
+    /// to the package with the closest enclosing manifest directory; files
+    /// outside every package are ignored.
+    pub fn packages_containing(&self, files: &[PathBuf]) -> Result<Vec<String>> {
+        Ok(self.partition_by_package(files)?.0)
+    }
+
+    /// Like [`WorkspaceScanner::packages_containing`], but also returns the
+    /// files that don't belong to any package.
+    pub fn partition_by_package(&self, files: &[PathBuf]) -> Result<(Vec<String>, Vec<PathBuf>)> {
+        let packages: Vec<(PathBuf, String)> = self
+            .find_embeddenator_packages()?
+            .into_iter()
+            .filter_map(|m| {
+                let dir = m.path.parent()?;
+                Some((normalize_path(dir), m.package_name))
+            })
+            .collect();
+
+        let mut owners = Vec::new();
+        let mut unowned = Vec::new();
+
+        for file in files {
+            let full = normalize_path(&self.root.join(file));
+            let owner = packages
+                .iter()
+                .filter(|(dir, _)| full.starts_with(dir))
+                .max_by_key(|(dir, _)| dir.components().count());
+
+            match owner {
+                Some((_, name)) => owners.push(name.clone()),
+                None => unowned.push(file.clone()),
+            }
+        }
+
+        owners.sort();
+        owners.dedup();
+        Ok((owners, unowned))
+    }
+
+    /// Files changed since `git_ref` in the repositories of every package,
+    /// including uncommitted and untracked changes.
+    ///
+    /// Each package may live in its own repository; `git_ref` must resolve in
+    /// all of them.
+    pub fn changed_files_since(&self, git_ref: &str) -> Result<Vec<PathBuf>> {
+        let mut repo_paths: Vec<PathBuf> = Vec::new();
+        for manifest in self.find_embeddenator_packages()? {
+            let pkg_dir = manifest.path.parent().unwrap_or_else(|| Path::new("."));
+            if let Ok(repo) = git2::Repository::discover(pkg_dir) {
+                let path = repo.path().to_path_buf();
+                if !repo_paths.contains(&path) {
+                    repo_paths.push(path);
+                }
+            }
+        }
+
+        if repo_paths.is_empty() {
+            anyhow::bail!("No git repositories found for embeddenator packages");
+        }
+
+        let mut files = Vec::new();
+        for repo_path in &repo_paths {
+            let repo = git2::Repository::open(repo_path)
+                .with_context(|| format!("Failed to open {}", repo_path.display()))?;
+            let Some(workdir) = repo.workdir() else {
+                continue;
+            };
+
+            let tree = repo
+                .revparse_single(git_ref)
+                .with_context(|| format!("Unknown git ref '{}' in {}", git_ref, workdir.display()))?
+                .peel_to_tree()?;
+            let mut options = git2::DiffOptions::new();
+            options.include_untracked(true).recurse_untracked_dirs(true);
+            let diff = repo.diff_tree_to_workdir_with_index(Some(&tree), Some(&mut options))?;
+
+            for delta in diff.deltas() {
+                for path in [delta.old_file().path(), delta.new_file().path()]
+                    .into_iter()
+                    .flatten()
+                {
+                    files.push(workdir.join(path));
+                }
+            }
+        }
+
+        files.sort();
+        files.dedup();
+        Ok(files)
+    }
+
+    /// Find package names (matching the scanner's prefix) declared by more
+    /// than one manifest, with every declaring manifest path.
+    ///
+    /// Nested crates are included; manifests that fail to parse are ignored.
+    pub fn duplicate_package_names(&self) -> Vec<(String, Vec<PathBuf>)> {
+        let mut by_name: BTreeMap<String, Vec<PathBuf>> = BTreeMap::new();
+        for manifest in self.find_manifests_with_errors().0 {
+            if manifest.package_name.starts_with(&self.prefix) {
+                by_name
+                    .entry(manifest.package_name)
+                    .or_default()
+                    .push(manifest.path);
+            }
+        }
+
+        by_name
+            .into_iter()
+            .filter(|(_, paths)| paths.len() > 1)
+            .collect()
+    }
+
+    /// Find dependency cycles among the embeddenator packages.
+    ///
+    /// Returns each strongly-connected component with more than one package,
+    /// names sorted. Dev-dependencies are ignored: cargo allows them to form
+    /// cycles, and they don't constrain build or publish order.
+    pub fn dependency_cycles(&self) -> Result<Vec<Vec<String>>> {
+        let edges = self.dependency_edges()?;
+        Ok(strongly_connected(&edges)
+            .into_iter()
+            .filter(|component| component.len() > 1)
+            .collect())
+    }
+
+    /// Embeddenator package names ordered so dependencies come before their
+    /// dependents, e.g. for `cargo publish`.
+    ///
+    /// Ties are broken by name. Dev-dependencies are ignored, as in
+    /// [`WorkspaceScanner::dependency_cycles`]; fails if a cycle remains.
+    pub fn publish_order(&self) -> Result<Vec<String>> {
+        let edges = self.dependency_edges()?;
+
+        if let Some(cycle) = strongly_connected(&edges)
+            .into_iter()
+            .find(|component| component.len() > 1)
+        {
+            anyhow::bail!("Dependency cycle between {}", cycle.join(", "));
+        }
+
+        let mut pending: BTreeMap<&str, usize> = edges
+            .iter()
+            .map(|(name, deps)| (name.as_str(), deps.len()))
+            .collect();
+        let mut ready: BTreeSet<&str> = pending
+            .iter()
+            .filter(|(_, count)| **count == 0)
+            .map(|(name, _)| *name)
+            .collect();
+
+        let mut order = Vec::with_capacity(edges.len());
+        while let Some(name) = ready.pop_first() {
+            pending.remove(name);
+            order.push(name.to_string());
+
+            for (dependent, deps) in &edges {
+                if deps.contains(name) {
+                    if let Some(count) = pending.get_mut(dependent.as_str()) {
+                        *count -= 1;
+                        if *count == 0 {
+                            ready.insert(dependent);
+                        }
+                    }
+                }
+            }
+        }
+
+        Ok(order)
+    }
+
+    /// Edges from each embeddenator package to the workspace packages it
+    /// depends on (normal and build dependencies only).
+    fn dependency_edges(&self) -> Result<BTreeMap<String, BTreeSet<String>>> {
+        let manifests = self.find_embeddenator_packages()?;
+        let names: BTreeSet<&str> = manifests.iter().map(|m| m.package_name.as_str()).collect();
+
+        Ok(manifests
+            .iter()
+            .map(|m| {
+                let deps = m
+                    .dependencies_with_prefix(&self.prefix)
+                    .into_iter()
+                    .filter(|d| d.dep_type != DependencyType::Dev)
+                    .filter(|d| names.contains(d.name.as_str()))
+                    .map(|d| d.name.clone())
+                    .collect();
+                (m.package_name.clone(), deps)
+            })
+            .collect())
+    }
+
+    /// Resolve the `[workspace] members` of the root Cargo.toml.
+    ///
+    /// Glob patterns in `members` and `exclude` are expanded relative to the
+    /// root and excluded directories are removed. Literal members are
+    /// returned even if they don't exist on disk.
+    /// Returns `None` if the root has no manifest or no `[workspace]` table.
+    pub fn workspace_members(&self) -> Result<Option<Vec<PathBuf>>> {
+        self.resolve_member_list("members")
+    }
+
+    /// Resolve the `[workspace] default-members` of the root Cargo.toml.
+    ///
+    /// Returns `None` if the root has no `[workspace]` table or it doesn't set
+    /// `default-members`, in which case all members are built by default.
+    pub fn default_members(&self) -> Result<Option<Vec<PathBuf>>> {
+        let has_defaults = self
+            .workspace_table()?
+            .is_some_and(|ws| ws.get("default-members").is_some());
+
+        if has_defaults {
+            self.resolve_member_list("default-members")
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// Load the root manifest's `[workspace]` table, if present.
+    fn workspace_table(&self) -> Result<Option<toml_edit::Item>> {
+        let manifest_path = self.root.join("Cargo.toml");
+        if !manifest_path.exists() {
+            return Ok(None);
+        }
+
+        let content = std::fs::read_to_string(&manifest_path)
+            .with_context(|| format!("Failed to read {}", manifest_path.display()))?;
+        let document: DocumentMut = content
+            .parse()
+            .with_context(|| format!("Failed to parse {}", manifest_path.display()))?;
+
+        Ok(document.get("workspace").cloned())
+    }
+
+    /// Expand a list of member paths under `[workspace]`, minus `exclude`.
+    fn resolve_member_list(&self, key: &str) -> Result<Option<Vec<PathBuf>>> {
+        let Some(workspace) = self.workspace_table()? else {
+            return Ok(None);
+        };
+
+        let patterns = |name: &str| -> Vec<String> {
+            workspace
+                .get(name)
+                .and_then(|v| v.as_array())
+                .map(|a| {
+                    a.iter()
+                        .filter_map(|v| v.as_str())
+                        .map(String::from)
+                        .collect()
+                })
+                .unwrap_or_default()
+        };
+
+        let expand = |patterns: Vec<String>| -> Result<Vec<PathBuf>> {
+            let mut paths = Vec::new();
+            for pattern in patterns {
+                if pattern.contains(['*', '?', '[']) {
+                    let full = self.root.join(&pattern);
+                    let matches = glob::glob(&full.to_string_lossy()).with_context(|| {
+                        format!("Invalid workspace member pattern '{}'", pattern)
+                    })?;
+                    for path in matches.flatten() {
+                        if path.is_dir() {
+                            paths.push(path);
+                        }
+                    }
+                } else {
+                    paths.push(self.member_path(&pattern));
+                }
+            }
+            Ok(paths)
+        };
+
+        let mut members = expand(patterns(key))?;
+        let excluded = expand(patterns("exclude"))?;
+        members.retain(|m| !excluded.contains(m));
+
+        members.sort();
+        members.dedup();
+        Ok(Some(members))
+    }
+
+    /// Join a member entry onto the root, dropping `.` components so `"."`
+    /// resolves to the root itself.
+    fn member_path(&self, member: &str) -> PathBuf {
+        self.root
+            .join(member)
+            .components()
+            .filter(|c| !matches!(c, Component::CurDir))
+            .collect()
+    }
+}
+
+/// Strongly-connected components of `edges` (Tarjan's algorithm), each sorted
+/// by name, in name order.
+fn strongly_connected(edges: &BTreeMap<String, BTreeSet<String>>) -> Vec<Vec<String>> {
+    struct Tarjan<'a> {
+        edges: &'a BTreeMap<String, BTreeSet<String>>,
+        index: BTreeMap<&'a str, usize>,
+        lowlink: BTreeMap<&'a str, usize>,
+        stack: Vec<&'a str>,
+        on_stack: BTreeSet<&'a str>,
+        components: Vec<Vec<String>>,
+    }
+
+    impl<'a> Tarjan<'a> {
+        fn visit(&mut self, node: &'a str) {
+            let index = self.index.len();
+            self.index.insert(node, index);
+            self.lowlink.insert(node, index);
+            self.stack.push(node);
+            self.on_stack.insert(node);
+
+            for next in self.edges.get(node).into_iter().flatten() {
+                let next = next.as_str();
+                if !self.index.contains_key(next) {
+                    self.visit(next);
+                    let low = self.lowlink[node].min(self.lowlink[next]);
+                    self.lowlink.insert(node, low);
+                } else if self.on_stack.contains(next) {
+                    let low = self.lowlink[node].min(self.index[next]);
+                    self.lowlink.insert(node, low);
+                }
+            }
+
+            if self.lowlink[node] == self.index[node] {
+                let mut component = Vec::new();
+                while let Some(member) = self.stack.pop() {
+                    self.on_stack.remove(member);
+                    component.push(member.to_string());
+                    if member == node {
+                        break;
+                    }
+                }
+                component.sort();
+                self.components.push(component);
+            }
+        }
+    }
+
+    let mut tarjan = Tarjan {
+        edges,
+        index: BTreeMap::new(),
+        lowlink: BTreeMap::new(),
+        stack: Vec::new(),
+        on_stack: BTreeSet::new(),
+        components: Vec::new(),
+    };
+    for node in edges.keys() {
+        if !tarjan.index.contains_key(node.as_str()) {
+            tarjan.visit(node);
+        }
+    }
+
+    let mut components = tarjan.components;
+    components.sort();
+    components
 }
+
+/// Make a path absolute and resolve symlinks where possible.
+///
+/// Deleted files can't be canonicalized, so fall back to canonicalizing the
+/// parent directory, then to a lexical absolute path.
+pub(crate) fn normalize_path(path: &Path) -> PathBuf {
+    if let Ok(canonical) = path.canonicalize() {
+        return canonical;
+    }
+
+    if let (Some(parent), Some(name)) = (path.parent(), path.file_name()) {
+        if let Ok(parent) = parent.canonicalize() {
+            return parent.join(name);
+        }
+    }
+
+    std::path::absolute(path)
+        .unwrap_or_else(|_| path.to_path_buf())
+        .components()
+        .filter(|c| !matches!(c, Component::CurDir))
+        .collect()
+}
+
+/// Load manifests on all available cores, keeping the order of `paths`.
+fn load_manifests(paths: &[PathBuf]) -> Vec<Result<CargoManifest>> {
+    let threads = std::thread::available_parallelism().map_or(1, |n| n.get());
+    let chunk_size = paths.len().div_ceil(threads).max(1);
+
+    std::thread::scope(|scope| {
+        let workers: Vec<_> = paths
+            .chunks(chunk_size)
+            .map(|chunk| scope.spawn(|| chunk.iter().map(CargoManifest::load).collect::<Vec<_>>()))
+            .collect();
+        workers
+            .into_iter()
+            .flat_map(|worker| worker.join().expect("manifest parsing panicked"))
+            .collect()
+    })
+}
+
+/// Whether `path` is a valid manifest with `[workspace]` but no `[package]`.
+fn is_virtual_manifest(path: &Path) -> bool {
+    std::fs::read_to_string(path)
+        .ok()
+        .and_then(|content| content.parse::<DocumentMut>().ok())
+        .is_some_and(|doc| doc.get("workspace").is_some() && doc.get("package").is_none())
+}
+
+/// The path a walk error refers to, if it names one.
+fn error_path(error: &ignore::Error) -> Option<&Path> {
+    match error {
+        ignore::Error::WithPath { path, .. } => Some(path),
+        ignore::Error::WithDepth { err, .. } | ignore::Error::WithLineNumber { err, .. } => {
+            error_path(err)
+        }
+        ignore::Error::Loop { child, .. } => Some(child),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+#[path = "workspace_tests.rs"]
+mod tests;
@@ -0,0 +1,399 @@
+use crate::workspace::WorkspaceScanner;
+use std::fs;
+use tempfile::TempDir;
+
+/// Write `embeddenator-<name>` packages with the given `[dependencies]`
+/// and `[dev-dependencies]` sections.
+fn create_workspace(packages: &[(&str, &str)]) -> TempDir {
+    let temp_dir = TempDir::new().unwrap();
+
+    for (name, deps) in packages {
+        let pkg = temp_dir.path().join(name);
+        fs::create_dir_all(&pkg).unwrap();
+        fs::write(
+            pkg.join("Cargo.toml"),
+            format!(
+                "[package]\nname = \"{}\"\nversion = \"0.20.0\"\nedition = \"2021\"\n\n{}",
+                name, deps
+            ),
+        )
+        .unwrap();
+    }
+
+    temp_dir
+}
+
+#[test]
+fn test_dependency_cycles() {
+    let temp_dir = create_workspace(&[
+        (
+            "embeddenator-a",
+            "[dependencies]\nembeddenator-b = \"0.20.0\"\n",
+        ),
+        (
+            "embeddenator-b",
+            "[dependencies]\nembeddenator-a = \"0.20.0\"\n",
+        ),
+        (
+            "embeddenator-c",
+            "[dependencies]\nembeddenator-a = \"0.20.0\"\n",
+        ),
+    ]);
+
+    let cycles = WorkspaceScanner::new(temp_dir.path())
+        .dependency_cycles()
+        .unwrap();
+    assert_eq!(cycles, vec![vec!["embeddenator-a", "embeddenator-b"]]);
+}
+
+#[test]
+fn test_dependency_cycles_ignore_dev_dependencies() {
+    let temp_dir = create_workspace(&[
+        (
+            "embeddenator-a",
+            "[dependencies]\nembeddenator-b = \"0.20.0\"\n",
+        ),
+        (
+            "embeddenator-b",
+            "[dev-dependencies]\nembeddenator-a = \"0.20.0\"\n",
+        ),
+    ]);
+
+    assert!(WorkspaceScanner::new(temp_dir.path())
+        .dependency_cycles()
+        .unwrap()
+        .is_empty());
+}
+
+#[test]
+fn test_publish_order() {
+    let temp_dir = create_workspace(&[
+        (
+            "embeddenator-fs",
+            "[dependencies]\nembeddenator-io = \"0.20.0\"\nembeddenator-vsa = \"0.20.0\"\n",
+        ),
+        (
+            "embeddenator-io",
+            "[dependencies]\nembeddenator-vsa = \"0.20.0\"\n",
+        ),
+        ("embeddenator-vsa", ""),
+        (
+            "embeddenator-cli",
+            "[build-dependencies]\nembeddenator-fs = \"0.20.0\"\n",
+        ),
+    ]);
+
+    let order = WorkspaceScanner::new(temp_dir.path())
+        .publish_order()
+        .unwrap();
+    assert_eq!(
+        order,
+        vec![
+            "embeddenator-vsa",
+            "embeddenator-io",
+            "embeddenator-fs",
+            "embeddenator-cli",
+        ]
+    );
+}
+
+#[test]
+fn test_publish_order_cycle() {
+    let temp_dir = create_workspace(&[
+        (
+            "embeddenator-a",
+            "[dependencies]\nembeddenator-b = \"0.20.0\"\n",
+        ),
+        (
+            "embeddenator-b",
+            "[dependencies]\nembeddenator-a = \"0.20.0\"\n",
+        ),
+    ]);
+
+    let err = WorkspaceScanner::new(temp_dir.path())
+        .publish_order()
+        .unwrap_err();
+    assert!(err.to_string().contains("embeddenator-a, embeddenator-b"));
+}
+
+#[test]
+fn test_inherited_dependencies_resolved() {
+    let temp_dir = create_workspace(&[
+        ("embeddenator-a", ""),
+        (
+            "embeddenator-b",
+            "[dependencies]\na = { workspace = true }\nserde = { workspace = true }\n",
+        ),
+    ]);
+    fs::write(
+        temp_dir.path().join("Cargo.toml"),
+        "[workspace]\nmembers = [\"embeddenator-a\", \"embeddenator-b\"]\n\n[workspace.dependencies]\na = { package = \"embeddenator-a\", version = \"0.20.0\" }\nserde = \"1.0\"\n",
+    )
+    .unwrap();
+
+    let packages = WorkspaceScanner::new(temp_dir.path())
+        .find_embeddenator_packages()
+        .unwrap();
+    let b = packages
+        .iter()
+        .find(|m| m.package_name == "embeddenator-b")
+        .unwrap();
+
+    let deps = b.embeddenator_dependencies();
+    assert_eq!(deps.len(), 1);
+    assert_eq!(deps[0].name, "embeddenator-a");
+    assert_eq!(deps[0].key, "a");
+    assert!(deps[0].workspace);
+    assert_eq!(deps[0].req.as_deref(), Some("0.20.0"));
+
+    let serde = b.dependencies.iter().find(|d| d.name == "serde").unwrap();
+    assert_eq!(serde.req.as_deref(), Some("1.0"));
+    assert!(serde.version.is_none());
+
+    let order = WorkspaceScanner::new(temp_dir.path())
+        .publish_order()
+        .unwrap();
+    assert_eq!(order, vec!["embeddenator-a", "embeddenator-b"]);
+}
+
+#[test]
+fn test_custom_package_prefix() {
+    let temp_dir = create_workspace(&[
+        ("myorg-core", ""),
+        ("myorg-cli", "[dependencies]\nmyorg-core = \"0.20.0\"\n"),
+        ("embeddenator-vsa", ""),
+    ]);
+
+    let default_names: Vec<String> = WorkspaceScanner::new(temp_dir.path())
+        .find_embeddenator_packages()
+        .unwrap()
+        .into_iter()
+        .map(|m| m.package_name)
+        .collect();
+    assert_eq!(default_names, vec!["embeddenator-vsa"]);
+
+    let scanner = WorkspaceScanner::new(temp_dir.path()).with_prefix("myorg-");
+    assert_eq!(scanner.prefix(), "myorg-");
+
+    let packages = scanner.find_embeddenator_packages().unwrap();
+    let names: Vec<&str> = packages.iter().map(|m| m.package_name.as_str()).collect();
+    assert_eq!(names, vec!["myorg-cli", "myorg-core"]);
+
+    let deps: Vec<&str> = packages[0]
+        .dependencies_with_prefix(scanner.prefix())
+        .iter()
+        .map(|d| d.name.as_str())
+        .collect();
+    assert_eq!(deps, vec!["myorg-core"]);
+    assert_eq!(
+        scanner.publish_order().unwrap(),
+        vec!["myorg-core", "myorg-cli"]
+    );
+}
+
+#[test]
+fn test_scanner_max_depth() {
+    let temp_dir = create_workspace(&[("embeddenator-top", "")]);
+    let deep = temp_dir.path().join("a/b/embeddenator-deep");
+    fs::create_dir_all(&deep).unwrap();
+    fs::write(
+        deep.join("Cargo.toml"),
+        "[package]\nname = \"embeddenator-deep\"\nversion = \"0.20.0\"\n",
+    )
+    .unwrap();
+
+    let names = |scanner: WorkspaceScanner| -> Vec<String> {
+        scanner
+            .find_embeddenator_packages()
+            .unwrap()
+            .into_iter()
+            .map(|m| m.package_name)
+            .collect()
+    };
+
+    assert_eq!(
+        names(WorkspaceScanner::new(temp_dir.path())),
+        vec!["embeddenator-deep", "embeddenator-top"]
+    );
+    assert_eq!(
+        names(WorkspaceScanner::new(temp_dir.path()).with_max_depth(2)),
+        vec!["embeddenator-top"]
+    );
+}
+
+#[test]
+fn test_find_manifests_with_errors() {
+    let temp_dir = create_workspace(&[("embeddenator-good", "")]);
+    fs::write(
+        temp_dir.path().join("Cargo.toml"),
+        "[workspace]\nmembers = [\"embeddenator-*\"]\n",
+    )
+    .unwrap();
+    let broken = temp_dir.path().join("embeddenator-broken");
+    fs::create_dir_all(&broken).unwrap();
+    fs::write(
+        broken.join("Cargo.toml"),
+        "[package]\nname = \"embeddenator-broken\"\n",
+    )
+    .unwrap();
+
+    let scanner = WorkspaceScanner::new(temp_dir.path());
+    let (manifests, errors) = scanner.find_manifests_with_errors();
+    assert_eq!(manifests.len(), 1);
+    assert_eq!(manifests[0].package_name, "embeddenator-good");
+
+    // The virtual root manifest is not an error
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].0, broken.join("Cargo.toml"));
+    assert!(errors[0].1.to_string().contains("Missing package.version"));
+
+    // The convenience method skips parse failures
+    assert_eq!(scanner.find_manifests().unwrap().len(), 1);
+}
+
+#[test]
+fn test_scanner_skips_gitignored_and_excluded_paths() {
+    let temp_dir = create_workspace(&[("embeddenator-core", ""), ("embeddenator-gen", "")]);
+    let fixtures = temp_dir.path().join("fixtures/embeddenator-fixture");
+    fs::create_dir_all(&fixtures).unwrap();
+    fs::write(
+        fixtures.join("Cargo.toml"),
+        "[package]\nname = \"embeddenator-fixture\"\nversion = \"0.1.0\"\n",
+    )
+    .unwrap();
+    fs::write(temp_dir.path().join(".gitignore"), "fixtures/\n").unwrap();
+
+    let names = |scanner: WorkspaceScanner| -> Vec<String> {
+        scanner
+            .find_manifests()
+            .unwrap()
+            .into_iter()
+            .map(|m| m.package_name)
+            .collect::<std::collections::BTreeSet<_>>()
+            .into_iter()
+            .collect()
+    };
+
+    assert_eq!(
+        names(WorkspaceScanner::new(temp_dir.path())),
+        vec!["embeddenator-core", "embeddenator-gen"]
+    );
+    assert_eq!(
+        names(WorkspaceScanner::new(temp_dir.path()).with_excludes(["embeddenator-gen"])),
+        vec!["embeddenator-core"]
+    );
+
+    let invalid = WorkspaceScanner::new(temp_dir.path()).with_excludes(["a/**b["]);
+    assert!(invalid.find_manifests().is_err());
+}
+
+#[test]
+fn test_find_manifests_large_workspace() {
+    let names: Vec<String> = (0..50)
+        .map(|i| format!("embeddenator-crate{:02}", i))
+        .collect();
+    let packages: Vec<(&str, &str)> = names.iter().map(|n| (n.as_str(), "")).collect();
+    let temp_dir = create_workspace(&packages);
+    let broken = temp_dir.path().join("embeddenator-broken");
+    fs::create_dir_all(&broken).unwrap();
+    fs::write(broken.join("Cargo.toml"), "[package\n").unwrap();
+
+    let scanner = WorkspaceScanner::new(temp_dir.path());
+    let (manifests, errors) = scanner.find_manifests_with_errors();
+    assert_eq!(manifests.len(), 50);
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].0, broken.join("Cargo.toml"));
+
+    let found: Vec<String> = scanner
+        .find_embeddenator_packages()
+        .unwrap()
+        .into_iter()
+        .map(|m| m.package_name)
+        .collect();
+    assert_eq!(found, names);
+}
+
+#[test]
+fn test_packages_follow_workspace_members() {
+    let temp_dir = create_workspace(&[
+        ("embeddenator-a", ""),
+        ("embeddenator-b", ""),
+        ("embeddenator-old", ""),
+    ]);
+    let nested = temp_dir
+        .path()
+        .join("embeddenator-a/crates/embeddenator-nested");
+    fs::create_dir_all(&nested).unwrap();
+    fs::write(
+        nested.join("Cargo.toml"),
+        "[package]\nname = \"embeddenator-nested\"\nversion = \"0.20.0\"\n",
+    )
+    .unwrap();
+
+    let names = |scanner: &WorkspaceScanner| -> Vec<String> {
+        scanner
+            .find_embeddenator_packages()
+            .unwrap()
+            .into_iter()
+            .map(|m| m.package_name)
+            .collect()
+    };
+
+    // Without [workspace], nested crates are excluded by path
+    let scanner = WorkspaceScanner::new(temp_dir.path());
+    assert_eq!(
+        names(&scanner),
+        vec!["embeddenator-a", "embeddenator-b", "embeddenator-old"]
+    );
+
+    fs::write(
+        temp_dir.path().join("Cargo.toml"),
+        "[workspace]\nmembers = [\"embeddenator-*\", \"embeddenator-a/crates/*\"]\nexclude = [\"embeddenator-o*\"]\n",
+    )
+    .unwrap();
+    assert_eq!(
+        names(&scanner),
+        vec!["embeddenator-a", "embeddenator-b", "embeddenator-nested"]
+    );
+
+    // Excluded directories are not members
+    let members = scanner.workspace_members().unwrap().unwrap();
+    assert_eq!(members.len(), 3);
+    assert!(!members.contains(&temp_dir.path().join("embeddenator-old")));
+
+    let on_disk: Vec<String> = scanner
+        .find_packages_on_disk()
+        .unwrap()
+        .into_iter()
+        .map(|m| m.package_name)
+        .collect();
+    assert_eq!(
+        on_disk,
+        vec!["embeddenator-a", "embeddenator-b", "embeddenator-old"]
+    );
+}
+
+#[test]
+fn test_duplicate_package_names() {
+    let temp_dir = create_workspace(&[("embeddenator-vsa", ""), ("embeddenator-io", "")]);
+    let copy = temp_dir.path().join("vsa-copy");
+    fs::create_dir_all(&copy).unwrap();
+    fs::copy(
+        temp_dir.path().join("embeddenator-vsa/Cargo.toml"),
+        copy.join("Cargo.toml"),
+    )
+    .unwrap();
+
+    let duplicates = WorkspaceScanner::new(temp_dir.path()).duplicate_package_names();
+    assert_eq!(duplicates.len(), 1);
+    let (name, mut paths) = duplicates.into_iter().next().unwrap();
+    assert_eq!(name, "embeddenator-vsa");
+    paths.sort();
+    assert_eq!(
+        paths,
+        vec![
+            temp_dir.path().join("embeddenator-vsa/Cargo.toml"),
+            copy.join("Cargo.toml"),
+        ]
+    );
+}
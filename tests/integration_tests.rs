@@ -93,6 +93,25 @@ edition = "2021"
 
     let stdout = String::from_utf8_lossy(&output.stdout);
     assert!(stdout.contains("Dependency Inconsistencies"));
+
+    let output = Command::new(env!("CARGO_BIN_EXE_embeddenator-workspace"))
+        .args(["check-versions", "--json"])
+        .current_dir(workspace.path())
+        .output()
+        .unwrap();
+
+    assert_eq!(output.status.code(), Some(1));
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(report["total_packages"], 2);
+    assert_eq!(
+        report["inconsistencies"],
+        serde_json::json!([{
+            "package": "embeddenator-pkg1",
+            "dependency": "embeddenator-pkg2",
+            "expected": "0.21.0",
+            "found": "0.20.0-alpha.1",
+        }])
+    );
 }
 
 #[test]
@@ -136,3 +155,464 @@ fn test_bump_version_actual() {
     let pkg2_content = fs::read_to_string(workspace.path().join("pkg2/Cargo.toml")).unwrap();
     assert!(pkg2_content.contains("version = \"0.20.0-alpha.2\""));
 }
+
+#[test]
+fn test_patch_local_discover_only() {
+    let workspace = TempDir::new().unwrap();
+
+    let vsa = workspace.path().join("embeddenator-vsa");
+    let main = workspace.path().join("embeddenator");
+    fs::create_dir_all(&vsa).unwrap();
+    fs::create_dir_all(&main).unwrap();
+
+    fs::write(
+        vsa.join("Cargo.toml"),
+        r#"[package]
+name = "embeddenator-vsa"
+version = "0.1.0"
+edition = "2021"
+"#,
+    )
+    .unwrap();
+
+    fs::write(
+        main.join("Cargo.toml"),
+        r#"[package]
+name = "embeddenator"
+version = "0.20.0"
+edition = "2021"
+
+[dependencies]
+embeddenator-vsa = { git = "https://github.com/tzervas/embeddenator-vsa", tag = "v0.1.0" }
+"#,
+    )
+    .unwrap();
+
+    let output = Command::new(env!("CARGO_BIN_EXE_embeddenator-workspace"))
+        .args([
+            "patch-local",
+            "--discover-only",
+            "--json",
+            "--workspace-root",
+        ])
+        .arg(workspace.path())
+        .output()
+        .unwrap();
+
+    assert_eq!(output.status.code(), Some(0));
+
+    let deps: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(deps[0]["name"], "embeddenator-vsa");
+    assert_eq!(deps[0]["branch_or_tag"], "v0.1.0");
+    assert!(deps[0]["rev"].is_null());
+
+    // Nothing was written
+    assert!(!workspace.path().join(".cargo/config.toml").exists());
+}
+
+#[test]
+fn test_affected_changed_files() {
+    let workspace = create_test_workspace();
+    fs::write(workspace.path().join("update_all.sh"), "").unwrap();
+
+    let list = workspace.path().join("changed.txt");
+    fs::write(&list, "pkg2/src/lib.rs\n\nREADME.md\n").unwrap();
+
+    let output = Command::new(env!("CARGO_BIN_EXE_embeddenator-workspace"))
+        .current_dir(workspace.path())
+        .args(["affected", "--changed-files"])
+        .arg(&list)
+        .output()
+        .unwrap();
+
+    assert_eq!(output.status.code(), Some(0));
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "embeddenator-pkg2\n"
+    );
+
+    // README.md is outside every package, so everything is affected
+    let output = Command::new(env!("CARGO_BIN_EXE_embeddenator-workspace"))
+        .current_dir(workspace.path())
+        .args(["affected", "--all-if-unowned", "--changed-files"])
+        .arg(&list)
+        .output()
+        .unwrap();
+
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "embeddenator-pkg1\nembeddenator-pkg2\n"
+    );
+}
+
+#[test]
+fn test_patch_local_refused_in_ci() {
+    let workspace = create_test_workspace();
+
+    let output = Command::new(env!("CARGO_BIN_EXE_embeddenator-workspace"))
+        .env("EMBEDDENATOR_TEST_CI", "true")
+        .args([
+            "patch-local",
+            "--ci-env",
+            "EMBEDDENATOR_TEST_CI",
+            "--workspace-root",
+        ])
+        .arg(workspace.path())
+        .output()
+        .unwrap();
+
+    assert_eq!(output.status.code(), Some(1));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("EMBEDDENATOR_TEST_CI is set"));
+    assert!(!workspace.path().join(".cargo").exists());
+
+    let output = Command::new(env!("CARGO_BIN_EXE_embeddenator-workspace"))
+        .env("EMBEDDENATOR_TEST_CI", "true")
+        .args([
+            "patch-local",
+            "--ci-env",
+            "EMBEDDENATOR_TEST_CI",
+            "--force",
+            "--workspace-root",
+        ])
+        .arg(workspace.path())
+        .output()
+        .unwrap();
+
+    assert_eq!(output.status.code(), Some(0));
+}
+
+#[test]
+fn test_list() {
+    let workspace = create_test_workspace();
+
+    let output = Command::new(env!("CARGO_BIN_EXE_embeddenator-workspace"))
+        .arg("list")
+        .current_dir(workspace.path())
+        .output()
+        .unwrap();
+
+    assert_eq!(output.status.code(), Some(0));
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "embeddenator-pkg1  0.20.0-alpha.1  pkg1\nembeddenator-pkg2  0.20.0-alpha.1  pkg2\n"
+    );
+
+    let output = Command::new(env!("CARGO_BIN_EXE_embeddenator-workspace"))
+        .args(["list", "--json"])
+        .current_dir(workspace.path())
+        .output()
+        .unwrap();
+
+    assert_eq!(output.status.code(), Some(0));
+    let packages: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(
+        packages,
+        serde_json::json!([
+            { "name": "embeddenator-pkg1", "version": "0.20.0-alpha.1", "path": "pkg1" },
+            { "name": "embeddenator-pkg2", "version": "0.20.0-alpha.1", "path": "pkg2" },
+        ])
+    );
+}
+
+#[test]
+fn test_global_workspace_root() {
+    let workspace = create_test_workspace();
+    let elsewhere = TempDir::new().unwrap();
+    let run = |args: &[&str]| {
+        let output = Command::new(env!("CARGO_BIN_EXE_embeddenator-workspace"))
+            .args(args)
+            .current_dir(elsewhere.path())
+            .output()
+            .unwrap();
+        assert_eq!(output.status.code(), Some(0));
+        String::from_utf8_lossy(&output.stdout).into_owned()
+    };
+
+    // Accepted before or after the subcommand
+    let root = workspace.path().to_str().unwrap();
+    let stdout = run(&["--workspace-root", root, "order"]);
+    assert_eq!(stdout, "embeddenator-pkg2\nembeddenator-pkg1\n");
+
+    let stdout = run(&["check-versions", "--workspace-root", root]);
+    assert!(stdout.contains("2 package(s) scanned"));
+}
+
+#[test]
+fn test_order() {
+    let workspace = create_test_workspace();
+
+    let output = Command::new(env!("CARGO_BIN_EXE_embeddenator-workspace"))
+        .arg("order")
+        .current_dir(workspace.path())
+        .output()
+        .unwrap();
+
+    assert_eq!(output.status.code(), Some(0));
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "embeddenator-pkg2\nembeddenator-pkg1\n"
+    );
+}
+
+#[test]
+fn test_patch_retarget() {
+    let workspace = create_test_workspace();
+    let manifest = workspace.path().join("pkg1/Cargo.toml");
+    let content = fs::read_to_string(&manifest).unwrap();
+    fs::write(
+        &manifest,
+        content.replace(
+            "embeddenator-pkg2 = \"0.20.0-alpha.1\"",
+            "embeddenator-pkg2 = { git = \"https://github.com/tzervas/embeddenator-pkg2\", branch = \"main\" }",
+        ),
+    )
+    .unwrap();
+
+    let output = Command::new(env!("CARGO_BIN_EXE_embeddenator-workspace"))
+        .args([
+            "patch-retarget",
+            "embeddenator-pkg2",
+            "--tag",
+            "v0.2.0",
+            "--workspace-root",
+        ])
+        .arg(workspace.path())
+        .output()
+        .unwrap();
+
+    assert_eq!(output.status.code(), Some(0));
+    let content = fs::read_to_string(&manifest).unwrap();
+    assert!(content.contains(
+        "embeddenator-pkg2 = { git = \"https://github.com/tzervas/embeddenator-pkg2\", tag = \"v0.2.0\" }"
+    ));
+}
+
+#[test]
+fn test_patch_local_dry_run() {
+    let workspace = create_test_workspace();
+    let manifest = workspace.path().join("pkg1/Cargo.toml");
+    let content = fs::read_to_string(&manifest).unwrap();
+    fs::write(
+        &manifest,
+        content.replace(
+            "embeddenator-pkg2 = \"0.20.0-alpha.1\"",
+            "embeddenator-pkg2 = { git = \"https://github.com/tzervas/embeddenator-pkg2\" }",
+        ),
+    )
+    .unwrap();
+    fs::rename(
+        workspace.path().join("pkg2"),
+        workspace.path().join("embeddenator-pkg2"),
+    )
+    .unwrap();
+
+    let output = Command::new(env!("CARGO_BIN_EXE_embeddenator-workspace"))
+        .args(["patch-local", "--dry-run", "--workspace-root"])
+        .arg(workspace.path())
+        .env_remove("CI")
+        .output()
+        .unwrap();
+
+    assert_eq!(output.status.code(), Some(0));
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("https://github.com/tzervas/embeddenator-pkg2"));
+    assert!(stdout.contains("Dry run"));
+    assert!(!workspace.path().join(".cargo").exists());
+}
+
+#[test]
+fn test_color_output() {
+    let workspace = create_test_workspace();
+    let run = |args: &[&str], envs: &[(&str, &str)]| {
+        let output = Command::new(env!("CARGO_BIN_EXE_embeddenator-workspace"))
+            .args(args)
+            .env_remove("NO_COLOR")
+            .envs(envs.iter().copied())
+            .current_dir(workspace.path())
+            .output()
+            .unwrap();
+        assert_eq!(output.status.code(), Some(0));
+        String::from_utf8_lossy(&output.stdout).contains('\x1b')
+    };
+
+    // Piped output is plain unless colors are forced
+    assert!(!run(&["check-versions"], &[]));
+    assert!(run(&["check-versions"], &[("CLICOLOR_FORCE", "1")]));
+    assert!(!run(
+        &["check-versions", "--no-color"],
+        &[("CLICOLOR_FORCE", "1")]
+    ));
+    assert!(!run(
+        &["check-versions"],
+        &[("CLICOLOR_FORCE", "1"), ("NO_COLOR", "1")]
+    ));
+}
+
+#[test]
+fn test_doctor() {
+    let workspace = create_test_workspace();
+    fs::write(
+        workspace.path().join("Cargo.toml"),
+        "[workspace]\nmembers = [\"pkg1\"]\n",
+    )
+    .unwrap();
+
+    let output = Command::new(env!("CARGO_BIN_EXE_embeddenator-workspace"))
+        .args(["doctor", "--check", "version,members"])
+        .current_dir(workspace.path())
+        .output()
+        .unwrap();
+
+    // pkg2 is not a member: a warning, so the exit code stays 0
+    assert_eq!(output.status.code(), Some(0));
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("1 problem(s) to fix"));
+    assert!(stdout.contains("Add the listed packages to `[workspace] members`"));
+}
+
+#[test]
+fn test_completions() {
+    for shell in ["bash", "zsh", "fish", "powershell"] {
+        let output = Command::new(env!("CARGO_BIN_EXE_embeddenator-workspace"))
+            .args(["completions", shell])
+            .output()
+            .unwrap();
+
+        assert_eq!(output.status.code(), Some(0));
+        let script = String::from_utf8_lossy(&output.stdout);
+        assert!(script.contains("embeddenator-workspace"), "{}", shell);
+        assert!(script.contains("check-versions"), "{}", shell);
+    }
+}
+
+#[test]
+fn test_health_compare_reads_saved_json() {
+    let workspace = create_test_workspace();
+    let run = |extra: &[&str]| {
+        Command::new(env!("CARGO_BIN_EXE_embeddenator-workspace"))
+            .args(["health", "--check", "version", "--json"])
+            .args(extra)
+            .arg("--workspace-root")
+            .arg(workspace.path())
+            .output()
+            .unwrap()
+    };
+
+    let saved = workspace.path().join("health-report.json");
+    let output = run(&[]);
+    assert_eq!(output.status.code(), Some(0));
+    fs::write(&saved, &output.stdout).unwrap();
+
+    let output = run(&["--compare", saved.to_str().unwrap()]);
+    assert_eq!(
+        output.status.code(),
+        Some(0),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let diff: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert!(diff.is_object());
+
+    let output = run(&["--by-owner", "--compare", saved.to_str().unwrap()]);
+    let combined: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert!(combined.get("by_owner").is_some());
+    assert_eq!(combined["diff"], diff);
+}
+
+#[test]
+fn test_default_members_with_relative_root() {
+    let workspace = create_test_workspace();
+    fs::write(
+        workspace.path().join("Cargo.toml"),
+        "[workspace]\nmembers = [\"pkg1\", \"pkg2\"]\ndefault-members = [\"pkg2\"]\n",
+    )
+    .unwrap();
+    let run = |args: &[&str]| {
+        Command::new(env!("CARGO_BIN_EXE_embeddenator-workspace"))
+            .args(["--workspace-root", "."])
+            .args(args)
+            .current_dir(workspace.path())
+            .output()
+            .unwrap()
+    };
+
+    let output = run(&["check-versions", "--default-members", "--json"]);
+    assert_eq!(output.status.code(), Some(0));
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(report["total_packages"], 1);
+    assert_eq!(report["packages"][0][0], "embeddenator-pkg2");
+
+    let output = run(&[
+        "health",
+        "--check",
+        "version",
+        "--default-members",
+        "--json",
+    ]);
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(
+        report["checks"][0]["message"],
+        "All 1 packages have consistent versions"
+    );
+}
+
+#[test]
+fn test_members_check_with_relative_root() {
+    let workspace = TempDir::new().unwrap();
+    for name in ["embeddenator-a", "embeddenator-b"] {
+        let dir = workspace.path().join(name);
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(
+            dir.join("Cargo.toml"),
+            format!("[package]\nname = \"{}\"\nversion = \"0.1.0\"\n", name),
+        )
+        .unwrap();
+    }
+    fs::write(
+        workspace.path().join("Cargo.toml"),
+        "[workspace]\nmembers = [\"embeddenator-a\", \"embeddenator-b\"]\n",
+    )
+    .unwrap();
+
+    let output = Command::new(env!("CARGO_BIN_EXE_embeddenator-workspace"))
+        .args([
+            "--workspace-root",
+            ".",
+            "health",
+            "--check",
+            "members",
+            "--json",
+        ])
+        .current_dir(workspace.path())
+        .output()
+        .unwrap();
+
+    assert_eq!(output.status.code(), Some(0));
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(report["checks"][0]["status"], "pass");
+    assert_eq!(
+        report["checks"][0]["message"],
+        "All 2 workspace members are present"
+    );
+}
+
+#[test]
+fn test_health_fail_on_levels() {
+    let workspace = create_test_workspace();
+    let run = |level: &str| {
+        Command::new(env!("CARGO_BIN_EXE_embeddenator-workspace"))
+            .args(["health", "--check", "version", "--fail-on", level])
+            .arg("--workspace-root")
+            .arg(workspace.path())
+            .output()
+            .unwrap()
+    };
+
+    assert_eq!(run("warn").status.code(), Some(0));
+    assert_eq!(run("fail").status.code(), Some(0));
+
+    // Anything else is a usage error rather than a guaranteed red run
+    let output = run("pass");
+    assert_eq!(output.status.code(), Some(2));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("possible values: warn, fail"));
+}